@@ -26,6 +26,15 @@ const TERMINAL16X16_GS_RO_PATH: &str = "terminal16x16_gs_ro.png";
 static TERMINAL16X16_GS_RO_DATA: &[u8] =
     include_bytes!("../assets/terminal16x16_gs_ro.png");
 
+/// Recipe definitions, see [RecipeBook](crate::data::RecipeBook).
+pub const RECIPES: &str = include_str!("../assets/recipes.ron");
+/// Backstories and traits, see [Backgrounds](crate::data::Backgrounds).
+pub const BACKGROUNDS: &str = include_str!("../assets/backgrounds.ron");
+/// Word lists of the name generator, see [Names](crate::data::Names).
+pub const NAMES: &str = include_str!("../assets/names.ron");
+/// Research projects, see [TechTree](crate::data::TechTree).
+pub const RESEARCH: &str = include_str!("../assets/research.ron");
+
 static INIT: Once = Once::new();
 
 pub enum Assets {
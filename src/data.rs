@@ -15,18 +15,97 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
+pub use background::{Background, Backgrounds};
+pub use bill::Bill;
+pub use combat::{cover_tiles, has_line_of_fire, line, Attack};
+pub use corpse::{rot_thought, Disposal};
+pub use crop::{is_growing_season, Crop};
 pub use direction::Direction;
+pub use equipment::{
+    Durability, Equipped, Loadout, Slot, COMFORTABLE_TEMPERATURE,
+};
+pub use faction::{gift_goodwill, trade_goodwill, Factions, Relation};
+pub use food::MealQuality;
+pub use freshness::{spoilage_rate, Freshness};
+pub use health::{BodyPart, Injuries, InjuryKind, Treatment};
+pub use item::ItemKind;
 pub use location::Location;
+pub use mental_break::{MentalBreak, MentalBreakKind, MENTAL_BREAK_THRESHOLD};
+pub use names::{unique_name, Culture, Names};
 pub use object::VisibleObject;
+pub use outcome::{Goal, Outcome};
+pub use path::find_path;
 pub use pause::Pause;
 pub use probability::ProbabilityTable;
+pub use quest::{
+    days_until_next_quest, Objective, Quest, QuestStatus, FIRST_QUEST_DAY,
+    MAX_ACTIVE_QUESTS,
+};
+pub use raid::{
+    arrival_point, is_beaten, raid_size, raider_weapons, RaiderPlan,
+    FIRST_RAID_DAY,
+};
+pub use random::Random;
+pub use recipe::{Recipe, RecipeBook, WorkbenchKind};
 pub use rectangle::Rectangle;
-pub use time::Time;
+pub use research::{Research, TechTree, Unlock};
+pub use room::{RoomOverlay, RoomQuality, Rooms};
+pub use schedule::{Activity, Schedule, HOURS_PER_DAY};
+pub use season::{Season, DAYS_PER_SEASON};
+pub use skill::{SkillKind, Skills, MAX_SKILL_LEVEL};
+pub use social::{InteractionKind, Opinions};
+pub use storyteller::{
+    herd_size, resource_drops, wanderer, Personality, StoryEvent, Storyteller,
+};
+pub use structure::Structure;
+pub use temperature::{indoor_temperature, outdoor_temperature};
+pub use thought::{ThoughtKind, Thoughts};
+pub use time::{Time, MILLIS_PER_DAY, MILLIS_PER_HOUR, MILLIS_PER_MINUTE};
+pub use trade::{
+    buy_price, condition, days_until_next_caravan, sell_price, TraderKind,
+    CARAVAN_CAMP_HOURS, FIRST_CARAVAN_DAY,
+};
+pub use wildlife::{
+    AnimalKind, AnimalState, Biome, Biomes, Designation, Temperament,
+};
+pub use work::{WorkPriorities, WorkType};
 
+mod background;
+mod bill;
+mod combat;
+mod corpse;
+mod crop;
 mod direction;
+mod equipment;
+mod faction;
+mod food;
+mod freshness;
+mod health;
+mod item;
 mod location;
+mod mental_break;
+mod names;
 mod object;
+mod outcome;
+mod path;
 mod pause;
 mod probability;
+mod quest;
+mod raid;
+mod random;
+mod recipe;
 mod rectangle;
+mod research;
+mod room;
+mod schedule;
+mod season;
+mod skill;
+mod social;
+mod storyteller;
+mod structure;
+mod temperature;
+mod thought;
 mod time;
+mod trade;
+mod wildlife;
+mod work;
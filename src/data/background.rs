@@ -0,0 +1,290 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Random, SkillKind, WorkType, MAX_SKILL_LEVEL};
+use ron::de;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const MIN_AGE: u8 = 18;
+const MAX_AGE: u8 = 60;
+/// Colonists get a level in their adulthood skills every this many years
+/// over [MIN_AGE].
+const YEARS_PER_LEVEL: u8 = 10;
+const MAX_TRAITS: u32 = 3;
+
+/// What a colonist did as a child or as an adult.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Backstory {
+    pub title: String,
+    pub skills: Vec<(SkillKind, u8)>,
+    /// Work the colonist will not do.
+    pub disabled: Vec<WorkType>,
+}
+
+/// A trait of character, see `assets/backgrounds.ron` for what the fields
+/// mean.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Trait {
+    pub name: String,
+    pub description: String,
+    pub skills: Vec<(SkillKind, i8)>,
+    pub food: f32,
+    pub rest: f32,
+    pub damage: f32,
+    pub mood: i32,
+    pub sleeps_by_day: bool,
+    /// Names of the traits this one cannot be picked with.
+    pub conflicts: Vec<String>,
+}
+
+/// Age, backstories and traits of a colonist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Background {
+    pub age: u8,
+    pub childhood: Backstory,
+    pub adulthood: Backstory,
+    pub traits: Vec<Trait>,
+}
+
+impl Background {
+    /// Skill levels from the backstories, age and traits.
+    pub fn skills(&self) -> Vec<(SkillKind, u8)> {
+        let mut levels: BTreeMap<SkillKind, i32> = BTreeMap::new();
+        let experience =
+            (self.age.saturating_sub(MIN_AGE) / YEARS_PER_LEVEL) as i32;
+        for (kind, level) in self.childhood.skills.iter() {
+            *levels.entry(*kind).or_default() += i32::from(*level);
+        }
+        for (kind, level) in self.adulthood.skills.iter() {
+            *levels.entry(*kind).or_default() +=
+                i32::from(*level) + experience;
+        }
+        for (kind, level) in self.traits.iter().flat_map(|t| t.skills.iter()) {
+            *levels.entry(*kind).or_default() += i32::from(*level);
+        }
+        levels
+            .into_iter()
+            .map(|(kind, level)| {
+                (kind, level.clamp(0, i32::from(MAX_SKILL_LEVEL)) as u8)
+            })
+            .collect()
+    }
+
+    /// Work types disabled by the backstories, in [WorkType::ALL] order.
+    pub fn disabled(&self) -> Vec<WorkType> {
+        WorkType::ALL
+            .iter()
+            .filter(|work| {
+                self.childhood.disabled.contains(work)
+                    || self.adulthood.disabled.contains(work)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// How fast the colonist gets hungry, `1.0` being normal.
+    pub fn food_rate(&self) -> f32 {
+        (1.0 + self.traits.iter().map(|t| t.food).sum::<f32>()).max(0.0)
+    }
+
+    /// How fast the colonist gets tired, `1.0` being normal.
+    pub fn rest_rate(&self) -> f32 {
+        (1.0 + self.traits.iter().map(|t| t.rest).sum::<f32>()).max(0.0)
+    }
+
+    /// Multiplier of the damage the colonist takes.
+    pub fn damage_taken(&self) -> f32 {
+        (1.0 + self.traits.iter().map(|t| t.damage).sum::<f32>()).max(0.0)
+    }
+
+    /// Permanent change to the mood.
+    pub fn mood(&self) -> i32 {
+        self.traits.iter().map(|t| t.mood).sum()
+    }
+
+    pub fn sleeps_by_day(&self) -> bool {
+        self.traits.iter().any(|t| t.sleeps_by_day)
+    }
+}
+
+/// All the backstories and traits colonists can be generated with.
+///
+/// They are not hard-coded, they are read from `assets/backgrounds.ron`.
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct Backgrounds {
+    childhoods: Vec<Backstory>,
+    adulthoods: Vec<Backstory>,
+    traits: Vec<Trait>,
+}
+
+impl Backgrounds {
+    pub fn from_ron(source: &str) -> Result<Backgrounds, de::Error> {
+        let backgrounds: Backgrounds = de::from_str(source)?;
+        for t in backgrounds.traits.iter() {
+            for name in t.conflicts.iter() {
+                if !backgrounds.traits.iter().any(|other| other.name == *name)
+                {
+                    return Err(de::Error::Message(format!(
+                        "trait {} conflicts with unknown trait {}",
+                        t.name, name
+                    )));
+                }
+            }
+        }
+        Ok(backgrounds)
+    }
+
+    /// A random background with one to three traits that do not conflict
+    /// with each other.
+    pub fn generate(&self, random: &mut Random) -> Background {
+        let age = random.range(i32::from(MIN_AGE), i32::from(MAX_AGE)) as u8;
+        let childhood = random
+            .pick(&self.childhoods)
+            .cloned()
+            .expect("No childhood backstories");
+        let adulthood = random
+            .pick(&self.adulthoods)
+            .cloned()
+            .expect("No adulthood backstories");
+        let count = 1 + random.below(MAX_TRAITS) as usize;
+        let mut traits: Vec<Trait> = Vec::with_capacity(count);
+        while traits.len() < count {
+            let candidates: Vec<&Trait> = self
+                .traits
+                .iter()
+                .filter(|t| {
+                    traits.iter().all(|picked| {
+                        picked.name != t.name
+                            && !picked.conflicts.contains(&t.name)
+                            && !t.conflicts.contains(&picked.name)
+                    })
+                })
+                .collect();
+            match random.pick(&candidates) {
+                Some(t) => traits.push((*t).clone()),
+                None => break,
+            }
+        }
+        Background {
+            age,
+            childhood,
+            adulthood,
+            traits,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+
+    fn backgrounds() -> Backgrounds {
+        Backgrounds::from_ron(asset::BACKGROUNDS).unwrap()
+    }
+
+    fn named(name: &str) -> Trait {
+        backgrounds()
+            .traits
+            .into_iter()
+            .find(|t| t.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn from_ron_fails_for_conflicts_with_unknown_traits() {
+        let error = Backgrounds::from_ron(
+            r#"(childhoods: [], adulthoods: [],
+                traits: [(name: "Tough", conflicts: ["Frail"])])"#,
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("trait Tough conflicts with unknown trait Frail"));
+    }
+
+    #[test]
+    fn generated_traits_do_not_conflict() {
+        let backgrounds = backgrounds();
+        let mut random = Random::new(3);
+        for _ in 0..200 {
+            let background = backgrounds.generate(&mut random);
+            assert!((MIN_AGE..=MAX_AGE).contains(&background.age));
+            assert!((1..=3).contains(&background.traits.len()));
+            for t in background.traits.iter() {
+                assert_eq!(
+                    1,
+                    background
+                        .traits
+                        .iter()
+                        .filter(|other| other.name == t.name)
+                        .count()
+                );
+                assert!(background
+                    .traits
+                    .iter()
+                    .all(|other| !t.conflicts.contains(&other.name)));
+            }
+        }
+    }
+
+    #[test]
+    fn backgrounds_add_up_skills_and_effects() {
+        let backgrounds = backgrounds();
+        let background = Background {
+            age: 40,
+            childhood: backgrounds.childhoods[0].clone(),
+            adulthood: backgrounds.adulthoods[0].clone(),
+            traits: vec![named("Tough"), named("Handy"), named("Big eater")],
+        };
+        // Carpenter, 2 levels of experience and Handy.
+        assert!(background
+            .skills()
+            .contains(&(SkillKind::Construction, 4 + 2 + 2)));
+        assert_eq!(0.5, background.damage_taken());
+        assert_eq!(1.5, background.food_rate());
+        assert_eq!(1.0, background.rest_rate());
+        assert_eq!(0, background.mood());
+        assert!(!background.sleeps_by_day());
+    }
+
+    #[test]
+    fn backstories_disable_work() {
+        let backgrounds = backgrounds();
+        let noble = backgrounds
+            .adulthoods
+            .iter()
+            .find(|b| b.title == "Noble")
+            .unwrap();
+        let background = Background {
+            age: MIN_AGE,
+            childhood: backgrounds.childhoods[0].clone(),
+            adulthood: noble.clone(),
+            traits: vec![named("Brawler")],
+        };
+        assert_eq!(
+            vec![WorkType::Construct, WorkType::Haul],
+            background.disabled()
+        );
+        // Shooting 1 from the backstory, -2 from being a brawler.
+        assert!(!background
+            .skills()
+            .iter()
+            .any(|(kind, level)| *kind == SkillKind::Shooting && *level > 0));
+    }
+}
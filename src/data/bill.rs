@@ -0,0 +1,133 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter, Result};
+
+const DEFAULT_REPEAT_COUNT: u16 = 10;
+const DEFAULT_TARGET_COUNT: u16 = 30;
+
+/// How many times a [`Bill`] should be carried out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BillMode {
+    /// Repeat the recipe a fixed number of times.
+    DoTimes(u16),
+    /// Repeat the recipe until there are that many products on the map.
+    UntilHave(u16),
+    Forever,
+}
+
+/// A production order on a workbench.
+///
+/// `recipe` is an index into [`RecipeBook`](crate::data::RecipeBook).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bill {
+    pub recipe: usize,
+    pub mode: BillMode,
+    done: u16,
+}
+
+impl Bill {
+    pub fn new(recipe: usize) -> Bill {
+        Bill {
+            recipe,
+            mode: BillMode::DoTimes(DEFAULT_REPEAT_COUNT),
+            done: 0,
+        }
+    }
+
+    pub fn complete_once(&mut self) {
+        self.done = self.done.saturating_add(1);
+    }
+
+    /// Switch to the next mode, the number of completions is reset.
+    pub fn cycle_mode(&mut self) {
+        self.mode = match self.mode {
+            BillMode::DoTimes(_) => BillMode::UntilHave(DEFAULT_TARGET_COUNT),
+            BillMode::UntilHave(_) => BillMode::Forever,
+            BillMode::Forever => BillMode::DoTimes(DEFAULT_REPEAT_COUNT),
+        };
+        self.done = 0;
+    }
+
+    pub fn done(&self) -> u16 {
+        self.done
+    }
+
+    /// Whether a worker should pick this bill up, `in_stock` is the number
+    /// of main products of the recipe currently available.
+    pub fn is_active(&self, in_stock: u32) -> bool {
+        match self.mode {
+            BillMode::DoTimes(n) => self.done < n,
+            BillMode::UntilHave(n) => in_stock < u32::from(n),
+            BillMode::Forever => true,
+        }
+    }
+
+    pub fn set_recipe(&mut self, recipe: usize) {
+        self.recipe = recipe;
+        self.done = 0;
+    }
+}
+
+impl Display for BillMode {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            BillMode::DoTimes(n) => write!(f, "x{}", n),
+            BillMode::UntilHave(n) => write!(f, "until {}", n),
+            BillMode::Forever => write!(f, "forever"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn do_times_bill_is_active_until_completed_n_times() {
+        let mut bill = Bill::new(0);
+        bill.mode = BillMode::DoTimes(2);
+        assert!(bill.is_active(0));
+        bill.complete_once();
+        assert!(bill.is_active(100));
+        bill.complete_once();
+        assert!(!bill.is_active(0));
+    }
+
+    #[test]
+    fn until_have_bill_is_active_while_stock_is_below_target() {
+        let mut bill = Bill::new(0);
+        bill.mode = BillMode::UntilHave(30);
+        assert!(bill.is_active(0));
+        assert!(bill.is_active(29));
+        assert!(!bill.is_active(30));
+        assert!(!bill.is_active(31));
+    }
+
+    #[test]
+    fn cycle_mode_visits_all_modes_and_resets_progress() {
+        let mut bill = Bill::new(0);
+        bill.complete_once();
+        bill.cycle_mode();
+        assert_eq!(BillMode::UntilHave(DEFAULT_TARGET_COUNT), bill.mode);
+        assert_eq!(0, bill.done());
+        bill.cycle_mode();
+        assert_eq!(BillMode::Forever, bill.mode);
+        bill.cycle_mode();
+        assert_eq!(BillMode::DoTimes(DEFAULT_REPEAT_COUNT), bill.mode);
+    }
+}
@@ -0,0 +1,256 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{AnimalKind, InjuryKind, ItemKind, Location, SkillKind};
+use std::fmt::{Display, Formatter, Result};
+
+/// Hit chance lost for each tile between a ranged attacker and its target.
+const ACCURACY_PER_TILE: f64 = 0.04;
+/// Hit chance gained for each skill level.
+const ACCURACY_PER_LEVEL: f64 = 0.02;
+const MAX_HIT_CHANCE: f64 = 0.95;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DamageType {
+    Blunt,
+    Cut,
+    Pierce,
+}
+
+impl DamageType {
+    /// Kind of injury the damage causes.
+    pub fn injury(self) -> InjuryKind {
+        match self {
+            DamageType::Blunt => InjuryKind::Bruise,
+            DamageType::Cut => InjuryKind::Cut,
+            DamageType::Pierce => InjuryKind::Puncture,
+        }
+    }
+}
+
+impl Display for DamageType {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// How a colonist or an animal attacks.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Attack {
+    /// Past tense, as in "Ada *stabbed* the Boar".
+    pub verb: &'static str,
+    pub damage_type: DamageType,
+    pub min_damage: f32,
+    pub max_damage: f32,
+    /// Farthest distance in tiles the attack reaches, `1` for melee.
+    pub range: u32,
+    /// Hit chance of an attacker with no skill, next to the target.
+    pub accuracy: f64,
+    /// Game time to wait between two attacks.
+    pub cooldown_millis: u32,
+}
+
+impl Attack {
+    /// Attack of colonists without a weapon.
+    pub const FISTS: Attack = Attack {
+        verb: "punched",
+        damage_type: DamageType::Blunt,
+        min_damage: 1.0,
+        max_damage: 3.0,
+        range: 1,
+        accuracy: 0.7,
+        cooldown_millis: 60_000,
+    };
+
+    /// Attack of a spike trap on the hostile stepping on it.
+    pub const SPIKE_TRAP: Attack = Attack {
+        verb: "impaled",
+        damage_type: DamageType::Pierce,
+        min_damage: 5.0,
+        max_damage: 12.0,
+        range: 1,
+        accuracy: 0.8,
+        cooldown_millis: 0,
+    };
+
+    /// Chance to hit a target `distance` tiles away.
+    pub fn hit_chance(&self, distance: u32, skill_level: u8) -> f64 {
+        let chance = self.accuracy
+            + f64::from(skill_level) * ACCURACY_PER_LEVEL
+            - f64::from(distance.saturating_sub(1)) * ACCURACY_PER_TILE;
+        chance.clamp(0.0, MAX_HIT_CHANCE)
+    }
+
+    pub fn is_ranged(&self) -> bool {
+        self.range > 1
+    }
+
+    /// Skill trained by using the attack.
+    pub fn skill(&self) -> SkillKind {
+        if self.is_ranged() {
+            SkillKind::Shooting
+        } else {
+            SkillKind::Melee
+        }
+    }
+}
+
+impl ItemKind {
+    /// Attack made with the item, `None` if it is not a weapon.
+    pub fn weapon(self) -> Option<Attack> {
+        match self {
+            ItemKind::Bow => Some(Attack {
+                verb: "shot",
+                damage_type: DamageType::Pierce,
+                min_damage: 4.0,
+                max_damage: 9.0,
+                range: 10,
+                accuracy: 0.75,
+                cooldown_millis: 90_000,
+            }),
+            ItemKind::Knife => Some(Attack {
+                verb: "stabbed",
+                damage_type: DamageType::Cut,
+                min_damage: 3.0,
+                max_damage: 7.0,
+                range: 1,
+                accuracy: 0.75,
+                cooldown_millis: 60_000,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl AnimalKind {
+    pub fn attack(self) -> Attack {
+        let (verb, damage_type, min_damage, max_damage) = match self {
+            AnimalKind::Boar => ("gored", DamageType::Cut, 3.0, 8.0),
+            AnimalKind::Deer => ("kicked", DamageType::Blunt, 2.0, 5.0),
+            AnimalKind::Fowl => ("pecked", DamageType::Pierce, 1.0, 2.0),
+            AnimalKind::Goat => ("headbutted", DamageType::Blunt, 2.0, 5.0),
+            AnimalKind::Hare => ("bit", DamageType::Cut, 1.0, 2.0),
+            AnimalKind::Sheep => ("butted", DamageType::Blunt, 1.0, 3.0),
+        };
+        Attack {
+            verb,
+            damage_type,
+            min_damage,
+            max_damage,
+            range: 1,
+            accuracy: 0.8,
+            cooldown_millis: 60_000,
+        }
+    }
+}
+
+/// Tiles on the straight line from `from` to `to`, both included.
+pub fn line(from: Location, to: Location) -> Vec<Location> {
+    let dx = (to.x - from.x).abs();
+    let dy = -(to.y - from.y).abs();
+    let sx = if from.x < to.x { 1 } else { -1 };
+    let sy = if from.y < to.y { 1 } else { -1 };
+    let mut err = dx + dy;
+    let mut current = from;
+    let mut tiles = vec![current];
+    while current != to {
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            current.x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            current.y += sy;
+        }
+        tiles.push(current);
+    }
+    tiles
+}
+
+/// Whether a projectile can fly from `from` to `to` without hitting a
+/// blocking tile in between.
+pub fn has_line_of_fire<F>(
+    from: Location,
+    to: Location,
+    is_blocking: F,
+) -> bool
+where
+    F: Fn(Location) -> bool,
+{
+    let tiles = line(from, to);
+    tiles.len() < 3
+        || !tiles[1..tiles.len() - 1].iter().any(|l| is_blocking(*l))
+}
+
+/// Tiles whose structures give cover to a target at `to` against a ranged
+/// attack from `from`: the tile of the target and the one in front of it.
+pub fn cover_tiles(from: Location, to: Location) -> Vec<Location> {
+    line(from, to)
+        .into_iter()
+        .rev()
+        .take(2)
+        .filter(|l| *l != from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_includes_both_ends() {
+        let tiles = line(Location::new(0, 0), Location::new(4, 2));
+        assert_eq!(5, tiles.len());
+        assert_eq!(Some(&Location::new(0, 0)), tiles.first());
+        assert_eq!(Some(&Location::new(4, 2)), tiles.last());
+        assert_eq!(
+            vec![Location::new(3, 3)],
+            line(Location::new(3, 3), Location::new(3, 3))
+        );
+    }
+
+    #[test]
+    fn blocking_tiles_in_between_block_the_line_of_fire() {
+        let from = Location::new(0, 0);
+        let to = Location::new(6, 0);
+        assert!(has_line_of_fire(from, to, |_| false));
+        assert!(!has_line_of_fire(from, to, |l| l == Location::new(3, 0)));
+        // The shooter and the target do not block.
+        assert!(has_line_of_fire(from, to, |l| l == from || l == to));
+    }
+
+    #[test]
+    fn cover_is_next_to_or_under_the_target() {
+        assert_eq!(
+            vec![Location::new(6, 0), Location::new(5, 0)],
+            cover_tiles(Location::new(0, 0), Location::new(6, 0))
+        );
+        // Nothing is in between adjacent tiles.
+        assert_eq!(
+            vec![Location::new(1, 1)],
+            cover_tiles(Location::new(0, 0), Location::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn hit_chance_drops_with_distance_and_rises_with_skill() {
+        let bow = ItemKind::Bow.weapon().unwrap();
+        assert!(bow.hit_chance(8, 0) < bow.hit_chance(2, 0));
+        assert!(bow.hit_chance(8, 10) > bow.hit_chance(8, 0));
+        assert!(bow.hit_chance(1, 20) <= MAX_HIT_CHANCE);
+    }
+}
@@ -0,0 +1,132 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{has_line_of_fire, Location, ThoughtKind};
+use std::fmt::{Display, Formatter, Result};
+
+/// Colonists see rotting corpses this many tiles away.
+const SIGHT_DISTANCE: u32 = 8;
+/// Colonists smell rotting corpses this many tiles away, even through
+/// walls.
+const SMELL_DISTANCE: u32 = 2;
+
+/// What the colonists should do with a corpse.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Disposal {
+    Bury,
+    /// Cut up for meat and leather, only animals are butchered.
+    Butcher,
+    Cremate,
+}
+
+impl Disposal {
+    /// Disposal ordered after `current`: nothing, butcher if `can_butcher`,
+    /// bury, cremate and back to nothing.
+    pub fn cycle(
+        current: Option<Disposal>,
+        can_butcher: bool,
+    ) -> Option<Disposal> {
+        match current {
+            None if can_butcher => Some(Disposal::Butcher),
+            None | Some(Disposal::Butcher) => Some(Disposal::Bury),
+            Some(Disposal::Bury) => Some(Disposal::Cremate),
+            Some(Disposal::Cremate) => None,
+        }
+    }
+}
+
+impl Display for Disposal {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Thought a colonist at `from` gets from a rotting corpse at `corpse`,
+/// smelling it is worse than seeing it.
+pub fn rot_thought<F>(
+    from: Location,
+    corpse: Location,
+    is_blocking: F,
+) -> Option<ThoughtKind>
+where
+    F: Fn(Location) -> bool,
+{
+    let distance = from.distance_to(corpse);
+    if distance <= SMELL_DISTANCE {
+        Some(ThoughtKind::SmelledRot)
+    } else if distance <= SIGHT_DISTANCE
+        && has_line_of_fire(from, corpse, is_blocking)
+    {
+        Some(ThoughtKind::SawRot)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle_goes_through_nothing() {
+        let mut disposal = None;
+        let mut seen = vec![];
+        for _ in 0..3 {
+            disposal = Disposal::cycle(disposal, false);
+            seen.push(disposal);
+        }
+        assert_eq!(
+            vec![Some(Disposal::Bury), Some(Disposal::Cremate), None],
+            seen
+        );
+    }
+
+    #[test]
+    fn only_animals_are_butchered() {
+        assert_eq!(Some(Disposal::Butcher), Disposal::cycle(None, true));
+        assert_eq!(
+            Some(Disposal::Bury),
+            Disposal::cycle(Some(Disposal::Butcher), true)
+        );
+    }
+
+    #[test]
+    fn rot_is_smelled_nearby_and_seen_further_away() {
+        let corpse = Location::origin();
+        let open = |_| false;
+        assert_eq!(
+            Some(ThoughtKind::SmelledRot),
+            rot_thought(Location::new(2, 1), corpse, open)
+        );
+        assert_eq!(
+            Some(ThoughtKind::SawRot),
+            rot_thought(Location::new(6, 0), corpse, open)
+        );
+        assert_eq!(None, rot_thought(Location::new(9, 0), corpse, open));
+    }
+
+    #[test]
+    fn walls_block_the_sight_but_not_the_smell() {
+        let corpse = Location::origin();
+        let wall = |l: Location| l.x == 1;
+        assert_eq!(None, rot_thought(Location::new(6, 0), corpse, wall));
+        assert_eq!(
+            Some(ThoughtKind::SmelledRot),
+            rot_thought(Location::new(2, 0), corpse, wall)
+        );
+    }
+}
@@ -0,0 +1,42 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::Season;
+
+/// Properties of items that can be grown in fields.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crop {
+    /// Number of days it takes a sown field to ripen.
+    pub growing_days: u16,
+    /// Number of items harvested from a ripe field.
+    pub harvest_yield: u16,
+}
+
+/// Crops grow in every season but winter.
+pub fn is_growing_season(season: Season) -> bool {
+    season != Season::Winter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nothing_grows_in_winter() {
+        assert!(is_growing_season(Season::Autumn));
+        assert!(!is_growing_season(Season::Winter));
+    }
+}
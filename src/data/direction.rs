@@ -15,7 +15,9 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-#[derive(Clone, Copy, Debug)]
+use std::fmt::{Display, Formatter, Result};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Direction {
     North,
     East,
@@ -24,6 +26,13 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const ALL: &'static [Direction] = &[
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
     pub fn to_vector(self) -> (i32, i32) {
         match self {
             Direction::North => (0, -1),
@@ -33,3 +42,15 @@ impl Direction {
         }
     }
 }
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            Direction::North => "north",
+            Direction::East => "east",
+            Direction::South => "south",
+            Direction::West => "west",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -0,0 +1,386 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Attack, BodyPart, ItemKind, SkillKind, MILLIS_PER_DAY};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+
+/// Below this temperature colonists without warm clothes are cold.
+pub const COMFORTABLE_TEMPERATURE: f32 = 12.0;
+
+/// Where a colonist wears an item.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Slot {
+    Head,
+    Body,
+    /// A tool or a weapon.
+    Hand,
+}
+
+impl Slot {
+    pub const ALL: &'static [Slot] = &[Slot::Head, Slot::Body, Slot::Hand];
+
+    /// Slot of the armor that protects `part`.
+    pub fn covering(part: BodyPart) -> Option<Slot> {
+        match part {
+            BodyPart::Head => Some(Slot::Head),
+            BodyPart::Torso | BodyPart::LeftArm | BodyPart::RightArm => {
+                Some(Slot::Body)
+            }
+            BodyPart::LeftLeg | BodyPart::RightLeg => None,
+        }
+    }
+}
+
+impl Display for Slot {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Whether colonists pick their equipment themselves.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum EquipPolicy {
+    /// Empty slots without a wanted item are filled with the best item
+    /// available.
+    #[default]
+    Auto,
+    /// Only the wanted items are worn.
+    Manual,
+}
+
+impl Display for EquipPolicy {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// What an equippable item does for the colonist wearing it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EquipmentStats {
+    pub slot: Slot,
+    /// Degrees Celsius the colonist is comfortable below the
+    /// [COMFORTABLE_TEMPERATURE].
+    pub warmth: f32,
+    /// Fraction of the damage absorbed on the covered body parts.
+    pub armor: f32,
+    /// Skill the item is a tool for and the work speed bonus.
+    pub tool: Option<(SkillKind, f32)>,
+    /// How long the item lasts, in game days worn or used.
+    pub lifetime_days: u16,
+}
+
+impl EquipmentStats {
+    /// Rough measure of usefulness for picking the best item.
+    fn score(self) -> f32 {
+        self.warmth
+            + self.armor * 50.0
+            + self.tool.map_or(0.0, |(_, b)| b * 50.0)
+    }
+}
+
+impl ItemKind {
+    /// Equipment properties of the item, `None` if it cannot be equipped.
+    pub fn equipment(self) -> Option<EquipmentStats> {
+        let (slot, warmth, armor, tool, lifetime_days) = match self {
+            ItemKind::Bow => (Slot::Hand, 0.0, 0.0, None, 30),
+            ItemKind::Hammer => (
+                Slot::Hand,
+                0.0,
+                0.0,
+                Some((SkillKind::Construction, 0.3)),
+                20,
+            ),
+            ItemKind::Helmet => (Slot::Head, 2.0, 0.4, None, 30),
+            ItemKind::Knife => {
+                (Slot::Hand, 0.0, 0.0, Some((SkillKind::Cooking, 0.2)), 20)
+            }
+            ItemKind::Parka => (Slot::Body, 12.0, 0.1, None, 15),
+            _ => return None,
+        };
+        Some(EquipmentStats {
+            slot,
+            warmth,
+            armor,
+            tool,
+            lifetime_days,
+        })
+    }
+}
+
+/// Condition of an equippable item, starting from `1.0` and broken at
+/// `0.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Durability(f32);
+
+impl Durability {
+    pub fn is_broken(self) -> bool {
+        self.0 <= 0.0
+    }
+
+    /// Durability of the stack that results from merging two stacks.
+    pub fn merge(
+        self,
+        quantity: u16,
+        other: Durability,
+        other_quantity: u16,
+    ) -> Durability {
+        let total = f32::from(quantity) + f32::from(other_quantity);
+        if total == 0.0 {
+            return self;
+        }
+        Durability(
+            (self.0 * f32::from(quantity)
+                + other.0 * f32::from(other_quantity))
+                / total,
+        )
+    }
+
+    pub fn percent(self) -> u8 {
+        (self.0 * 100.0).round() as u8
+    }
+
+    /// Wear by `game_millis` of use, the item breaks after
+    /// `lifetime_days`.
+    pub fn wear(&mut self, game_millis: u32, lifetime_days: u16) {
+        let lifetime = f64::from(lifetime_days) * f64::from(MILLIS_PER_DAY);
+        self.0 =
+            (self.0 - (f64::from(game_millis) / lifetime) as f32).max(0.0);
+    }
+}
+
+impl Default for Durability {
+    fn default() -> Self {
+        Durability(1.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Equipped {
+    pub kind: ItemKind,
+    pub durability: Durability,
+}
+
+/// Items a colonist wears and the items the player wants them to wear.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Loadout {
+    equipped: BTreeMap<Slot, Equipped>,
+    wanted: BTreeMap<Slot, ItemKind>,
+    pub policy: EquipPolicy,
+}
+
+impl Loadout {
+    /// Fraction of the damage to `part` absorbed by armor.
+    pub fn armor(&self, part: BodyPart) -> f32 {
+        Slot::covering(part)
+            .and_then(|slot| self.stats(slot))
+            .map_or(0.0, |stats| stats.armor)
+    }
+
+    /// Kind of item wanted in `slot` next: nothing, then each item that
+    /// fits the slot in turn.
+    pub fn cycle_wanted(&mut self, slot: Slot) {
+        let kinds: Vec<ItemKind> = ItemKind::ALL
+            .iter()
+            .copied()
+            .filter(|k| k.equipment().is_some_and(|e| e.slot == slot))
+            .collect();
+        let next = match self.wanted(slot) {
+            None => kinds.first().copied(),
+            Some(kind) => {
+                kinds.iter().skip_while(|k| **k != kind).nth(1).copied()
+            }
+        };
+        match next {
+            Some(kind) => self.wanted.insert(slot, kind),
+            None => self.wanted.remove(&slot),
+        };
+    }
+
+    /// Attack of the weapon in hand, `None` if the item in hand is not a
+    /// weapon.
+    pub fn weapon(&self) -> Option<Attack> {
+        self.equipped(Slot::Hand).and_then(|e| e.kind.weapon())
+    }
+
+    /// Put `item` on, returns the item that was in its slot.
+    pub fn equip(&mut self, item: Equipped) -> Option<Equipped> {
+        let slot = item.kind.equipment()?.slot;
+        self.equipped.insert(slot, item)
+    }
+
+    pub fn equipped(&self, slot: Slot) -> Option<Equipped> {
+        self.equipped.get(&slot).copied()
+    }
+
+    /// Item kind the colonist should look for to fill `slot`, if the slot
+    /// is empty.  `available` are the kinds of the items that are lying
+    /// around.
+    pub fn looking_for(
+        &self,
+        slot: Slot,
+        available: &[ItemKind],
+    ) -> Option<ItemKind> {
+        if self.equipped.contains_key(&slot) {
+            return None;
+        }
+        match (self.wanted(slot), self.policy) {
+            (Some(kind), _) => Some(kind),
+            (None, EquipPolicy::Manual) => None,
+            (None, EquipPolicy::Auto) => available
+                .iter()
+                .filter_map(|k| k.equipment().map(|e| (*k, e)))
+                .filter(|(_, e)| e.slot == slot)
+                .max_by(|(_, a), (_, b)| {
+                    a.score().partial_cmp(&b.score()).unwrap()
+                })
+                .map(|(k, _)| k),
+        }
+    }
+
+    pub fn toggle_policy(&mut self) {
+        self.policy = match self.policy {
+            EquipPolicy::Auto => EquipPolicy::Manual,
+            EquipPolicy::Manual => EquipPolicy::Auto,
+        };
+    }
+
+    pub fn unequip(&mut self, slot: Slot) -> Option<Equipped> {
+        self.equipped.remove(&slot)
+    }
+
+    /// Slots with items that are not wanted any more.
+    pub fn unwanted(&self) -> Vec<Slot> {
+        self.equipped
+            .iter()
+            .filter(|(slot, item)| match (self.wanted(**slot), self.policy) {
+                (Some(kind), _) => kind != item.kind,
+                (None, EquipPolicy::Auto) => false,
+                (None, EquipPolicy::Manual) => true,
+            })
+            .map(|(slot, _)| *slot)
+            .collect()
+    }
+
+    pub fn wanted(&self, slot: Slot) -> Option<ItemKind> {
+        self.wanted.get(&slot).copied()
+    }
+
+    /// Degrees Celsius the colonist is comfortable below the
+    /// [COMFORTABLE_TEMPERATURE].
+    pub fn warmth(&self) -> f32 {
+        Slot::ALL
+            .iter()
+            .filter_map(|slot| self.stats(*slot))
+            .map(|stats| stats.warmth)
+            .sum()
+    }
+
+    /// Wear the item in `slot` by `game_millis` of use, returns the kind of
+    /// the item if it broke.
+    pub fn wear(&mut self, slot: Slot, game_millis: u32) -> Option<ItemKind> {
+        let item = self.equipped.get_mut(&slot)?;
+        let lifetime_days = item.kind.equipment()?.lifetime_days;
+        item.durability.wear(game_millis, lifetime_days);
+        if item.durability.is_broken() {
+            self.equipped.remove(&slot).map(|item| item.kind)
+        } else {
+            None
+        }
+    }
+
+    /// How much faster the tool in hand makes work that uses `skill`.
+    pub fn work_speed(&self, skill: SkillKind) -> f32 {
+        match self.stats(Slot::Hand).and_then(|stats| stats.tool) {
+            Some((tool_skill, bonus)) if tool_skill == skill => 1.0 + bonus,
+            _ => 1.0,
+        }
+    }
+
+    fn stats(&self, slot: Slot) -> Option<EquipmentStats> {
+        self.equipped(slot).and_then(|item| item.kind.equipment())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn equipped(kind: ItemKind) -> Equipped {
+        Equipped {
+            kind,
+            durability: Durability::default(),
+        }
+    }
+
+    #[test]
+    fn equipment_changes_stats() {
+        let mut loadout = Loadout::default();
+        assert_eq!(1.0, loadout.work_speed(SkillKind::Construction));
+        loadout.equip(equipped(ItemKind::Hammer));
+        loadout.equip(equipped(ItemKind::Parka));
+        assert!(loadout.work_speed(SkillKind::Construction) > 1.0);
+        assert_eq!(1.0, loadout.work_speed(SkillKind::Cooking));
+        assert!(loadout.warmth() > 0.0);
+        assert!(loadout.armor(BodyPart::Torso) > 0.0);
+        assert_eq!(0.0, loadout.armor(BodyPart::LeftLeg));
+    }
+
+    #[test]
+    fn items_break_after_their_lifetime() {
+        let mut loadout = Loadout::default();
+        loadout.equip(equipped(ItemKind::Parka));
+        let lifetime = ItemKind::Parka.equipment().unwrap().lifetime_days;
+        assert_eq!(None, loadout.wear(Slot::Body, MILLIS_PER_DAY));
+        assert_eq!(
+            Some(ItemKind::Parka),
+            loadout.wear(Slot::Body, u32::from(lifetime) * MILLIS_PER_DAY)
+        );
+        assert_eq!(None, loadout.equipped(Slot::Body));
+    }
+
+    #[test]
+    fn auto_policy_picks_the_best_item() {
+        let mut loadout = Loadout::default();
+        let available = [ItemKind::Log, ItemKind::Hammer, ItemKind::Knife];
+        assert_eq!(
+            Some(ItemKind::Hammer),
+            loadout.looking_for(Slot::Hand, &available)
+        );
+        assert_eq!(None, loadout.looking_for(Slot::Head, &available));
+        loadout.toggle_policy();
+        assert_eq!(EquipPolicy::Manual, loadout.policy);
+        assert_eq!(None, loadout.looking_for(Slot::Hand, &available));
+    }
+
+    #[test]
+    fn wanted_items_replace_equipped_ones() {
+        let mut loadout = Loadout::default();
+        loadout.equip(equipped(ItemKind::Hammer));
+        assert!(loadout.unwanted().is_empty());
+        loadout.cycle_wanted(Slot::Hand);
+        assert_eq!(Some(ItemKind::Bow), loadout.wanted(Slot::Hand));
+        assert_eq!(vec![Slot::Hand], loadout.unwanted());
+        loadout.cycle_wanted(Slot::Hand);
+        assert_eq!(Some(ItemKind::Hammer), loadout.wanted(Slot::Hand));
+        assert!(loadout.unwanted().is_empty());
+        loadout.cycle_wanted(Slot::Hand);
+        assert_eq!(Some(ItemKind::Knife), loadout.wanted(Slot::Hand));
+        assert_eq!(vec![Slot::Hand], loadout.unwanted());
+        loadout.cycle_wanted(Slot::Hand);
+        assert_eq!(None, loadout.wanted(Slot::Hand));
+    }
+}
@@ -0,0 +1,253 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{unique_name, Names, Random};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter, Result};
+
+pub const MAX_GOODWILL: i32 = 100;
+pub const MIN_GOODWILL: i32 = -100;
+/// Factions with less goodwill than this are hostile.
+const HOSTILE_BELOW: i32 = -20;
+/// Factions with at least this much goodwill are allies.
+const ALLY_FROM: i32 = 50;
+/// Goodwill that takes the price markup of a trader up or down by 1%.
+const GOODWILL_PER_MARKUP_PERCENT: f64 = 5.0;
+/// Entries kept in the history of a faction.
+const MAX_HISTORY: usize = 20;
+/// Silver worth of gifts that raises the goodwill by one.
+const GIFT_SILVER_PER_GOODWILL: u32 = 10;
+/// Silver worth of trade that raises the goodwill by one.
+const TRADE_SILVER_PER_GOODWILL: u32 = 100;
+const FACTION_COUNT: usize = 4;
+
+/// How a faction feels about the colony.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Relation {
+    /// Sends raids.
+    Hostile,
+    /// Sends caravans.
+    Neutral,
+    /// Sends caravans with better prices.
+    Ally,
+}
+
+impl Display for Relation {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A group of people living beyond the map.  Raiders and traders are its
+/// members.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Faction {
+    pub name: String,
+    /// Culture the faction takes its names from.
+    pub culture: String,
+    goodwill: i32,
+    /// Past changes of the goodwill, the latest last.
+    history: Vec<String>,
+}
+
+impl Faction {
+    pub fn new(name: &str, goodwill: i32) -> Faction {
+        Faction {
+            name: name.to_owned(),
+            culture: String::new(),
+            goodwill: goodwill.clamp(MIN_GOODWILL, MAX_GOODWILL),
+            history: vec![],
+        }
+    }
+
+    /// Change the goodwill toward the colony and note why in the history.
+    pub fn change_goodwill(&mut self, delta: i32, reason: &str, day: u16) {
+        self.goodwill =
+            (self.goodwill + delta).clamp(MIN_GOODWILL, MAX_GOODWILL);
+        self.history
+            .push(format!("Day {}: {} {:+}", day, reason, delta));
+        if self.history.len() > MAX_HISTORY {
+            self.history.remove(0);
+        }
+    }
+
+    pub fn goodwill(&self) -> i32 {
+        self.goodwill
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    /// Fraction added to the price markup of the traders of the faction,
+    /// negative for factions that like the colony.
+    pub fn markup(&self) -> f64 {
+        -f64::from(self.goodwill) / GOODWILL_PER_MARKUP_PERCENT / 100.0
+    }
+
+    pub fn relation(&self) -> Relation {
+        if self.goodwill < HOSTILE_BELOW {
+            Relation::Hostile
+        } else if self.goodwill < ALLY_FROM {
+            Relation::Neutral
+        } else {
+            Relation::Ally
+        }
+    }
+}
+
+/// Goodwill gained by giving goods worth `silver` to a faction.
+pub fn gift_goodwill(silver: u32) -> i32 {
+    goodwill_for(silver, GIFT_SILVER_PER_GOODWILL)
+}
+
+/// Goodwill gained by trading goods worth `silver` with a faction.
+pub fn trade_goodwill(silver: u32) -> i32 {
+    goodwill_for(silver, TRADE_SILVER_PER_GOODWILL)
+}
+
+fn goodwill_for(silver: u32, silver_per_goodwill: u32) -> i32 {
+    i32::try_from(silver / silver_per_goodwill)
+        .unwrap_or(MAX_GOODWILL)
+        .clamp(1, MAX_GOODWILL)
+}
+
+/// The factions of the world, generated at game start.  Factions are
+/// referred to by their index.
+#[derive(Debug, Default)]
+pub struct Factions {
+    factions: Vec<Faction>,
+}
+
+impl Factions {
+    /// Factions of random cultures with generated names and random
+    /// goodwill.  At least one of them is hostile and one is not.
+    pub fn generate(names: &Names, random: &mut Random) -> Factions {
+        let mut factions: Vec<Faction> = vec![];
+        for idx in 0..FACTION_COUNT {
+            let culture = names
+                .culture(names.pick_culture(random))
+                .expect("No cultures");
+            let name = unique_name(
+                || names.faction(culture, random),
+                |name| factions.iter().any(|f| f.name == name),
+            );
+            let goodwill = match idx {
+                0 => random.range(-60, -30),
+                1 => random.range(0, 30),
+                _ => random.range(-50, 40),
+            };
+            factions.push(Faction {
+                culture: culture.name.clone(),
+                ..Faction::new(&name, goodwill)
+            });
+        }
+        Factions { factions }
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&Faction> {
+        self.factions.get(idx)
+    }
+
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut Faction> {
+        self.factions.get_mut(idx)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Faction> {
+        self.factions.iter()
+    }
+
+    /// A random hostile faction to send a raid.
+    pub fn pick_raiders(&self, random: &mut Random) -> Option<usize> {
+        self.pick(random, |relation| relation == Relation::Hostile)
+    }
+
+    /// A random faction that is not hostile to send a caravan.
+    pub fn pick_traders(&self, random: &mut Random) -> Option<usize> {
+        self.pick(random, |relation| relation != Relation::Hostile)
+    }
+
+    fn pick<F>(&self, random: &mut Random, is_wanted: F) -> Option<usize>
+    where
+        F: Fn(Relation) -> bool,
+    {
+        let candidates: Vec<usize> = self
+            .factions
+            .iter()
+            .enumerate()
+            .filter(|(_, faction)| is_wanted(faction.relation()))
+            .map(|(idx, _)| idx)
+            .collect();
+        random.pick(&candidates).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+
+    #[test]
+    fn goodwill_decides_the_relation() {
+        let mut faction = Faction::new("A", -30);
+        assert_eq!(Relation::Hostile, faction.relation());
+        faction.change_goodwill(15, "Rescued a member", 2);
+        assert_eq!(Relation::Neutral, faction.relation());
+        faction.change_goodwill(200, "Gift", 3);
+        assert_eq!(MAX_GOODWILL, faction.goodwill());
+        assert_eq!(Relation::Ally, faction.relation());
+        assert_eq!(
+            vec!["Day 2: Rescued a member +15", "Day 3: Gift +200"],
+            faction.history()
+        );
+    }
+
+    #[test]
+    fn gifts_please_more_than_trades() {
+        assert_eq!(1, trade_goodwill(20));
+        assert_eq!(2, gift_goodwill(20));
+        assert_eq!(3, trade_goodwill(300));
+        assert_eq!(30, gift_goodwill(300));
+        assert_eq!(MAX_GOODWILL, gift_goodwill(u32::MAX));
+    }
+
+    #[test]
+    fn goodwill_changes_prices() {
+        assert!(Faction::new("A", 50).markup() < 0.0);
+        assert_eq!(0.0, Faction::new("B", 0).markup());
+        assert!(Faction::new("C", -50).markup() > 0.0);
+    }
+
+    #[test]
+    fn generated_factions_both_raid_and_trade() {
+        let names = Names::from_ron(asset::NAMES).unwrap();
+        for seed in 0..20 {
+            let mut random = Random::new(seed);
+            let factions = Factions::generate(&names, &mut random);
+            assert_eq!(FACTION_COUNT, factions.iter().count());
+            let raiders = factions.pick_raiders(&mut random).unwrap();
+            let traders = factions.pick_traders(&mut random).unwrap();
+            assert_eq!(
+                Relation::Hostile,
+                factions.get(raiders).unwrap().relation()
+            );
+            assert_ne!(
+                Relation::Hostile,
+                factions.get(traders).unwrap().relation()
+            );
+        }
+    }
+}
@@ -0,0 +1,57 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter, Result};
+
+/// Quality of the food eaten, it affects the mood of the colonist.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum MealQuality {
+    Raw,
+    Simple,
+    Fine,
+}
+
+impl MealQuality {
+    pub fn mood_effect(self) -> i32 {
+        match self {
+            MealQuality::Raw => -5,
+            MealQuality::Simple => 0,
+            MealQuality::Fine => 5,
+        }
+    }
+}
+
+impl Display for MealQuality {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            MealQuality::Raw => "raw food",
+            MealQuality::Simple => "a simple meal",
+            MealQuality::Fine => "a fine meal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Properties of items that can be eaten.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Food {
+    /// How much of the hunger is satisfied by eating one.
+    pub nutrition: f32,
+    pub quality: MealQuality,
+    /// Number of days it takes to rot at normal spoilage rate.
+    pub shelf_life_days: u16,
+}
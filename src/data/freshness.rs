@@ -0,0 +1,132 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::time::MILLIS_PER_DAY;
+
+/// Above this temperature food spoils faster than its shelf life suggests.
+const REFERENCE_TEMPERATURE: f32 = 15.0;
+const OUTDOOR_SPOILAGE_FACTOR: f32 = 1.5;
+const MIN_SPOILAGE_RATE: f32 = 0.25;
+/// Below this freshness a corpse starts to smell.
+const ROTTING_THRESHOLD: f32 = 0.75;
+
+/// How fresh a food item is, starting from `1.0` and rotten at `0.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Freshness(f32);
+
+impl Freshness {
+    /// Decay by `game_millis` of game time, `shelf_life_days` is how long
+    /// it takes to rot at the normal rate.
+    pub fn decay(
+        &mut self,
+        game_millis: u32,
+        shelf_life_days: u16,
+        rate: f32,
+    ) {
+        let shelf_life =
+            f64::from(shelf_life_days) * f64::from(MILLIS_PER_DAY);
+        let amount = f64::from(game_millis) * f64::from(rate) / shelf_life;
+        self.0 = (self.0 - amount as f32).max(0.0);
+    }
+
+    pub fn is_rotten(self) -> bool {
+        self.0 <= 0.0
+    }
+
+    pub fn is_rotting(self) -> bool {
+        self.0 < ROTTING_THRESHOLD
+    }
+
+    /// Freshness of the stack that results from merging two stacks.
+    pub fn merge(
+        self,
+        quantity: u16,
+        other: Freshness,
+        other_quantity: u16,
+    ) -> Freshness {
+        let total = f32::from(quantity) + f32::from(other_quantity);
+        if total == 0.0 {
+            return self;
+        }
+        Freshness(
+            (self.0 * f32::from(quantity)
+                + other.0 * f32::from(other_quantity))
+                / total,
+        )
+    }
+
+    pub fn percent(self) -> u8 {
+        (self.0 * 100.0).round() as u8
+    }
+}
+
+impl Default for Freshness {
+    fn default() -> Self {
+        Freshness(1.0)
+    }
+}
+
+/// How fast food spoils compared to its shelf life.
+///
+/// Frozen food does not spoil.
+pub fn spoilage_rate(temperature: f32, is_outdoors: bool) -> f32 {
+    if temperature <= 0.0 {
+        return 0.0;
+    }
+    let rate = (temperature / REFERENCE_TEMPERATURE).max(MIN_SPOILAGE_RATE);
+    if is_outdoors {
+        rate * OUTDOOR_SPOILAGE_FACTOR
+    } else {
+        rate
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn food_rots_after_shelf_life_at_normal_rate() {
+        let mut freshness = Freshness::default();
+        freshness.decay(MILLIS_PER_DAY, 2, 1.0);
+        assert_eq!(50, freshness.percent());
+        assert!(!freshness.is_rotten());
+        assert!(freshness.is_rotting());
+        freshness.decay(MILLIS_PER_DAY, 2, 1.0);
+        assert!(freshness.is_rotten());
+        freshness.decay(MILLIS_PER_DAY, 2, 1.0);
+        assert_eq!(0, freshness.percent());
+    }
+
+    #[test]
+    fn merge_takes_the_weighted_average() {
+        let fresh = Freshness::default();
+        let mut old = Freshness::default();
+        old.decay(MILLIS_PER_DAY, 2, 1.0);
+        assert_eq!(75, fresh.merge(1, old, 1).percent());
+        assert_eq!(60, fresh.merge(1, old, 4).percent());
+        assert_eq!(100, fresh.merge(0, old, 0).percent());
+    }
+
+    #[test]
+    fn spoilage_is_faster_when_warm_or_outdoors() {
+        assert_eq!(0.0, spoilage_rate(-5.0, true));
+        assert!(spoilage_rate(30.0, false) > spoilage_rate(15.0, false));
+        assert!(spoilage_rate(15.0, true) > spoilage_rate(15.0, false));
+        assert!(spoilage_rate(1.0, false) > 0.0);
+    }
+}
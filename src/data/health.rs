@@ -0,0 +1,347 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Random, MILLIS_PER_DAY};
+use std::fmt::{Display, Formatter, Result};
+
+/// Blood regained in a game day once the bleeding stops.
+const BLOOD_PER_DAY: f32 = 0.5;
+/// Colonists who lost at least this much blood are downed.
+const DOWNED_BLOOD_LOSS: f32 = 0.5;
+/// Colonists in at least this much pain are downed.
+const DOWNED_PAIN: f32 = 0.8;
+/// Colonists die once a vital body part takes this many times its hit
+/// points.
+const FATAL_DAMAGE: f32 = 2.0;
+/// Severity healed in a game day by injuries that are not tended.
+const HEAL_PER_DAY: f32 = 2.0;
+const INFECTION_CHANCE: f64 = 0.25;
+const INFECTION_CHANCE_WITH_MEDICINE: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BodyPart {
+    Head,
+    Torso,
+    LeftArm,
+    RightArm,
+    LeftLeg,
+    RightLeg,
+}
+
+impl BodyPart {
+    pub const ALL: &'static [BodyPart] = &[
+        BodyPart::Head,
+        BodyPart::Torso,
+        BodyPart::LeftArm,
+        BodyPart::RightArm,
+        BodyPart::LeftLeg,
+        BodyPart::RightLeg,
+    ];
+
+    /// Injuries to a body part that add up to this much severity destroy
+    /// it, which downs the colonist.
+    pub fn max_hp(self) -> f32 {
+        match self {
+            BodyPart::Head => 25.0,
+            BodyPart::Torso => 40.0,
+            _ => 30.0,
+        }
+    }
+}
+
+impl Display for BodyPart {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            BodyPart::Head => "head",
+            BodyPart::Torso => "torso",
+            BodyPart::LeftArm => "left arm",
+            BodyPart::RightArm => "right arm",
+            BodyPart::LeftLeg => "left leg",
+            BodyPart::RightLeg => "right leg",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InjuryKind {
+    Bruise,
+    Cut,
+    Puncture,
+    /// Disease, which does not bleed.
+    Sickness,
+}
+
+impl InjuryKind {
+    /// Injuries accidents cause.
+    pub const ALL: &'static [InjuryKind] =
+        &[InjuryKind::Bruise, InjuryKind::Cut, InjuryKind::Puncture];
+
+    /// Blood lost in a game day for each point of severity, until the
+    /// injury is tended.
+    pub fn bleeding(self) -> f32 {
+        match self {
+            InjuryKind::Bruise => 0.0,
+            InjuryKind::Cut => 0.04,
+            InjuryKind::Puncture => 0.03,
+            InjuryKind::Sickness => 0.0,
+        }
+    }
+
+    /// Pain for each point of severity.
+    pub fn pain(self) -> f32 {
+        match self {
+            InjuryKind::Bruise => 0.01,
+            InjuryKind::Cut => 0.015,
+            InjuryKind::Puncture => 0.02,
+            InjuryKind::Sickness => 0.015,
+        }
+    }
+}
+
+impl Display for InjuryKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            InjuryKind::Bruise => write!(f, "bruise"),
+            InjuryKind::Cut => write!(f, "cut"),
+            InjuryKind::Puncture => write!(f, "puncture"),
+            InjuryKind::Sickness => write!(f, "sickness"),
+        }
+    }
+}
+
+/// How an injury was tended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Treatment {
+    Bandaged,
+    Medicine,
+}
+
+impl Treatment {
+    /// How much faster tended injuries heal.
+    fn healing(self) -> f32 {
+        match self {
+            Treatment::Bandaged => 3.0,
+            Treatment::Medicine => 5.0,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Injury {
+    pub part: BodyPart,
+    pub kind: InjuryKind,
+    pub severity: f32,
+    pub treatment: Option<Treatment>,
+    /// Infected injuries hurt more and heal slower.
+    pub is_infected: bool,
+}
+
+impl Injury {
+    fn healing_per_day(&self) -> f32 {
+        let healing =
+            HEAL_PER_DAY * self.treatment.map_or(1.0, Treatment::healing);
+        if self.is_infected {
+            healing / 2.0
+        } else {
+            healing
+        }
+    }
+
+    fn pain(&self) -> f32 {
+        let pain = self.severity * self.kind.pain();
+        if self.is_infected {
+            pain * 1.5
+        } else {
+            pain
+        }
+    }
+}
+
+/// Injuries of a colonist and the blood they lost.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Injuries {
+    injuries: Vec<Injury>,
+    blood_loss: f32,
+}
+
+impl Injuries {
+    pub fn add(&mut self, part: BodyPart, kind: InjuryKind, severity: f32) {
+        self.injuries.push(Injury {
+            part,
+            kind,
+            severity,
+            treatment: None,
+            is_infected: false,
+        });
+    }
+
+    /// Between `0.0` and `1.0`.
+    pub fn blood_loss(&self) -> f32 {
+        self.blood_loss
+    }
+
+    /// Blood lost in a game day.
+    pub fn bleeding(&self) -> f32 {
+        self.injuries
+            .iter()
+            .filter(|i| i.treatment.is_none())
+            .map(|i| i.severity * i.kind.bleeding())
+            .sum()
+    }
+
+    /// Colonists who are downed cannot move or work.
+    pub fn is_downed(&self) -> bool {
+        self.pain() >= DOWNED_PAIN
+            || self.blood_loss >= DOWNED_BLOOD_LOSS
+            || BodyPart::ALL
+                .iter()
+                .any(|part| self.severity_of(*part) >= part.max_hp())
+    }
+
+    /// Colonists who lost all their blood, or whose head or torso is
+    /// damaged beyond repair, die.
+    pub fn is_fatal(&self) -> bool {
+        self.blood_loss >= 1.0
+            || [BodyPart::Head, BodyPart::Torso].iter().any(|part| {
+                self.severity_of(*part) >= part.max_hp() * FATAL_DAMAGE
+            })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Injury> {
+        self.injuries.iter()
+    }
+
+    pub fn needs_tending(&self) -> bool {
+        self.injuries.iter().any(|i| i.treatment.is_none())
+    }
+
+    /// Between `0.0` and `1.0`.
+    pub fn pain(&self) -> f32 {
+        self.injuries.iter().map(Injury::pain).sum::<f32>().min(1.0)
+    }
+
+    /// Tend all injuries that are not tended yet, some of them may get
+    /// infected.  Returns the number of infected injuries.
+    pub fn tend(
+        &mut self,
+        treatment: Treatment,
+        random: &mut Random,
+    ) -> usize {
+        let infection_chance = match treatment {
+            Treatment::Bandaged => INFECTION_CHANCE,
+            Treatment::Medicine => INFECTION_CHANCE_WITH_MEDICINE,
+        };
+        let mut infected = 0;
+        for injury in self.injuries.iter_mut() {
+            if injury.treatment.is_none() {
+                injury.treatment = Some(treatment);
+                if random.chance(infection_chance) {
+                    injury.is_infected = true;
+                    infected += 1;
+                }
+            }
+        }
+        infected
+    }
+
+    /// Bleed, heal and forget the healed injuries.  Healing is faster when
+    /// resting in a bed.
+    pub fn tick(&mut self, game_dt_millis: u32, is_in_bed: bool) {
+        let days = game_dt_millis as f32 / MILLIS_PER_DAY as f32;
+        let bleeding = self.bleeding();
+        self.blood_loss = if bleeding > 0.0 {
+            (self.blood_loss + bleeding * days).min(1.0)
+        } else {
+            (self.blood_loss - BLOOD_PER_DAY * days).max(0.0)
+        };
+        let rest = if is_in_bed { 1.5 } else { 1.0 };
+        for injury in self.injuries.iter_mut() {
+            injury.severity -= injury.healing_per_day() * rest * days;
+        }
+        self.injuries.retain(|i| i.severity > 0.0);
+    }
+
+    fn severity_of(&self, part: BodyPart) -> f32 {
+        self.injuries
+            .iter()
+            .filter(|i| i.part == part)
+            .map(|i| i.severity)
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn injuries_heal() {
+        let mut injuries = Injuries::default();
+        injuries.add(BodyPart::LeftArm, InjuryKind::Bruise, 3.0);
+        injuries.tick(MILLIS_PER_DAY, false);
+        assert_eq!(1, injuries.iter().count());
+        injuries.tick(MILLIS_PER_DAY, false);
+        assert_eq!(0, injuries.iter().count());
+    }
+
+    #[test]
+    fn untended_cuts_bleed() {
+        let mut injuries = Injuries::default();
+        injuries.add(BodyPart::Torso, InjuryKind::Cut, 20.0);
+        assert!(!injuries.is_downed());
+        injuries.tick(MILLIS_PER_DAY, false);
+        assert!(injuries.is_downed());
+        injuries.tend(Treatment::Medicine, &mut Random::new(1));
+        assert_eq!(0.0, injuries.bleeding());
+        assert!(!injuries.needs_tending());
+    }
+
+    #[test]
+    fn destroyed_body_parts_down_colonists() {
+        let mut injuries = Injuries::default();
+        injuries.add(BodyPart::Head, InjuryKind::Bruise, 25.0);
+        assert!(injuries.pain() < DOWNED_PAIN);
+        assert!(injuries.is_downed());
+    }
+
+    #[test]
+    fn vital_injuries_are_fatal() {
+        let mut injuries = Injuries::default();
+        injuries.add(BodyPart::LeftLeg, InjuryKind::Bruise, 100.0);
+        assert!(!injuries.is_fatal());
+        injuries.add(BodyPart::Torso, InjuryKind::Cut, 80.0);
+        assert!(injuries.is_fatal());
+        let mut bleeding = Injuries::default();
+        bleeding.add(BodyPart::RightArm, InjuryKind::Cut, 15.0);
+        bleeding.tick(MILLIS_PER_DAY, false);
+        assert!(bleeding.is_downed() && !bleeding.is_fatal());
+        bleeding.tick(MILLIS_PER_DAY * 2, false);
+        assert!(bleeding.is_fatal());
+    }
+
+    #[test]
+    fn medicine_heals_faster() {
+        let mut bandaged = Injuries::default();
+        bandaged.add(BodyPart::RightLeg, InjuryKind::Bruise, 10.0);
+        let mut medicine = bandaged.clone();
+        bandaged.tend(Treatment::Bandaged, &mut Random::new(3));
+        medicine.tend(Treatment::Medicine, &mut Random::new(3));
+        bandaged.tick(MILLIS_PER_DAY / 2, false);
+        medicine.tick(MILLIS_PER_DAY / 2, false);
+        assert!(medicine.pain() < bandaged.pain());
+    }
+}
@@ -0,0 +1,199 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::food::Food;
+use crate::data::{Crop, MealQuality};
+use serde::Deserialize;
+use std::fmt::{Display, Formatter, Result};
+use std::str::FromStr;
+
+/// Corpses rot slower than meat, they are too big to spoil quickly.
+const CORPSE_SHELF_LIFE_DAYS: u16 = 6;
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub enum ItemKind {
+    Berries,
+    Bow,
+    Corpse,
+    Egg,
+    FineMeal,
+    Hammer,
+    Helmet,
+    Knife,
+    Leather,
+    Log,
+    Medicine,
+    Milk,
+    Parka,
+    Plank,
+    RawMeat,
+    SimpleMeal,
+    Silver,
+    Stone,
+    StoneBlock,
+    Wool,
+}
+
+impl ItemKind {
+    pub const ALL: &'static [ItemKind] = &[
+        ItemKind::Berries,
+        ItemKind::Bow,
+        ItemKind::Corpse,
+        ItemKind::Egg,
+        ItemKind::FineMeal,
+        ItemKind::Hammer,
+        ItemKind::Helmet,
+        ItemKind::Knife,
+        ItemKind::Leather,
+        ItemKind::Log,
+        ItemKind::Medicine,
+        ItemKind::Milk,
+        ItemKind::Parka,
+        ItemKind::Plank,
+        ItemKind::RawMeat,
+        ItemKind::SimpleMeal,
+        ItemKind::Silver,
+        ItemKind::Stone,
+        ItemKind::StoneBlock,
+        ItemKind::Wool,
+    ];
+
+    /// How the item is grown, `None` if it is not a crop.
+    pub fn crop(self) -> Option<Crop> {
+        let (growing_days, harvest_yield) = match self {
+            ItemKind::Berries => (6, 20),
+            _ => return None,
+        };
+        Some(Crop {
+            growing_days,
+            harvest_yield,
+        })
+    }
+
+    /// Food properties of the item, `None` if the item is not edible.
+    pub fn food(self) -> Option<Food> {
+        let (nutrition, quality, shelf_life_days) = match self {
+            ItemKind::Berries => (0.1, MealQuality::Raw, 5),
+            ItemKind::Egg => (0.15, MealQuality::Raw, 4),
+            ItemKind::FineMeal => (0.9, MealQuality::Fine, 4),
+            ItemKind::Milk => (0.1, MealQuality::Raw, 2),
+            ItemKind::RawMeat => (0.2, MealQuality::Raw, 2),
+            ItemKind::SimpleMeal => (0.9, MealQuality::Simple, 4),
+            _ => return None,
+        };
+        Some(Food {
+            nutrition,
+            quality,
+            shelf_life_days,
+        })
+    }
+
+    /// Days it takes the item to rot at the normal rate, `None` if it
+    /// does not rot.
+    pub fn shelf_life_days(self) -> Option<u16> {
+        match self {
+            ItemKind::Corpse => Some(CORPSE_SHELF_LIFE_DAYS),
+            kind => kind.food().map(|food| food.shelf_life_days),
+        }
+    }
+
+    /// Days the item lasts when left outdoors before the weather destroys
+    /// it, `None` if the weather does not harm it.
+    pub fn weathering_days(self) -> Option<u16> {
+        match self {
+            ItemKind::Leather | ItemKind::Medicine | ItemKind::Wool => {
+                Some(10)
+            }
+            ItemKind::Log | ItemKind::Plank => Some(20),
+            ItemKind::Bow
+            | ItemKind::Hammer
+            | ItemKind::Helmet
+            | ItemKind::Knife
+            | ItemKind::Parka => Some(30),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct UnrecognizedItemName(String);
+
+impl FromStr for ItemKind {
+    type Err = UnrecognizedItemName;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "Berries" => Ok(ItemKind::Berries),
+            "Bow" => Ok(ItemKind::Bow),
+            "Corpse" => Ok(ItemKind::Corpse),
+            "Egg" => Ok(ItemKind::Egg),
+            "FineMeal" => Ok(ItemKind::FineMeal),
+            "Hammer" => Ok(ItemKind::Hammer),
+            "Helmet" => Ok(ItemKind::Helmet),
+            "Knife" => Ok(ItemKind::Knife),
+            "Leather" => Ok(ItemKind::Leather),
+            "Log" => Ok(ItemKind::Log),
+            "Medicine" => Ok(ItemKind::Medicine),
+            "Milk" => Ok(ItemKind::Milk),
+            "Parka" => Ok(ItemKind::Parka),
+            "Plank" => Ok(ItemKind::Plank),
+            "RawMeat" => Ok(ItemKind::RawMeat),
+            "SimpleMeal" => Ok(ItemKind::SimpleMeal),
+            "Silver" => Ok(ItemKind::Silver),
+            "Stone" => Ok(ItemKind::Stone),
+            "StoneBlock" => Ok(ItemKind::StoneBlock),
+            "Wool" => Ok(ItemKind::Wool),
+            _ => Err(UnrecognizedItemName(s.to_owned())),
+        }
+    }
+}
+
+impl Display for UnrecognizedItemName {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "unrecognized item name: {}", self.0)
+    }
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            ItemKind::Berries => "Berries",
+            ItemKind::Bow => "Bow",
+            ItemKind::Corpse => "Corpse",
+            ItemKind::Egg => "Egg",
+            ItemKind::FineMeal => "Fine meal",
+            ItemKind::Hammer => "Hammer",
+            ItemKind::Helmet => "Helmet",
+            ItemKind::Knife => "Knife",
+            ItemKind::Leather => "Leather",
+            ItemKind::Log => "Log",
+            ItemKind::Medicine => "Medicine",
+            ItemKind::Milk => "Milk",
+            ItemKind::Parka => "Parka",
+            ItemKind::Plank => "Plank",
+            ItemKind::RawMeat => "Raw meat",
+            ItemKind::SimpleMeal => "Simple meal",
+            ItemKind::Silver => "Silver",
+            ItemKind::Stone => "Stone",
+            ItemKind::StoneBlock => "Stone block",
+            ItemKind::Wool => "Wool",
+        };
+        write!(f, "{}", name)
+    }
+}
@@ -32,6 +32,13 @@ impl Location {
         Location::new(0, 0)
     }
 
+    /// Number of steps between two locations, diagonal steps included.
+    pub fn distance_to(self, other: Location) -> u32 {
+        let dx = (other.x - self.x).unsigned_abs();
+        let dy = (other.y - self.y).unsigned_abs();
+        dx.max(dy)
+    }
+
     pub fn move_by(self, dx: i32, dy: i32) -> Location {
         Location::new(self.x + dx, self.y + dy)
     }
@@ -49,6 +56,13 @@ impl Location {
 mod tests {
     use super::*;
 
+    #[test]
+    fn distance_to_counts_diagonal_steps_as_one() {
+        assert_eq!(0, Location::origin().distance_to(Location::origin()));
+        assert_eq!(3, Location::origin().distance_to(Location::new(3, -2)));
+        assert_eq!(7, Location::new(-2, 5).distance_to(Location::new(1, -2)));
+    }
+
     #[test]
     fn move_by_adds_differences_to_the_coordinates() {
         assert_eq!(Location::new(10, 0), Location::new(5, 0).move_by(5, 0));
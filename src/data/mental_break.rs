@@ -0,0 +1,56 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter, Result};
+
+/// Mood below which colonists may break.  A bad day of going hungry and
+/// tired, sleeping outside and eating raw food is enough.
+pub const MENTAL_BREAK_THRESHOLD: u8 = 35;
+
+/// Behaviors that take over colonists with very low mood.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MentalBreakKind {
+    FoodBinge,
+    Sulk,
+    Wander,
+}
+
+impl MentalBreakKind {
+    pub const ALL: &'static [MentalBreakKind] = &[
+        MentalBreakKind::FoodBinge,
+        MentalBreakKind::Sulk,
+        MentalBreakKind::Wander,
+    ];
+}
+
+impl Display for MentalBreakKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            MentalBreakKind::FoodBinge => "food binge",
+            MentalBreakKind::Sulk => "sulking",
+            MentalBreakKind::Wander => "wandering in a daze",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A mental break that is going on, `remaining_millis` is in game time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MentalBreak {
+    pub kind: MentalBreakKind,
+    pub remaining_millis: u32,
+}
@@ -0,0 +1,318 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Biome, Random};
+use ron::de;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::iter;
+
+/// Letters a generated letter depends on.
+const ORDER: usize = 2;
+/// Marks the beginning of a word in the chain.
+const START: char = '^';
+/// Marks the end of a word in the chain.
+const END: char = '$';
+const MIN_LENGTH: usize = 3;
+const MAX_LENGTH: usize = 10;
+/// Words generated before giving up on a new one of the right length.
+const MAX_TRIES: usize = 50;
+
+/// Markov chain of letters, learns which letters follow which from a list
+/// of words and makes up new words that sound like them.
+#[derive(Clone, Debug, Default)]
+pub struct MarkovChain {
+    /// Letters following the last [ORDER] letters, and how often they do.
+    transitions: BTreeMap<String, BTreeMap<char, u32>>,
+    /// Words learned from, lowercase.
+    words: BTreeSet<String>,
+}
+
+impl MarkovChain {
+    pub fn train<S: AsRef<str>>(words: &[S]) -> MarkovChain {
+        let mut chain = MarkovChain::default();
+        for word in words {
+            let word = word.as_ref().to_lowercase();
+            let letters: Vec<char> = iter::repeat_n(START, ORDER)
+                .chain(word.chars())
+                .chain(iter::once(END))
+                .collect();
+            for window in letters.windows(ORDER + 1) {
+                let state: String = window[..ORDER].iter().collect();
+                *chain
+                    .transitions
+                    .entry(state)
+                    .or_default()
+                    .entry(window[ORDER])
+                    .or_default() += 1;
+            }
+            chain.words.insert(word);
+        }
+        chain
+    }
+
+    /// A capitalized word that is not one of the words learned from, unless
+    /// the chain cannot come up with a new one.
+    pub fn generate(&self, random: &mut Random) -> String {
+        let mut word = String::new();
+        for _ in 0..MAX_TRIES {
+            word = self.walk(random);
+            let length = word.chars().count();
+            if (MIN_LENGTH..=MAX_LENGTH).contains(&length)
+                && !self.words.contains(&word)
+            {
+                break;
+            }
+        }
+        let mut letters = word.chars();
+        match letters.next() {
+            Some(first) => first.to_uppercase().chain(letters).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// Follow the chain from the start of a word to its end.
+    fn walk(&self, random: &mut Random) -> String {
+        let mut state: Vec<char> = vec![START; ORDER];
+        let mut word = String::new();
+        while word.chars().count() <= MAX_LENGTH {
+            let key: String = state.iter().collect();
+            let followers = match self.transitions.get(&key) {
+                Some(followers) => followers,
+                None => break,
+            };
+            let total: u32 = followers.values().sum();
+            let mut roll = random.below(total);
+            let mut next = END;
+            for (letter, count) in followers.iter() {
+                if roll < *count {
+                    next = *letter;
+                    break;
+                }
+                roll -= count;
+            }
+            if next == END {
+                break;
+            }
+            word.push(next);
+            state.remove(0);
+            state.push(next);
+        }
+        word
+    }
+}
+
+#[derive(Deserialize)]
+struct CultureLists {
+    name: String,
+    people: Vec<String>,
+    places: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct NameLists {
+    cultures: Vec<CultureLists>,
+    factions: Vec<String>,
+    regions: Vec<(Biome, Vec<String>)>,
+}
+
+/// A people with names of their own.
+#[derive(Clone, Debug)]
+pub struct Culture {
+    pub name: String,
+    people: MarkovChain,
+    places: MarkovChain,
+}
+
+impl Culture {
+    pub fn person(&self, random: &mut Random) -> String {
+        self.people.generate(random)
+    }
+
+    pub fn place(&self, random: &mut Random) -> String {
+        self.places.generate(random)
+    }
+}
+
+/// Name generator for colonists, factions, regions and the colony.
+///
+/// Names are made up from the word lists of `assets/names.ron`, the same
+/// seed of [Random] gives the same names.
+#[derive(Clone, Debug, Default)]
+pub struct Names {
+    cultures: Vec<Culture>,
+    factions: Vec<String>,
+    regions: BTreeMap<Biome, Vec<String>>,
+}
+
+impl Names {
+    pub fn from_ron(source: &str) -> Result<Names, de::Error> {
+        let lists: NameLists = de::from_str(source)?;
+        if let Some(form) = lists.factions.iter().find(|f| !f.contains("{}")) {
+            return Err(de::Error::Message(format!(
+                "faction name {} has no {{}}",
+                form
+            )));
+        }
+        Ok(Names {
+            cultures: lists
+                .cultures
+                .into_iter()
+                .map(|c| Culture {
+                    name: c.name,
+                    people: MarkovChain::train(&c.people),
+                    places: MarkovChain::train(&c.places),
+                })
+                .collect(),
+            factions: lists.factions,
+            regions: lists.regions.into_iter().collect(),
+        })
+    }
+
+    pub fn culture(&self, idx: usize) -> Option<&Culture> {
+        self.cultures.get(idx)
+    }
+
+    /// Index of a random culture.
+    pub fn pick_culture(&self, random: &mut Random) -> usize {
+        random.below(self.cultures.len() as u32) as usize
+    }
+
+    /// A faction name with a place name of `culture` in it.
+    pub fn faction(&self, culture: &Culture, random: &mut Random) -> String {
+        let form = random.pick(&self.factions).expect("No faction names");
+        form.replacen("{}", &culture.place(random), 1)
+    }
+
+    /// Name of the `biome` around the colony.
+    pub fn region(
+        &self,
+        culture: &Culture,
+        biome: Biome,
+        random: &mut Random,
+    ) -> String {
+        let place = culture.place(random);
+        match self
+            .regions
+            .get(&biome)
+            .and_then(|words| random.pick(words))
+        {
+            Some(word) => format!("{} {}", place, word),
+            None => place,
+        }
+    }
+}
+
+/// A name from `generate` that is not taken yet.  After [MAX_TRIES] taken
+/// names the last one is numbered instead.
+pub fn unique_name(
+    mut generate: impl FnMut() -> String,
+    is_taken: impl Fn(&str) -> bool,
+) -> String {
+    let mut name = generate();
+    for _ in 0..MAX_TRIES {
+        if !is_taken(&name) {
+            return name;
+        }
+        name = generate();
+    }
+    (2..)
+        .map(|n| format!("{} {}", name, n))
+        .find(|numbered| !is_taken(numbered))
+        .expect("No free number")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+
+    fn names() -> Names {
+        Names::from_ron(asset::NAMES).unwrap()
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_names() {
+        let names = names();
+        let generate = |seed| {
+            let mut random = Random::new(seed);
+            let culture = names.culture(names.pick_culture(&mut random));
+            let culture = culture.unwrap();
+            vec![
+                culture.person(&mut random),
+                culture.place(&mut random),
+                names.faction(culture, &mut random),
+                names.region(culture, Biome::Meadow, &mut random),
+            ]
+        };
+        assert_eq!(generate(4), generate(4));
+        assert_ne!(generate(4), generate(5));
+    }
+
+    #[test]
+    fn generated_names_are_new_words_of_the_right_length() {
+        let names = names();
+        let mut random = Random::new(1);
+        for culture in names.cultures.iter() {
+            for _ in 0..50 {
+                let name = culture.person(&mut random);
+                let length = name.chars().count();
+                assert!(
+                    (MIN_LENGTH..=MAX_LENGTH).contains(&length),
+                    "{}",
+                    name
+                );
+                assert!(name.chars().next().unwrap().is_uppercase());
+                assert!(!culture.people.words.contains(&name.to_lowercase()));
+            }
+        }
+    }
+
+    #[test]
+    fn taken_names_are_numbered_when_no_new_one_comes_up() {
+        let taken = ["Ada", "Ada 2"];
+        let name = unique_name(|| "Ada".to_owned(), |n| taken.contains(&n));
+        assert_eq!("Ada 3", name);
+    }
+
+    #[test]
+    fn chains_only_use_letters_that_follow_each_other() {
+        let chain = MarkovChain::train(&["abc"]);
+        let mut random = Random::new(2);
+        // The only way through the chain is the word learned from.
+        assert_eq!("Abc", chain.generate(&mut random));
+    }
+
+    #[test]
+    fn from_ron_fails_for_faction_names_without_a_place() {
+        let error = Names::from_ron(
+            r#"(cultures: [], factions: ["The Order"], regions: [])"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("faction name The Order"));
+    }
+
+    #[test]
+    fn regions_and_factions_have_a_place_in_their_name() {
+        let names = names();
+        let mut random = Random::new(3);
+        let culture = names.culture(0).unwrap();
+        let faction = names.faction(culture, &mut random);
+        assert!(!faction.contains("{}"));
+        let region = names.region(culture, Biome::Scrubland, &mut random);
+        assert_eq!(2, region.split(' ').count());
+    }
+}
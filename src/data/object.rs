@@ -15,15 +15,37 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::data::{AnimalKind, ItemKind, Structure, WorkbenchKind};
 use std::str::FromStr;
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, PartialOrd)]
 pub enum VisibleObject {
+    Animal(AnimalKind),
+    Bed,
+    Blueprint(Structure),
+    Colonist,
+    /// A field sown with the crop.
+    Crop(ItemKind),
+    /// A built barricade, sandbags or trap.
+    Defense(Structure),
+    /// A field nothing is sown on.
+    Field,
     Grass,
+    Grave,
+    Item(ItemKind),
+    Monument,
+    Pen,
+    /// An arrow in flight, drawn for a moment.
+    Projectile,
+    Raider,
     Rock,
     Soil,
+    Stockpile,
+    Trader,
     TreeTrunk,
     TreeFoilage,
+    Wall,
+    Workbench(WorkbenchKind),
 }
 
 #[derive(Debug)]
@@ -37,12 +59,24 @@ impl FromStr for VisibleObject {
     //       this doesn't serve any purpose.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
+            "Bed" => Ok(VisibleObject::Bed),
+            "Colonist" => Ok(VisibleObject::Colonist),
             "Grass" => Ok(VisibleObject::Grass),
+            "Pen" => Ok(VisibleObject::Pen),
             "Rock" => Ok(VisibleObject::Rock),
             "Soil" => Ok(VisibleObject::Soil),
+            "Stockpile" => Ok(VisibleObject::Stockpile),
             "TreeTrunk" => Ok(VisibleObject::TreeTrunk),
             "TreeFoilage" => Ok(VisibleObject::TreeFoilage),
-            _ => Err(UnrecognizedTileName(s.to_owned())),
+            "Wall" => Ok(VisibleObject::Wall),
+            "Stove" => Ok(VisibleObject::Workbench(WorkbenchKind::Stove)),
+            "Workbench" => {
+                Ok(VisibleObject::Workbench(WorkbenchKind::Crafting))
+            }
+            _ => s
+                .parse::<ItemKind>()
+                .map(VisibleObject::Item)
+                .map_err(|_| UnrecognizedTileName(s.to_owned())),
         }
     }
 }
@@ -0,0 +1,130 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Season, DAYS_PER_SEASON};
+use std::fmt::{Display, Formatter, Result};
+
+/// Years the colony has to last for the survival goal.
+pub const SURVIVAL_YEARS: u16 = 3;
+
+/// What the colony plays for, picked when starting a new game.  Whatever
+/// the goal, the game is lost once all the colonists are dead.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Goal {
+    /// Play until the colony is gone.
+    #[default]
+    Endless,
+    Survive,
+    Monument,
+}
+
+impl Goal {
+    pub const ALL: &'static [Goal] =
+        &[Goal::Endless, Goal::Survive, Goal::Monument];
+
+    pub fn next(self) -> Goal {
+        let idx = Self::ALL.iter().position(|g| *g == self).unwrap();
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Goal {
+        let idx = Self::ALL.iter().position(|g| *g == self).unwrap();
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+}
+
+impl Display for Goal {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Goal::Endless => write!(f, "Endless"),
+            Goal::Survive => write!(f, "Survive {} years", SURVIVAL_YEARS),
+            Goal::Monument => write!(f, "Build a monument"),
+        }
+    }
+}
+
+/// How a game ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Lost,
+    Won(Goal),
+}
+
+impl Outcome {
+    /// Outcome of a game with the `goal` on game `day`, with `colonists`
+    /// alive and whether a monument is built.  `None` if the game goes on.
+    pub fn of(
+        goal: Goal,
+        day: u16,
+        colonists: usize,
+        has_monument: bool,
+    ) -> Option<Outcome> {
+        let days_per_year = DAYS_PER_SEASON * Season::ALL.len() as u16;
+        if colonists == 0 {
+            Some(Outcome::Lost)
+        } else if goal == Goal::Survive
+            && day >= SURVIVAL_YEARS * days_per_year
+            || goal == Goal::Monument && has_monument
+        {
+            Some(Outcome::Won(goal))
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Outcome::Lost => write!(f, "All the colonists are dead."),
+            Outcome::Won(Goal::Monument) => {
+                write!(f, "The colony built a monument.")
+            }
+            Outcome::Won(_) => {
+                write!(f, "The colony survived {} years.", SURVIVAL_YEARS)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn games_are_lost_when_all_colonists_are_dead() {
+        assert_eq!(None, Outcome::of(Goal::Endless, 500, 1, true));
+        assert_eq!(
+            Some(Outcome::Lost),
+            Outcome::of(Goal::Monument, 3, 0, true)
+        );
+    }
+
+    #[test]
+    fn games_are_won_when_the_goal_is_met() {
+        let end = SURVIVAL_YEARS * DAYS_PER_SEASON * 4;
+        assert_eq!(None, Outcome::of(Goal::Survive, end - 1, 3, true));
+        assert_eq!(
+            Some(Outcome::Won(Goal::Survive)),
+            Outcome::of(Goal::Survive, end, 3, false)
+        );
+        assert_eq!(None, Outcome::of(Goal::Monument, end, 3, false));
+        assert_eq!(
+            Some(Outcome::Won(Goal::Monument)),
+            Outcome::of(Goal::Monument, 2, 3, true)
+        );
+    }
+}
@@ -0,0 +1,158 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Location, Rectangle};
+use std::collections::{BTreeMap, VecDeque};
+
+/// Find the shortest path from `from` to `to`, avoiding blocked tiles.
+///
+/// Diagonal steps are allowed only if they don't cut a corner.  The
+/// returned path excludes `from` and includes `to`, it is empty if `from`
+/// and `to` are the same.  Search is limited to `bounds`.
+pub fn find_path<F>(
+    from: Location,
+    to: Location,
+    bounds: Rectangle,
+    is_blocked: F,
+) -> Option<Vec<Location>>
+where
+    F: Fn(Location) -> bool,
+{
+    if !bounds.contains(to) || is_blocked(to) {
+        return None;
+    }
+    let mut came_from: BTreeMap<Location, Location> = BTreeMap::new();
+    let mut frontier: VecDeque<Location> = VecDeque::new();
+    frontier.push_back(from);
+    while let Some(current) = frontier.pop_front() {
+        if current == to {
+            let mut path = vec![];
+            let mut loc = to;
+            while loc != from {
+                path.push(loc);
+                loc = came_from[&loc];
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for (dx, dy) in NEIGHBOURS.iter() {
+            let next = current.move_by(*dx, *dy);
+            let is_diagonal = *dx != 0 && *dy != 0;
+            if next == from
+                || !bounds.contains(next)
+                || came_from.contains_key(&next)
+                || is_blocked(next)
+                || (is_diagonal
+                    && (is_blocked(current.move_by(*dx, 0))
+                        || is_blocked(current.move_by(0, *dy))))
+            {
+                continue;
+            }
+            came_from.insert(next, current);
+            frontier.push_back(next);
+        }
+    }
+    None
+}
+
+const NEIGHBOURS: [(i32, i32); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn bounds() -> Rectangle {
+        Rectangle::centered_around(Location::origin(), 11, 11)
+    }
+
+    #[test]
+    fn path_to_the_same_location_is_empty() {
+        let loc = Location::new(1, 1);
+        assert_eq!(Some(vec![]), find_path(loc, loc, bounds(), |_| false));
+    }
+
+    #[test]
+    fn path_on_open_ground_takes_diagonal_steps() {
+        let path = find_path(
+            Location::origin(),
+            Location::new(3, 2),
+            bounds(),
+            |_| false,
+        )
+        .unwrap();
+        assert_eq!(3, path.len());
+        assert_eq!(Some(&Location::new(3, 2)), path.last());
+    }
+
+    #[test]
+    fn path_goes_around_walls() {
+        // A vertical wall between origin and the target, with a gap at the
+        // bottom.
+        let walls: BTreeSet<Location> =
+            (-5..4).map(|y| Location::new(1, y)).collect();
+        let path = find_path(
+            Location::origin(),
+            Location::new(2, 0),
+            bounds(),
+            |loc| walls.contains(&loc),
+        )
+        .unwrap();
+        assert!(path.iter().all(|loc| !walls.contains(loc)));
+        assert!(path.contains(&Location::new(1, 4)));
+    }
+
+    #[test]
+    fn diagonal_steps_do_not_cut_corners() {
+        let walls: BTreeSet<Location> =
+            vec![Location::new(1, 0), Location::new(0, 1)]
+                .into_iter()
+                .collect();
+        let path = find_path(
+            Location::origin(),
+            Location::new(1, 1),
+            bounds(),
+            |loc| walls.contains(&loc),
+        )
+        .unwrap();
+        assert!(path.len() > 1);
+    }
+
+    #[test]
+    fn there_is_no_path_into_an_enclosed_area() {
+        let target = Location::new(3, 3);
+        let walls: BTreeSet<Location> =
+            Rectangle::centered_around(target, 3, 3)
+                .into_iter()
+                .filter(|loc| *loc != target)
+                .collect();
+        assert_eq!(
+            None,
+            find_path(Location::origin(), target, bounds(), |loc| walls
+                .contains(&loc))
+        );
+    }
+}
@@ -0,0 +1,265 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    ItemKind, Random, CARAVAN_CAMP_HOURS, MILLIS_PER_DAY, MILLIS_PER_HOUR,
+};
+use std::fmt::{Display, Formatter, Result};
+
+/// Game day the first delivery may be asked for.
+pub const FIRST_QUEST_DAY: u16 = 2;
+/// Quests the colony can have at once.
+pub const MAX_ACTIVE_QUESTS: usize = 3;
+const MAX_DAYS_BETWEEN_QUESTS: i32 = 6;
+const MIN_DAYS_BETWEEN_QUESTS: i32 = 3;
+/// Game time the colony has to deliver the goods asked for.
+const DELIVERY_DAYS: u32 = 5;
+/// Goods a faction may ask for and how many.
+const DELIVERIES: &[(ItemKind, u16)] = &[
+    (ItemKind::Berries, 40),
+    (ItemKind::Leather, 10),
+    (ItemKind::Log, 50),
+    (ItemKind::Medicine, 5),
+    (ItemKind::Plank, 30),
+    (ItemKind::Stone, 40),
+    (ItemKind::Wool, 15),
+];
+/// A delivery pays this much more than the goods are worth.
+const DELIVERY_BONUS: f64 = 1.5;
+/// Game time the colony has to beat the raiders after a caravan, long
+/// enough for the caravan to leave.
+const DEFENSE_HOURS: u32 = CARAVAN_CAMP_HOURS + 8;
+
+/// What the colony is asked to do.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Objective {
+    /// Stockpile the goods for the faction to collect.
+    Deliver { kind: ItemKind, quantity: u16 },
+    /// Keep at least one colonist alive until the winter is over.
+    SurviveWinter,
+    /// Keep the traders of the camping caravan on their feet while
+    /// raiders are around.
+    DefendCaravan,
+}
+
+impl Display for Objective {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Objective::Deliver { kind, quantity } => {
+                write!(f, "Deliver {} {}", quantity, kind)
+            }
+            Objective::SurviveWinter => write!(f, "Survive the winter"),
+            Objective::DefendCaravan => write!(f, "Defend the caravan"),
+        }
+    }
+}
+
+/// Paid by the faction offering a quest once it is completed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Reward {
+    pub silver: u16,
+    pub goodwill: i32,
+}
+
+impl Display for Reward {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{} silver, {:+} goodwill", self.silver, self.goodwill)
+    }
+}
+
+/// Where a quest stands.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum QuestStatus {
+    Active,
+    Completed,
+    Failed,
+}
+
+/// An objective offered by a faction, with a deadline and a reward.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quest {
+    /// Index of the faction in [Factions](crate::data::Factions).
+    pub faction: usize,
+    pub objective: Objective,
+    pub reward: Reward,
+    /// Game time left until the deadline, in milliseconds.
+    millis_left: u32,
+    progress: u32,
+    goal: u32,
+}
+
+impl Quest {
+    pub fn new(
+        faction: usize,
+        objective: Objective,
+        reward: Reward,
+        millis_left: u32,
+        goal: u32,
+    ) -> Quest {
+        Quest {
+            faction,
+            objective,
+            reward,
+            millis_left,
+            progress: 0,
+            goal,
+        }
+    }
+
+    /// A random delivery for `faction` to ask for.
+    pub fn delivery(faction: usize, random: &mut Random) -> Quest {
+        let (kind, quantity) = *random.pick(DELIVERIES).unwrap();
+        let value = kind.value() * f64::from(quantity) * DELIVERY_BONUS;
+        Quest::new(
+            faction,
+            Objective::Deliver { kind, quantity },
+            Reward {
+                silver: value.round() as u16,
+                goodwill: 10,
+            },
+            DELIVERY_DAYS * MILLIS_PER_DAY,
+            u32::from(quantity),
+        )
+    }
+
+    /// Keeping a colonist alive for the `days` left in the winter.
+    pub fn survive_winter(faction: usize, days: u16) -> Quest {
+        Quest::new(
+            faction,
+            Objective::SurviveWinter,
+            Reward {
+                silver: 200,
+                goodwill: 5,
+            },
+            u32::from(days) * MILLIS_PER_DAY,
+            u32::from(days),
+        )
+    }
+
+    /// Defending the caravan of `faction` from `raiders`.
+    pub fn defend_caravan(faction: usize, raiders: usize) -> Quest {
+        Quest::new(
+            faction,
+            Objective::DefendCaravan,
+            Reward {
+                silver: 100,
+                goodwill: 15,
+            },
+            DEFENSE_HOURS * MILLIS_PER_HOUR,
+            raiders as u32,
+        )
+    }
+
+    /// Count down to the deadline.
+    pub fn advance(&mut self, game_dt_millis: u32) {
+        self.millis_left = self.millis_left.saturating_sub(game_dt_millis);
+    }
+
+    /// Game time left until the deadline, in milliseconds.
+    pub fn millis_left(&self) -> u32 {
+        self.millis_left
+    }
+
+    pub fn goal(&self) -> u32 {
+        self.goal
+    }
+
+    pub fn progress(&self) -> u32 {
+        self.progress
+    }
+
+    pub fn set_progress(&mut self, progress: u32) {
+        self.progress = progress.min(self.goal);
+    }
+
+    /// Quests that reach the deadline before their goal fail.
+    pub fn status(&self) -> QuestStatus {
+        if self.progress >= self.goal {
+            QuestStatus::Completed
+        } else if self.millis_left == 0 {
+            QuestStatus::Failed
+        } else {
+            QuestStatus::Active
+        }
+    }
+
+    /// Game time left until the deadline, in days and hours.
+    pub fn time_left(&self) -> String {
+        let hours = self.millis_left / MILLIS_PER_HOUR;
+        format!("{}d {}h", hours / 24, hours % 24)
+    }
+}
+
+pub fn days_until_next_quest(random: &mut Random) -> u16 {
+    random.range(MIN_DAYS_BETWEEN_QUESTS, MAX_DAYS_BETWEEN_QUESTS) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quest() -> Quest {
+        Quest::new(
+            0,
+            Objective::Deliver {
+                kind: ItemKind::Log,
+                quantity: 50,
+            },
+            Reward {
+                silver: 100,
+                goodwill: 10,
+            },
+            MILLIS_PER_DAY,
+            50,
+        )
+    }
+
+    #[test]
+    fn quests_complete_once_the_goal_is_reached() {
+        let mut quest = quest();
+        quest.set_progress(30);
+        quest.advance(MILLIS_PER_HOUR);
+        assert_eq!(QuestStatus::Active, quest.status());
+        assert_eq!("0d 23h", quest.time_left());
+        quest.set_progress(80);
+        assert_eq!(50, quest.progress());
+        assert_eq!(QuestStatus::Completed, quest.status());
+    }
+
+    #[test]
+    fn quests_fail_at_the_deadline() {
+        let mut quest = quest();
+        quest.set_progress(49);
+        quest.advance(MILLIS_PER_DAY);
+        assert_eq!(QuestStatus::Failed, quest.status());
+    }
+
+    #[test]
+    fn deliveries_pay_more_than_the_goods_are_worth() {
+        let mut random = Random::new(7);
+        let quest = Quest::delivery(1, &mut random);
+        match quest.objective {
+            Objective::Deliver { kind, quantity } => {
+                assert_eq!(u32::from(quantity), quest.goal());
+                assert!(
+                    f64::from(quest.reward.silver)
+                        > kind.value() * f64::from(quantity)
+                );
+            }
+            _ => panic!("not a delivery"),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    Direction, ItemKind, Location, ProbabilityTable, Random, Rectangle,
+};
+use std::fmt::{Display, Formatter, Result};
+
+/// Game day the first raid may come on.
+pub const FIRST_RAID_DAY: u16 = 5;
+const MAX_RAID_SIZE: usize = 10;
+/// Raiders for each point of colony strength.
+const RAIDERS_PER_STRENGTH: f32 = 0.6;
+/// Raiders retreat once this fraction of them is down.
+const RETREAT_LOSSES: f32 = 0.5;
+
+/// What a raider came for, once there is no colonist to fight.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RaiderPlan {
+    /// Wreck walls, workbenches and beds.
+    Wreck,
+    /// Run away with a stack of items from the stockpiles.
+    Steal,
+}
+
+impl RaiderPlan {
+    pub const ALL: &'static [RaiderPlan] =
+        &[RaiderPlan::Wreck, RaiderPlan::Steal];
+}
+
+impl Display for RaiderPlan {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            RaiderPlan::Wreck => write!(f, "Wrecking"),
+            RaiderPlan::Steal => write!(f, "Stealing"),
+        }
+    }
+}
+
+/// Raiders sent against a colony of `colonists` able colonists, `armed` of
+/// them with weapons in their hands.
+pub fn raid_size(colonists: usize, armed: usize) -> usize {
+    let strength = (colonists + armed) as f32;
+    ((strength * RAIDERS_PER_STRENGTH).ceil() as usize).clamp(1, MAX_RAID_SIZE)
+}
+
+/// Whether a raid of `size` raiders with `down` of them downed or dead
+/// gives up.
+pub fn is_beaten(size: usize, down: usize) -> bool {
+    down as f32 >= size as f32 * RETREAT_LOSSES
+}
+
+/// Weapons raiders carry and how common they are.
+pub fn raider_weapons() -> ProbabilityTable<Option<ItemKind>> {
+    ProbabilityTable::new(vec![
+        (0.5, Some(ItemKind::Knife)),
+        (0.3, Some(ItemKind::Bow)),
+        (0.2, None),
+    ])
+}
+
+/// A random tile on the `edge` of the map.
+pub fn arrival_point(
+    map: Rectangle,
+    edge: Direction,
+    random: &mut Random,
+) -> Location {
+    match edge {
+        Direction::North => {
+            Location::new(random.range(map.min_x, map.max_x), map.min_y)
+        }
+        Direction::East => {
+            Location::new(map.max_x, random.range(map.min_y, map.max_y))
+        }
+        Direction::South => {
+            Location::new(random.range(map.min_x, map.max_x), map.max_y)
+        }
+        Direction::West => {
+            Location::new(map.min_x, random.range(map.min_y, map.max_y))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stronger_colonies_get_bigger_raids() {
+        assert_eq!(1, raid_size(0, 0));
+        assert!(raid_size(3, 0) < raid_size(3, 3));
+        assert_eq!(MAX_RAID_SIZE, raid_size(30, 30));
+    }
+
+    #[test]
+    fn raids_are_beaten_after_losing_half_of_the_raiders() {
+        assert!(!is_beaten(5, 2));
+        assert!(is_beaten(5, 3));
+        assert!(is_beaten(1, 1));
+    }
+
+    #[test]
+    fn raiders_arrive_on_the_edge_of_the_map() {
+        let map = Rectangle::new(Location::new(-5, -5), Location::new(5, 5));
+        let mut random = Random::new(9);
+        for _ in 0..20 {
+            assert_eq!(
+                -5,
+                arrival_point(map, Direction::North, &mut random).y
+            );
+            assert_eq!(5, arrival_point(map, Direction::East, &mut random).x);
+            let south = arrival_point(map, Direction::South, &mut random);
+            assert_eq!(5, south.y);
+            assert!(map.contains(south));
+        }
+    }
+}
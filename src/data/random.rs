@@ -0,0 +1,129 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seedable pseudo-random number generator (xorshift64*).
+///
+/// `tcod::random::Rng` is not `Send`, so it cannot be used as an ECS
+/// resource.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        // Zero is a fixed point of xorshift, mix the seed to avoid it.
+        Self {
+            state: (seed ^ 0x9e37_79b9_7f4a_7c15).max(1),
+        }
+    }
+
+    /// Seed from the system clock.
+    pub fn from_clock() -> Self {
+        Self::new(Self::clock_seed())
+    }
+
+    /// A seed that is different every time, taken from the system clock.
+    pub fn clock_seed() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    }
+
+    /// A number in `[0, n)`, `n` must not be zero.
+    pub fn below(&mut self, n: u32) -> u32 {
+        assert!(n > 0);
+        ((u64::from(self.next_u32()) * u64::from(n)) >> 32) as u32
+    }
+
+    /// Returns `true` with the probability `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        self.unit() < p
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        (self.state.wrapping_mul(0x2545_f491_4f6c_dd1d) >> 32) as u32
+    }
+
+    /// Choose an element of `items`, `None` if it is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.below(items.len() as u32) as usize)
+        }
+    }
+
+    /// A number in `[min, max]`.
+    pub fn range(&mut self, min: i32, max: i32) -> i32 {
+        assert!(min <= max);
+        min + self.below((max - min) as u32 + 1) as i32
+    }
+
+    /// A number in `[0.0, 1.0)`, suitable for
+    /// [ProbabilityTable::choose](crate::data::ProbabilityTable::choose).
+    pub fn unit(&mut self) -> f64 {
+        f64::from(self.next_u32()) / (f64::from(u32::MAX) + 1.0)
+    }
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        Self::from_clock()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_gives_same_numbers() {
+        let mut a = Random::new(42);
+        let mut b = Random::new(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+        assert_ne!(Random::new(1).next_u32(), Random::new(2).next_u32());
+    }
+
+    #[test]
+    fn numbers_are_within_bounds() {
+        let mut random = Random::new(0);
+        for _ in 0..1000 {
+            assert!(random.below(7) < 7);
+            let x = random.range(-3, 3);
+            assert!((-3..=3).contains(&x));
+            let u = random.unit();
+            assert!((0.0..1.0).contains(&u));
+        }
+        assert_eq!(None, random.pick::<u8>(&[]));
+        assert_eq!(Some(&5), random.pick(&[5]));
+    }
+
+    #[test]
+    fn chance_is_roughly_the_given_probability() {
+        let mut random = Random::new(7);
+        let hits = (0..10000).filter(|_| random.chance(0.25)).count();
+        assert!(2000 < hits && hits < 3000);
+    }
+}
@@ -0,0 +1,223 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{ItemKind, SkillKind};
+use ron::de;
+use serde::Deserialize;
+use std::fmt::{Display, Formatter};
+
+/// Kinds of workbenches, each recipe can be made at one kind.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub enum WorkbenchKind {
+    Crafting,
+    /// Has no recipes, colonists do research at it.
+    Research,
+    Stove,
+}
+
+impl WorkbenchKind {
+    /// Skill used when working at this kind of workbench.
+    pub fn skill(self) -> SkillKind {
+        match self {
+            WorkbenchKind::Crafting => SkillKind::Crafting,
+            WorkbenchKind::Research => SkillKind::Research,
+            WorkbenchKind::Stove => SkillKind::Cooking,
+        }
+    }
+}
+
+impl Display for WorkbenchKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let name = match self {
+            WorkbenchKind::Crafting => "Workbench",
+            WorkbenchKind::Research => "Research bench",
+            WorkbenchKind::Stove => "Stove",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A recipe turns ingredients into products at a workbench.
+///
+/// Recipes are not hard-coded, they are read from `assets/recipes.ron`.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct Recipe {
+    pub name: String,
+    pub workbench: WorkbenchKind,
+    pub ingredients: Vec<(ItemKind, u16)>,
+    pub products: Vec<(ItemKind, u16)>,
+    pub work_minutes: u32,
+}
+
+impl Recipe {
+    /// The item kind that is counted by bills that are repeated until a
+    /// certain amount is stocked.
+    pub fn main_product(&self) -> ItemKind {
+        self.products[0].0
+    }
+}
+
+/// All the recipes known to the game.
+#[derive(Debug)]
+pub struct RecipeBook {
+    recipes: Vec<Recipe>,
+}
+
+impl RecipeBook {
+    pub fn from_ron(source: &str) -> Result<RecipeBook, de::Error> {
+        let recipes: Vec<Recipe> = de::from_str(source)?;
+        if let Some(recipe) = recipes.iter().find(|r| r.products.is_empty()) {
+            return Err(de::Error::Message(format!(
+                "recipe {} has no products",
+                recipe.name
+            )));
+        }
+        Ok(RecipeBook { recipes })
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&Recipe> {
+        self.recipes.get(idx)
+    }
+
+    /// Index of the recipe that comes after `idx` among the known ones that
+    /// can be made at `workbench`, wrapping around.  If `idx` is `None` the
+    /// first recipe for `workbench` is returned.
+    pub fn next_recipe_for<F>(
+        &self,
+        workbench: WorkbenchKind,
+        idx: Option<usize>,
+        is_known: F,
+    ) -> Option<usize>
+    where
+        F: Fn(&Recipe) -> bool,
+    {
+        let candidates: Vec<usize> = self
+            .recipes
+            .iter()
+            .enumerate()
+            .filter(|(_, recipe)| {
+                recipe.workbench == workbench && is_known(recipe)
+            })
+            .map(|(i, _)| i)
+            .collect();
+        match idx {
+            Some(idx) => candidates
+                .iter()
+                .find(|i| **i > idx)
+                .or_else(|| candidates.first())
+                .copied(),
+            None => candidates.first().copied(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+
+    #[test]
+    fn from_ron_reads_a_list_of_recipes() {
+        let book = RecipeBook::from_ron(
+            r#"[
+                (
+                    name: "Planks",
+                    workbench: Crafting,
+                    ingredients: [(Log, 1)],
+                    products: [(Plank, 4)],
+                    work_minutes: 30,
+                ),
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(&Recipe {
+                name: "Planks".to_owned(),
+                workbench: WorkbenchKind::Crafting,
+                ingredients: vec![(ItemKind::Log, 1)],
+                products: vec![(ItemKind::Plank, 4)],
+                work_minutes: 30,
+            }),
+            book.get(0)
+        );
+        assert_eq!(None, book.get(1));
+    }
+
+    #[test]
+    fn from_ron_fails_for_unknown_items() {
+        assert!(RecipeBook::from_ron(
+            r#"[(name: "?", workbench: Crafting, ingredients: [(Gold, 1)],
+                 products: [], work_minutes: 1)]"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn from_ron_fails_for_recipes_without_products() {
+        let error = RecipeBook::from_ron(
+            r#"[(name: "Nothing", workbench: Crafting, ingredients: [],
+                 products: [], work_minutes: 1)]"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("recipe Nothing"));
+    }
+
+    #[test]
+    fn next_recipe_for_cycles_through_recipes_of_a_workbench_kind() {
+        let book = RecipeBook::from_ron(
+            r#"[
+                (name: "A", workbench: Crafting, ingredients: [],
+                 products: [(Plank, 1)], work_minutes: 1),
+                (name: "B", workbench: Stove, ingredients: [],
+                 products: [(SimpleMeal, 1)], work_minutes: 1),
+                (name: "C", workbench: Crafting, ingredients: [],
+                 products: [(StoneBlock, 1)], work_minutes: 1),
+            ]"#,
+        )
+        .unwrap();
+        let crafting = WorkbenchKind::Crafting;
+        let all = |_: &Recipe| true;
+        assert_eq!(Some(0), book.next_recipe_for(crafting, None, all));
+        assert_eq!(Some(2), book.next_recipe_for(crafting, Some(0), all));
+        assert_eq!(Some(0), book.next_recipe_for(crafting, Some(2), all));
+        assert_eq!(
+            Some(1),
+            book.next_recipe_for(WorkbenchKind::Stove, None, all)
+        );
+        assert_eq!(
+            Some(1),
+            book.next_recipe_for(WorkbenchKind::Stove, Some(1), all)
+        );
+        let not_a = |recipe: &Recipe| recipe.name != "A";
+        assert_eq!(Some(2), book.next_recipe_for(crafting, None, not_a));
+        assert_eq!(Some(2), book.next_recipe_for(crafting, Some(2), not_a));
+    }
+
+    #[test]
+    fn embedded_recipes_are_valid() {
+        let book = RecipeBook::from_ron(asset::RECIPES).unwrap();
+        let all = |_: &Recipe| true;
+        assert!(book
+            .next_recipe_for(WorkbenchKind::Crafting, None, all)
+            .is_some());
+        assert!(book
+            .next_recipe_for(WorkbenchKind::Stove, None, all)
+            .is_some());
+    }
+}
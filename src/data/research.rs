@@ -0,0 +1,325 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{ItemKind, Structure};
+use ron::de;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter, Result};
+
+/// Something that is not available until a research project is completed.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub enum Unlock {
+    /// A crop that fields can be sown with.
+    Crop(ItemKind),
+    /// A recipe, by name, that bills can be added for.
+    Recipe(String),
+    /// A structure that blueprints can be placed for.
+    Structure(Structure),
+}
+
+impl Display for Unlock {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Unlock::Crop(kind) => write!(f, "{} crop", kind),
+            Unlock::Recipe(name) => write!(f, "{} recipe", name),
+            Unlock::Structure(structure) => write!(f, "{}", structure),
+        }
+    }
+}
+
+/// A project of the technology tree.  Research points are spent on it once
+/// all of its prerequisites are completed.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+pub struct ResearchProject {
+    pub name: String,
+    pub cost: u32,
+    pub prerequisites: Vec<String>,
+    pub unlocks: Vec<Unlock>,
+}
+
+/// All the research projects known to the game.
+///
+/// Projects are not hard-coded, they are read from `assets/research.ron`.
+#[derive(Debug, Default)]
+pub struct TechTree {
+    projects: Vec<ResearchProject>,
+}
+
+impl TechTree {
+    pub fn from_ron(source: &str) -> std::result::Result<TechTree, de::Error> {
+        let projects: Vec<ResearchProject> = de::from_str(source)?;
+        for (idx, project) in projects.iter().enumerate() {
+            for name in project.prerequisites.iter() {
+                if !projects[..idx].iter().any(|p| p.name == *name) {
+                    return Err(de::Error::Message(format!(
+                        "prerequisite {} of {} is not listed before it",
+                        name, project.name
+                    )));
+                }
+            }
+        }
+        Ok(TechTree { projects })
+    }
+
+    /// Number of prerequisites to complete, one after another, before the
+    /// project can be started.
+    pub fn depth(&self, idx: usize) -> usize {
+        self.prerequisites(idx)
+            .into_iter()
+            .map(|p| self.depth(p) + 1)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&ResearchProject> {
+        self.projects.get(idx)
+    }
+
+    /// Indices of the prerequisites of the project.
+    pub fn prerequisites(&self, idx: usize) -> Vec<usize> {
+        self.projects.get(idx).map_or(vec![], |project| {
+            project
+                .prerequisites
+                .iter()
+                .filter_map(|name| {
+                    self.projects.iter().position(|p| p.name == *name)
+                })
+                .collect()
+        })
+    }
+
+    pub fn projects(&self) -> &[ResearchProject] {
+        &self.projects
+    }
+}
+
+/// Research progress of the colony.
+///
+/// Points go to the first project of the queue.  The queue always lists
+/// prerequisites before the projects depending on them.
+#[derive(Debug, Default)]
+pub struct Research {
+    tree: TechTree,
+    /// Points spent on the projects that are not completed yet.
+    progress: BTreeMap<usize, f32>,
+    completed: BTreeSet<usize>,
+    queue: Vec<usize>,
+}
+
+impl Research {
+    pub fn new(tree: TechTree) -> Research {
+        Research {
+            tree,
+            ..Research::default()
+        }
+    }
+
+    /// Spend research points on the current project.  Returns the project
+    /// if it got completed.
+    pub fn add_points(&mut self, points: f32) -> Option<&ResearchProject> {
+        let idx = self.current()?;
+        let cost = self.tree.get(idx)?.cost as f32;
+        let progress = self.progress.entry(idx).or_default();
+        *progress += points;
+        if *progress < cost {
+            return None;
+        }
+        self.progress.remove(&idx);
+        self.completed.insert(idx);
+        self.queue.remove(0);
+        self.tree.get(idx)
+    }
+
+    /// The project research points currently go to.
+    pub fn current(&self) -> Option<usize> {
+        self.queue.first().copied()
+    }
+
+    /// Remove the project from the queue, along with the queued projects
+    /// that need it.
+    pub fn dequeue(&mut self, idx: usize) {
+        self.queue.retain(|p| *p != idx);
+        let mut kept: Vec<usize> = vec![];
+        for project in self.queue.iter() {
+            let is_possible = self
+                .tree
+                .prerequisites(*project)
+                .iter()
+                .all(|p| self.completed.contains(p) || kept.contains(p));
+            if is_possible {
+                kept.push(*project);
+            }
+        }
+        self.queue = kept;
+    }
+
+    /// Add the project to the queue, after its missing prerequisites.
+    pub fn enqueue(&mut self, idx: usize) {
+        if self.tree.get(idx).is_none()
+            || self.is_completed(idx)
+            || self.queue.contains(&idx)
+        {
+            return;
+        }
+        for prerequisite in self.tree.prerequisites(idx) {
+            self.enqueue(prerequisite);
+        }
+        self.queue.push(idx);
+    }
+
+    /// Whether all the prerequisites of the project are completed.
+    pub fn is_available(&self, idx: usize) -> bool {
+        self.tree
+            .prerequisites(idx)
+            .iter()
+            .all(|p| self.completed.contains(p))
+    }
+
+    pub fn is_completed(&self, idx: usize) -> bool {
+        self.completed.contains(&idx)
+    }
+
+    /// Whether no project that is still to be completed unlocks `unlock`.
+    pub fn is_unlocked(&self, unlock: &Unlock) -> bool {
+        self.tree.projects.iter().enumerate().all(|(idx, project)| {
+            self.completed.contains(&idx) || !project.unlocks.contains(unlock)
+        })
+    }
+
+    /// Points spent on the project so far.
+    pub fn progress(&self, idx: usize) -> f32 {
+        if self.is_completed(idx) {
+            self.tree.get(idx).map_or(0.0, |p| p.cost as f32)
+        } else {
+            self.progress.get(&idx).copied().unwrap_or_default()
+        }
+    }
+
+    pub fn queue(&self) -> &[usize] {
+        &self.queue
+    }
+
+    /// Queue the project, or remove it from the queue if it is queued.
+    pub fn toggle_queued(&mut self, idx: usize) {
+        if self.queue.contains(&idx) {
+            self.dequeue(idx);
+        } else {
+            self.enqueue(idx);
+        }
+    }
+
+    pub fn tree(&self) -> &TechTree {
+        &self.tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+    use crate::data::RecipeBook;
+
+    const TREE: &str = r#"[
+        (name: "A", cost: 10, prerequisites: [],
+         unlocks: [Recipe("Planks")]),
+        (name: "B", cost: 10, prerequisites: [], unlocks: []),
+        (name: "C", cost: 20, prerequisites: ["A", "B"],
+         unlocks: [Structure(Sandbags), Crop(Berries)]),
+    ]"#;
+
+    #[test]
+    fn from_ron_reads_a_list_of_projects() {
+        let tree = TechTree::from_ron(TREE).unwrap();
+        assert_eq!(3, tree.projects().len());
+        assert_eq!(vec![0, 1], tree.prerequisites(2));
+        assert_eq!(1, tree.depth(2));
+        assert_eq!(0, tree.depth(0));
+    }
+
+    #[test]
+    fn from_ron_fails_for_prerequisites_listed_after_the_project() {
+        let error = TechTree::from_ron(
+            r#"[(name: "A", cost: 1, prerequisites: ["B"], unlocks: []),
+                (name: "B", cost: 1, prerequisites: [], unlocks: [])]"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("prerequisite B of A"));
+    }
+
+    #[test]
+    fn from_ron_fails_for_unknown_prerequisites() {
+        let error = TechTree::from_ron(
+            r#"[(name: "A", cost: 1, prerequisites: ["C"], unlocks: [])]"#,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("prerequisite C of A"));
+    }
+
+    #[test]
+    fn queueing_a_project_queues_its_prerequisites_first() {
+        let mut research = Research::new(TechTree::from_ron(TREE).unwrap());
+        research.enqueue(2);
+        assert_eq!(&[0, 1, 2], research.queue());
+        research.toggle_queued(1);
+        assert_eq!(&[0], research.queue());
+    }
+
+    #[test]
+    fn completed_projects_unlock_their_unlocks() {
+        let mut research = Research::new(TechTree::from_ron(TREE).unwrap());
+        let sandbags = Unlock::Structure(Structure::Sandbags);
+        assert!(!research.is_unlocked(&Unlock::Recipe("Planks".to_owned())));
+        assert!(research.is_unlocked(&Unlock::Structure(Structure::Wall)));
+        research.enqueue(2);
+        assert_eq!(None, research.add_points(9.0));
+        assert_eq!("A", research.add_points(1.0).unwrap().name);
+        assert!(research.is_unlocked(&Unlock::Recipe("Planks".to_owned())));
+        assert!(research.is_available(1));
+        assert!(!research.is_available(2));
+        research.add_points(10.0);
+        research.add_points(15.0);
+        assert!(!research.is_unlocked(&sandbags));
+        assert_eq!(15.0, research.progress(2));
+        assert_eq!("C", research.add_points(5.0).unwrap().name);
+        assert!(research.is_unlocked(&sandbags));
+        assert_eq!(None, research.current());
+        assert_eq!(None, research.add_points(1.0));
+    }
+
+    #[test]
+    fn embedded_research_unlocks_known_recipes_and_crops() {
+        let tree = TechTree::from_ron(asset::RESEARCH).unwrap();
+        let book = RecipeBook::from_ron(asset::RECIPES).unwrap();
+        let names: Vec<&str> = (0..)
+            .map_while(|idx| book.get(idx))
+            .map(|recipe| recipe.name.as_str())
+            .collect();
+        for project in tree.projects() {
+            for unlock in project.unlocks.iter() {
+                match unlock {
+                    Unlock::Crop(kind) => {
+                        assert!(kind.crop().is_some(), "{}", kind)
+                    }
+                    Unlock::Recipe(name) => {
+                        assert!(names.contains(&name.as_str()), "{}", name)
+                    }
+                    Unlock::Structure(_) => (),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,307 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Location, Rectangle};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt::{Display, Formatter, Result};
+
+/// Rooms of this size or smaller are roofed automatically.
+const AUTO_ROOF_MAX_AREA: usize = 64;
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RoomQuality {
+    Cramped,
+    Plain,
+    Decent,
+    Impressive,
+}
+
+impl Display for RoomQuality {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Whether rooms are highlighted on the map.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RoomOverlay {
+    pub is_visible: bool,
+}
+
+/// An area fully enclosed by walls.
+#[derive(Debug, PartialEq)]
+pub struct Room {
+    tiles: BTreeSet<Location>,
+    /// Number of workbenches and other furniture within the room.
+    pub furniture: u16,
+}
+
+impl Room {
+    pub fn area(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// Quality depends on the size of the room and how well it is
+    /// furnished.
+    pub fn quality(&self) -> RoomQuality {
+        let score = self.area() + 4 * usize::from(self.furniture);
+        match score {
+            0..=5 => RoomQuality::Cramped,
+            6..=15 => RoomQuality::Plain,
+            16..=35 => RoomQuality::Decent,
+            _ => RoomQuality::Impressive,
+        }
+    }
+}
+
+/// Walls, roofs and the rooms formed by the walls.
+///
+/// A tile is indoors if it has a roof over it, regardless of being in a
+/// room.  Roofs built by the player stay, the ones small rooms get
+/// automatically go away with the room.
+#[derive(Debug, Default)]
+pub struct Rooms {
+    walls: BTreeSet<Location>,
+    roofs: BTreeSet<Location>,
+    auto_roofs: BTreeSet<Location>,
+    rooms: Vec<Room>,
+    room_index: BTreeMap<Location, usize>,
+}
+
+impl Rooms {
+    pub fn is_blocked(&self, loc: Location) -> bool {
+        self.walls.contains(&loc)
+    }
+
+    pub fn is_indoors(&self, loc: Location) -> bool {
+        self.roofs.contains(&loc) || self.auto_roofs.contains(&loc)
+    }
+
+    pub fn room_at(&self, loc: Location) -> Option<&Room> {
+        self.room_index.get(&loc).map(|idx| &self.rooms[*idx])
+    }
+
+    /// Each tile that is in a room, along with the room.
+    pub fn room_tiles(&self) -> impl Iterator<Item = (Location, &Room)> {
+        self.room_index
+            .iter()
+            .map(move |(loc, idx)| (*loc, &self.rooms[*idx]))
+    }
+
+    /// Rectangle that contains all the walls with some room to walk around
+    /// them.
+    pub fn walled_area(&self) -> Option<Rectangle> {
+        bounding_rectangle(&self.walls)
+    }
+
+    /// Add or remove a roof, returns `true` if the roof is added.
+    pub fn toggle_roof(&mut self, loc: Location) -> bool {
+        if self.is_indoors(loc) {
+            self.roofs.remove(&loc);
+            self.auto_roofs.remove(&loc);
+            false
+        } else {
+            self.roofs.insert(loc);
+            true
+        }
+    }
+
+    /// Detect rooms again if the walls have changed.  Small rooms are
+    /// roofed automatically, and lose those roofs once they are no longer
+    /// rooms.
+    pub fn update(
+        &mut self,
+        walls: BTreeSet<Location>,
+        furniture: &[Location],
+    ) {
+        if walls != self.walls {
+            self.walls = walls;
+            self.rooms = detect_rooms(&self.walls)
+                .into_iter()
+                .map(|tiles| Room {
+                    tiles,
+                    furniture: 0,
+                })
+                .collect();
+            self.room_index.clear();
+            self.auto_roofs.clear();
+            for (idx, room) in self.rooms.iter().enumerate() {
+                for loc in room.tiles.iter() {
+                    self.room_index.insert(*loc, idx);
+                }
+                if room.area() <= AUTO_ROOF_MAX_AREA {
+                    self.auto_roofs.extend(room.tiles.iter());
+                }
+            }
+        }
+        for room in self.rooms.iter_mut() {
+            room.furniture = 0;
+        }
+        for loc in furniture {
+            if let Some(idx) = self.room_index.get(loc) {
+                self.rooms[*idx].furniture += 1;
+            }
+        }
+    }
+}
+
+/// Flood fill the area around the walls, regions that do not reach the
+/// outside are rooms.
+///
+/// Regions are 4-connected, so walls touching diagonally still enclose
+/// a room.
+pub fn detect_rooms(walls: &BTreeSet<Location>) -> Vec<BTreeSet<Location>> {
+    let bounds = match bounding_rectangle(walls) {
+        Some(bounds) => bounds,
+        None => return vec![],
+    };
+    let mut visited: BTreeSet<Location> = BTreeSet::new();
+    let mut rooms = vec![];
+    for start in bounds {
+        if walls.contains(&start) || visited.contains(&start) {
+            continue;
+        }
+        let mut region: BTreeSet<Location> = BTreeSet::new();
+        let mut is_enclosed = true;
+        let mut frontier: VecDeque<Location> = VecDeque::new();
+        frontier.push_back(start);
+        visited.insert(start);
+        while let Some(loc) = frontier.pop_front() {
+            if is_on_edge(bounds, loc) {
+                is_enclosed = false;
+            }
+            region.insert(loc);
+            for (dx, dy) in &[(0, -1), (1, 0), (0, 1), (-1, 0)] {
+                let next = loc.move_by(*dx, *dy);
+                if bounds.contains(next)
+                    && !walls.contains(&next)
+                    && visited.insert(next)
+                {
+                    frontier.push_back(next);
+                }
+            }
+        }
+        if is_enclosed {
+            rooms.push(region);
+        }
+    }
+    rooms
+}
+
+fn bounding_rectangle(locations: &BTreeSet<Location>) -> Option<Rectangle> {
+    let first = locations.iter().next()?;
+    let bounds =
+        locations
+            .iter()
+            .fold(Rectangle::new(*first, *first), |r, loc| {
+                Rectangle::new(
+                    Location::new(r.min_x.min(loc.x), r.min_y.min(loc.y)),
+                    Location::new(r.max_x.max(loc.x), r.max_y.max(loc.y)),
+                )
+            });
+    Some(Rectangle::new(
+        Location::new(bounds.min_x - 1, bounds.min_y - 1),
+        Location::new(bounds.max_x + 1, bounds.max_y + 1),
+    ))
+}
+
+fn is_on_edge(bounds: Rectangle, loc: Location) -> bool {
+    loc.x == bounds.min_x
+        || loc.x == bounds.max_x
+        || loc.y == bounds.min_y
+        || loc.y == bounds.max_y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Walls along the border of a rectangle.
+    fn walls_around(rect: Rectangle) -> BTreeSet<Location> {
+        rect.into_iter()
+            .filter(|loc| is_on_edge(rect, *loc))
+            .collect()
+    }
+
+    #[test]
+    fn there_are_no_rooms_without_walls() {
+        assert!(detect_rooms(&BTreeSet::new()).is_empty());
+    }
+
+    #[test]
+    fn a_closed_ring_of_walls_makes_a_room() {
+        let rect = Rectangle::centered_around(Location::origin(), 5, 4);
+        let rooms = detect_rooms(&walls_around(rect));
+        assert_eq!(1, rooms.len());
+        assert_eq!(6, rooms[0].len());
+        assert!(rooms[0].contains(&Location::origin()));
+    }
+
+    #[test]
+    fn a_gap_in_the_walls_opens_the_room() {
+        let rect = Rectangle::centered_around(Location::origin(), 5, 5);
+        let mut walls = walls_around(rect);
+        walls.remove(&Location::new(2, 0));
+        assert!(detect_rooms(&walls).is_empty());
+    }
+
+    #[test]
+    fn a_wall_inside_a_room_divides_it() {
+        let rect = Rectangle::centered_around(Location::origin(), 5, 5);
+        let mut walls = walls_around(rect);
+        walls.extend((-1..=1).map(|y| Location::new(0, y)));
+        assert_eq!(2, detect_rooms(&walls).len());
+    }
+
+    #[test]
+    fn update_roofs_small_rooms_and_counts_furniture() {
+        let rect = Rectangle::centered_around(Location::origin(), 5, 5);
+        let mut rooms = Rooms::default();
+        rooms.update(walls_around(rect), &[Location::origin()]);
+        assert!(rooms.is_indoors(Location::origin()));
+        assert!(!rooms.is_indoors(Location::new(5, 5)));
+        let room = rooms.room_at(Location::new(1, 1)).unwrap();
+        assert_eq!(9, room.area());
+        assert_eq!(1, room.furniture);
+        assert_eq!(RoomQuality::Plain, room.quality());
+        assert!(rooms.is_blocked(Location::new(2, 2)));
+    }
+
+    #[test]
+    fn automatic_roofs_go_away_with_the_room() {
+        let rect = Rectangle::centered_around(Location::origin(), 5, 5);
+        let mut walls = walls_around(rect);
+        let mut rooms = Rooms::default();
+        rooms.toggle_roof(Location::new(5, 5));
+        rooms.update(walls.clone(), &[]);
+        assert!(rooms.is_indoors(Location::origin()));
+        walls.remove(&Location::new(2, 0));
+        rooms.update(walls, &[]);
+        assert!(!rooms.is_indoors(Location::origin()));
+        assert!(rooms.is_indoors(Location::new(5, 5)));
+    }
+
+    #[test]
+    fn toggle_roof_adds_and_removes_roofs() {
+        let mut rooms = Rooms::default();
+        let loc = Location::new(3, 4);
+        assert!(rooms.toggle_roof(loc));
+        assert!(rooms.is_indoors(loc));
+        assert!(!rooms.toggle_roof(loc));
+        assert!(!rooms.is_indoors(loc));
+    }
+}
@@ -0,0 +1,127 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use std::fmt::{Display, Formatter, Result};
+
+pub const HOURS_PER_DAY: usize = 24;
+
+/// What a colonist is supposed to do during an hour of the day.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Activity {
+    /// Sleep when tired, otherwise work.
+    Anything,
+    Sleep,
+    Work,
+    Recreation,
+}
+
+impl Activity {
+    pub const ALL: &'static [Activity] = &[
+        Activity::Anything,
+        Activity::Sleep,
+        Activity::Work,
+        Activity::Recreation,
+    ];
+
+    /// Single character used to draw the activity in the schedule editor.
+    pub fn symbol(self) -> char {
+        match self {
+            Activity::Anything => '.',
+            Activity::Sleep => 'S',
+            Activity::Work => 'W',
+            Activity::Recreation => 'R',
+        }
+    }
+}
+
+impl Display for Activity {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Activities of a colonist for each hour of the day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Schedule {
+    hours: [Activity; HOURS_PER_DAY],
+}
+
+impl Schedule {
+    pub fn get(&self, hour: u8) -> Activity {
+        self.hours[usize::from(hour) % HOURS_PER_DAY]
+    }
+
+    pub fn set(&mut self, hour: u8, activity: Activity) {
+        self.hours[usize::from(hour) % HOURS_PER_DAY] = activity;
+    }
+
+    /// The default schedule turned around, to sleep during the day.
+    pub fn by_day() -> Self {
+        let night = Self::default();
+        let mut hours = [Activity::Anything; HOURS_PER_DAY];
+        for (hour, activity) in hours.iter_mut().enumerate() {
+            *activity =
+                night.hours[(hour + HOURS_PER_DAY / 2) % HOURS_PER_DAY];
+        }
+        Self { hours }
+    }
+}
+
+/// Sleep at night, free to do anything during the day.
+impl Default for Schedule {
+    fn default() -> Self {
+        let mut hours = [Activity::Anything; HOURS_PER_DAY];
+        for hour in (0..6).chain(22..24) {
+            hours[hour] = Activity::Sleep;
+        }
+        Self { hours }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colonists_sleep_at_night_by_default() {
+        let schedule = Schedule::default();
+        assert_eq!(Activity::Sleep, schedule.get(0));
+        assert_eq!(Activity::Sleep, schedule.get(5));
+        assert_eq!(Activity::Anything, schedule.get(6));
+        assert_eq!(Activity::Anything, schedule.get(21));
+        assert_eq!(Activity::Sleep, schedule.get(23));
+    }
+
+    #[test]
+    fn night_owls_sleep_during_the_day() {
+        let schedule = Schedule::by_day();
+        assert_eq!(Activity::Anything, schedule.get(0));
+        assert_eq!(Activity::Sleep, schedule.get(12));
+        assert_eq!(Activity::Sleep, schedule.get(17));
+        assert_eq!(Activity::Anything, schedule.get(18));
+        assert_eq!(Activity::Sleep, schedule.get(10));
+    }
+
+    #[test]
+    fn set_changes_a_single_hour() {
+        let mut schedule = Schedule::default();
+        schedule.set(12, Activity::Recreation);
+        assert_eq!(Activity::Recreation, schedule.get(12));
+        assert_eq!(Activity::Anything, schedule.get(11));
+        assert_eq!(Activity::Anything, schedule.get(13));
+    }
+}
@@ -0,0 +1,70 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use std::fmt::{Display, Formatter, Result};
+
+/// Number of game days in each season.
+pub const DAYS_PER_SEASON: u16 = 10;
+
+/// The year starts in spring, animals breed in their breeding season.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub const ALL: &'static [Season] = &[
+        Season::Spring,
+        Season::Summer,
+        Season::Autumn,
+        Season::Winter,
+    ];
+
+    /// Season of the given game day.
+    pub fn of_day(day: u16) -> Season {
+        let idx = (day / DAYS_PER_SEASON) as usize % Self::ALL.len();
+        Self::ALL[idx]
+    }
+}
+
+impl Display for Season {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seasons_follow_each_other() {
+        assert_eq!(Season::Spring, Season::of_day(0));
+        assert_eq!(Season::Spring, Season::of_day(DAYS_PER_SEASON - 1));
+        assert_eq!(Season::Summer, Season::of_day(DAYS_PER_SEASON));
+        assert_eq!(Season::Winter, Season::of_day(4 * DAYS_PER_SEASON - 1));
+        assert_eq!(Season::Spring, Season::of_day(4 * DAYS_PER_SEASON));
+    }
+}
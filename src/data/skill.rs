@@ -0,0 +1,238 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::MILLIS_PER_DAY;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+
+pub const MAX_SKILL_LEVEL: u8 = 20;
+/// Skills that are not practiced for this long start to decay.
+const DECAY_AFTER_MILLIS: u32 = MILLIS_PER_DAY;
+const DECAY_XP_PER_DAY: f32 = 40.0;
+/// Level at which work is done at the normal speed.
+const NORMAL_SPEED_LEVEL: f32 = 5.0;
+const SPEED_PER_LEVEL: f32 = 0.08;
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub enum SkillKind {
+    Animals,
+    Construction,
+    Cooking,
+    Crafting,
+    Farming,
+    Melee,
+    Research,
+    Shooting,
+}
+
+impl SkillKind {
+    pub const ALL: &'static [SkillKind] = &[
+        SkillKind::Animals,
+        SkillKind::Construction,
+        SkillKind::Cooking,
+        SkillKind::Crafting,
+        SkillKind::Farming,
+        SkillKind::Melee,
+        SkillKind::Research,
+        SkillKind::Shooting,
+    ];
+}
+
+impl Display for SkillKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Skill {
+    level: u8,
+    xp: f32,
+    idle_millis: u32,
+}
+
+/// Experience needed to advance from `level` to the next one.
+fn xp_for_next_level(level: u8) -> f32 {
+    100.0 * f32::from(level + 1)
+}
+
+/// Skill levels of a colonist.
+///
+/// Experience is gained by doing the related work.  Skills that are not
+/// practiced for a while lose experience, and levels once the experience
+/// runs out.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Skills {
+    skills: BTreeMap<SkillKind, Skill>,
+}
+
+impl Skills {
+    pub fn with_levels(levels: &[(SkillKind, u8)]) -> Self {
+        let mut skills = Self::default();
+        for (kind, level) in levels {
+            skills.skill_mut(*kind).level = (*level).min(MAX_SKILL_LEVEL);
+        }
+        skills
+    }
+
+    /// Lose experience in skills that have not been practiced recently,
+    /// returns the skills that lost a level along with their new level.
+    pub fn decay(&mut self, game_dt_millis: u32) -> Vec<(SkillKind, u8)> {
+        let days = game_dt_millis as f32 / MILLIS_PER_DAY as f32;
+        let mut lost = vec![];
+        for (kind, skill) in self.skills.iter_mut() {
+            skill.idle_millis =
+                skill.idle_millis.saturating_add(game_dt_millis);
+            if skill.idle_millis <= DECAY_AFTER_MILLIS {
+                continue;
+            }
+            skill.xp -= days * DECAY_XP_PER_DAY;
+            let level = skill.level;
+            while skill.xp < 0.0 && skill.level > 0 {
+                skill.level -= 1;
+                skill.xp += xp_for_next_level(skill.level);
+            }
+            skill.xp = skill.xp.max(0.0);
+            if skill.level < level {
+                lost.push((*kind, skill.level));
+            }
+        }
+        lost
+    }
+
+    /// Add experience, returns the new level if the skill levels up.
+    pub fn gain(&mut self, kind: SkillKind, xp: f32) -> Option<u8> {
+        let skill = self.skill_mut(kind);
+        skill.idle_millis = 0;
+        if skill.level >= MAX_SKILL_LEVEL {
+            return None;
+        }
+        skill.xp += xp;
+        let level = skill.level;
+        while skill.level < MAX_SKILL_LEVEL
+            && skill.xp >= xp_for_next_level(skill.level)
+        {
+            skill.xp -= xp_for_next_level(skill.level);
+            skill.level += 1;
+        }
+        if skill.level > level {
+            Some(skill.level)
+        } else {
+            None
+        }
+    }
+
+    pub fn level(&self, kind: SkillKind) -> u8 {
+        self.skills.get(&kind).map_or(0, |skill| skill.level)
+    }
+
+    /// Experience towards the next level, in percents.
+    pub fn progress_percent(&self, kind: SkillKind) -> u8 {
+        self.skills.get(&kind).map_or(0, |skill| {
+            (skill.xp / xp_for_next_level(skill.level) * 100.0) as u8
+        })
+    }
+
+    /// Work speed multiplier, `1.0` is the normal speed.
+    pub fn speed(&self, kind: SkillKind) -> f32 {
+        1.0 + (f32::from(self.level(kind)) - NORMAL_SPEED_LEVEL)
+            * SPEED_PER_LEVEL
+    }
+
+    /// Extra products made thanks to less wasted material, skilled workers
+    /// make up to half as many products more.
+    pub fn bonus_products(&self, kind: SkillKind, quantity: u16) -> u16 {
+        let level = u16::from(self.level(kind));
+        quantity * level / u16::from(MAX_SKILL_LEVEL) / 2
+    }
+
+    fn skill_mut(&mut self, kind: SkillKind) -> &mut Skill {
+        self.skills.entry(kind).or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_levels_up_and_reports_the_new_level() {
+        let mut skills = Skills::default();
+        assert_eq!(None, skills.gain(SkillKind::Cooking, 99.0));
+        assert_eq!(Some(1), skills.gain(SkillKind::Cooking, 1.0));
+        assert_eq!(Some(3), skills.gain(SkillKind::Cooking, 500.0));
+        assert_eq!(3, skills.level(SkillKind::Cooking));
+        assert_eq!(0, skills.level(SkillKind::Melee));
+    }
+
+    #[test]
+    fn skills_do_not_go_over_the_maximum_level() {
+        let mut skills = Skills::with_levels(&[(SkillKind::Melee, 30)]);
+        assert_eq!(MAX_SKILL_LEVEL, skills.level(SkillKind::Melee));
+        assert_eq!(None, skills.gain(SkillKind::Melee, 10000.0));
+    }
+
+    #[test]
+    fn unused_skills_lose_experience_first() {
+        let mut skills = Skills::with_levels(&[(SkillKind::Crafting, 2)]);
+        skills.gain(SkillKind::Crafting, 150.0);
+        assert_eq!(50, skills.progress_percent(SkillKind::Crafting));
+        assert!(skills.decay(DECAY_AFTER_MILLIS).is_empty());
+        assert_eq!(50, skills.progress_percent(SkillKind::Crafting));
+        for _ in 0..3 {
+            assert!(skills.decay(MILLIS_PER_DAY).is_empty());
+        }
+        assert_eq!(10, skills.progress_percent(SkillKind::Crafting));
+        assert_eq!(2, skills.level(SkillKind::Crafting));
+    }
+
+    #[test]
+    fn unused_skills_lose_levels_once_the_experience_runs_out() {
+        let mut skills = Skills::with_levels(&[(SkillKind::Crafting, 2)]);
+        skills.gain(SkillKind::Crafting, 20.0);
+        skills.decay(DECAY_AFTER_MILLIS);
+        assert_eq!(
+            vec![(SkillKind::Crafting, 1)],
+            skills.decay(MILLIS_PER_DAY)
+        );
+        assert_eq!(1, skills.level(SkillKind::Crafting));
+        assert_eq!(90, skills.progress_percent(SkillKind::Crafting));
+        for _ in 0..20 {
+            skills.decay(MILLIS_PER_DAY);
+        }
+        assert_eq!(0, skills.level(SkillKind::Crafting));
+        assert_eq!(0, skills.progress_percent(SkillKind::Crafting));
+    }
+
+    #[test]
+    fn skilled_workers_are_faster_and_waste_less() {
+        let skills = Skills::with_levels(&[
+            (SkillKind::Construction, 0),
+            (SkillKind::Crafting, 5),
+            (SkillKind::Cooking, 20),
+        ]);
+        assert!(skills.speed(SkillKind::Construction) < 1.0);
+        assert_eq!(1.0, skills.speed(SkillKind::Crafting));
+        assert!(skills.speed(SkillKind::Cooking) > 2.0);
+        assert_eq!(0, skills.bonus_products(SkillKind::Construction, 4));
+        assert_eq!(0, skills.bonus_products(SkillKind::Crafting, 4));
+        assert_eq!(2, skills.bonus_products(SkillKind::Cooking, 4));
+    }
+}
@@ -0,0 +1,167 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::ProbabilityTable;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+
+/// Colonists who like each other at least this much are friends.
+pub const FRIEND_OPINION: i32 = 20;
+/// Colonists who dislike each other at least this much are rivals.
+pub const RIVAL_OPINION: i32 = -20;
+const MAX_OPINION: i32 = 100;
+
+/// Ways colonists who are close to each other interact.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InteractionKind {
+    Argument,
+    Chat,
+    Compliment,
+}
+
+impl InteractionKind {
+    /// How likely each interaction is, depending on whether the colonists
+    /// like each other.
+    pub fn table(opinion: i32) -> ProbabilityTable<InteractionKind> {
+        let distribution = if opinion < 0 {
+            vec![
+                (0.4, InteractionKind::Argument),
+                (0.5, InteractionKind::Chat),
+                (0.1, InteractionKind::Compliment),
+            ]
+        } else {
+            vec![
+                (0.15, InteractionKind::Argument),
+                (0.6, InteractionKind::Chat),
+                (0.25, InteractionKind::Compliment),
+            ]
+        };
+        ProbabilityTable::new(distribution)
+    }
+
+    /// Change in the opinions the colonists have of each other.
+    pub fn opinion_change(self) -> i32 {
+        match self {
+            InteractionKind::Argument => -8,
+            InteractionKind::Chat => 2,
+            InteractionKind::Compliment => 5,
+        }
+    }
+}
+
+impl Display for InteractionKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            InteractionKind::Argument => write!(f, "argued with"),
+            InteractionKind::Chat => write!(f, "chatted with"),
+            InteractionKind::Compliment => write!(f, "complimented"),
+        }
+    }
+}
+
+/// What a colonist thinks of the others, between `-100` and `100`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opinions<T: Ord> {
+    opinions: BTreeMap<T, i32>,
+}
+
+impl<T: Copy + Ord> Opinions<T> {
+    pub fn change(&mut self, other: T, delta: i32) {
+        let opinion = self.opinions.entry(other).or_insert(0);
+        *opinion = (*opinion + delta).clamp(-MAX_OPINION, MAX_OPINION);
+    }
+
+    /// Friends, the best friend first.
+    pub fn friends(&self) -> Vec<(T, i32)> {
+        let mut friends: Vec<(T, i32)> = self
+            .iter()
+            .filter(|&(_, opinion)| opinion >= FRIEND_OPINION)
+            .collect();
+        friends.sort_by_key(|&(_, opinion)| -opinion);
+        friends
+    }
+
+    pub fn get(&self, other: T) -> i32 {
+        self.opinions.get(&other).copied().unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (T, i32)> + '_ {
+        self.opinions
+            .iter()
+            .map(|(&other, &opinion)| (other, opinion))
+    }
+
+    /// Rivals, the worst rival first.
+    pub fn rivals(&self) -> Vec<(T, i32)> {
+        let mut rivals: Vec<(T, i32)> = self
+            .iter()
+            .filter(|&(_, opinion)| opinion <= RIVAL_OPINION)
+            .collect();
+        rivals.sort_by_key(|&(_, opinion)| opinion);
+        rivals
+    }
+}
+
+impl<T: Ord> Default for Opinions<T> {
+    fn default() -> Self {
+        Self {
+            opinions: BTreeMap::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opinions_are_limited() {
+        let mut opinions = Opinions::default();
+        assert_eq!(0, opinions.get('a'));
+        for _ in 0..30 {
+            opinions.change('a', InteractionKind::Compliment.opinion_change());
+            opinions.change('b', InteractionKind::Argument.opinion_change());
+        }
+        assert_eq!(MAX_OPINION, opinions.get('a'));
+        assert_eq!(-MAX_OPINION, opinions.get('b'));
+    }
+
+    #[test]
+    fn friends_and_rivals() {
+        let mut opinions = Opinions::default();
+        opinions.change('a', FRIEND_OPINION);
+        opinions.change('b', FRIEND_OPINION + 10);
+        opinions.change('c', FRIEND_OPINION - 1);
+        opinions.change('d', RIVAL_OPINION);
+        assert_eq!(
+            vec![('b', FRIEND_OPINION + 10), ('a', FRIEND_OPINION)],
+            opinions.friends()
+        );
+        assert_eq!(vec![('d', RIVAL_OPINION)], opinions.rivals());
+    }
+
+    #[test]
+    fn rivals_argue_more() {
+        assert_eq!(
+            InteractionKind::Argument,
+            InteractionKind::table(-1).choose(0.3)
+        );
+        assert_eq!(
+            InteractionKind::Chat,
+            InteractionKind::table(1).choose(0.3)
+        );
+    }
+}
@@ -0,0 +1,343 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    Background, Backgrounds, ItemKind, Names, ProbabilityTable, Random,
+    FIRST_RAID_DAY,
+};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+
+/// Days it takes the chance of a raid to grow by its base chance.
+const DAYS_PER_RAID_WEIGHT: f64 = 20.0;
+/// Game day the storyteller tells its first story on.
+const FIRST_EVENT_DAY: u16 = 1;
+const MAX_HERD_SIZE: i32 = 6;
+const MIN_HERD_SIZE: i32 = 3;
+/// Colony wealth that grows the chance of a raid by its base chance.
+const WEALTH_PER_RAID_WEIGHT: f64 = 2000.0;
+
+/// Something that happens to the colony.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum StoryEvent {
+    /// A herd of wild animals wanders in.
+    Herd,
+    /// A colonist falls sick.
+    Disease,
+    /// Someone asks to join the colony.
+    Wanderer,
+    /// Supplies are found near the colony.
+    ResourceDrop,
+    Raid,
+}
+
+impl StoryEvent {
+    pub const ALL: &'static [StoryEvent] = &[
+        StoryEvent::Herd,
+        StoryEvent::Disease,
+        StoryEvent::Wanderer,
+        StoryEvent::ResourceDrop,
+        StoryEvent::Raid,
+    ];
+
+    /// Game days before the same kind of event can happen again.
+    pub fn cooldown_days(self) -> u16 {
+        match self {
+            StoryEvent::Herd => 3,
+            StoryEvent::Disease => 6,
+            StoryEvent::Wanderer => 8,
+            StoryEvent::ResourceDrop => 3,
+            StoryEvent::Raid => 4,
+        }
+    }
+
+    fn is_threat(self) -> bool {
+        matches!(self, StoryEvent::Disease | StoryEvent::Raid)
+    }
+}
+
+/// Storytellers decide how often events happen and which ones.  Picked
+/// when starting a new game.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Personality {
+    /// Tells a story every few days, threats grow with the colony.
+    #[default]
+    Steady,
+    /// Prefers threats and tells stories often.
+    Relentless,
+    /// Prefers good news and gives the colony time to breathe.
+    Gentle,
+    /// Any event is as likely as another, at any time.
+    Erratic,
+}
+
+impl Personality {
+    pub const ALL: &'static [Personality] = &[
+        Personality::Steady,
+        Personality::Relentless,
+        Personality::Gentle,
+        Personality::Erratic,
+    ];
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Personality::Steady => "A story every few days, growing harder.",
+            Personality::Relentless => "Frequent threats, little rest.",
+            Personality::Gentle => "Mostly good news, rare threats.",
+            Personality::Erratic => "Anything can happen, at any time.",
+        }
+    }
+
+    pub fn next(self) -> Personality {
+        let idx = Self::ALL.iter().position(|p| *p == self).unwrap();
+        Self::ALL[(idx + 1) % Self::ALL.len()]
+    }
+
+    pub fn previous(self) -> Personality {
+        let idx = Self::ALL.iter().position(|p| *p == self).unwrap();
+        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Game days between events, at least and at most.
+    fn days_between_events(self) -> (i32, i32) {
+        match self {
+            Personality::Steady => (1, 3),
+            Personality::Relentless => (1, 2),
+            Personality::Gentle => (2, 4),
+            Personality::Erratic => (1, 5),
+        }
+    }
+
+    fn weight(self, event: StoryEvent) -> f64 {
+        let threat = match self {
+            Personality::Steady => 1.0,
+            Personality::Relentless => 2.5,
+            Personality::Gentle => 0.4,
+            Personality::Erratic => return 1.0,
+        };
+        let base = match event {
+            StoryEvent::Herd | StoryEvent::ResourceDrop => 3.0,
+            StoryEvent::Disease | StoryEvent::Wanderer => 1.0,
+            StoryEvent::Raid => 2.0,
+        };
+        if event.is_threat() {
+            base * threat
+        } else {
+            base
+        }
+    }
+
+    /// Chances of the `events` on game `day`, raids get likelier as the
+    /// days pass and the colony gets wealthier.  `None` if there are no
+    /// events.
+    pub fn table(
+        self,
+        events: &[StoryEvent],
+        day: u16,
+        wealth: f64,
+    ) -> Option<ProbabilityTable<StoryEvent>> {
+        let weights: Vec<(f64, StoryEvent)> = events
+            .iter()
+            .map(|event| {
+                let weight = self.weight(*event);
+                match event {
+                    StoryEvent::Raid => (
+                        weight
+                            * (1.0
+                                + f64::from(day) / DAYS_PER_RAID_WEIGHT
+                                + wealth / WEALTH_PER_RAID_WEIGHT),
+                        *event,
+                    ),
+                    _ => (weight, *event),
+                }
+            })
+            .collect();
+        let sum: f64 = weights.iter().map(|(w, _)| w).sum();
+        if sum <= 0.0 {
+            return None;
+        }
+        Some(ProbabilityTable::new(
+            weights.into_iter().map(|(w, e)| (w / sum, e)).collect(),
+        ))
+    }
+}
+
+impl Display for Personality {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Rolls events every few days, keeping events of the same kind apart.
+#[derive(Debug, Default)]
+pub struct Storyteller {
+    personality: Personality,
+    next_event_day: u16,
+    /// Game day each kind of event last happened on.
+    last_days: BTreeMap<StoryEvent, u16>,
+    is_raid_due: bool,
+    /// Whether raiders are on the map, set by the raid system.
+    is_raid_active: bool,
+}
+
+impl Storyteller {
+    pub fn new(personality: Personality) -> Storyteller {
+        Storyteller {
+            personality,
+            next_event_day: FIRST_EVENT_DAY,
+            ..Storyteller::default()
+        }
+    }
+
+    /// Events that are not cooling down on game `day`.  There are no raids
+    /// before the first raid day, nor while another raid is due or under
+    /// way.
+    pub fn available(&self, day: u16) -> Vec<StoryEvent> {
+        let can_raid =
+            day >= FIRST_RAID_DAY && !self.is_raid_due && !self.is_raid_active;
+        StoryEvent::ALL
+            .iter()
+            .copied()
+            .filter(|e| *e != StoryEvent::Raid || can_raid)
+            .filter(|e| {
+                self.last_days
+                    .get(e)
+                    .is_none_or(|last| day >= last + e.cooldown_days())
+            })
+            .collect()
+    }
+
+    /// The event happening on game `day`, if one is due.  Raids are left
+    /// for the raid system to [take](Storyteller::take_raid).
+    pub fn tell(
+        &mut self,
+        day: u16,
+        wealth: f64,
+        random: &mut Random,
+    ) -> Option<StoryEvent> {
+        if day < self.next_event_day {
+            return None;
+        }
+        let (min, max) = self.personality.days_between_events();
+        self.next_event_day = day + random.range(min, max) as u16;
+        let event = self
+            .personality
+            .table(&self.available(day), day, wealth)?
+            .choose(random.unit());
+        self.last_days.insert(event, day);
+        if event == StoryEvent::Raid {
+            self.is_raid_due = true;
+        }
+        Some(event)
+    }
+
+    pub fn set_raid_active(&mut self, is_raid_active: bool) {
+        self.is_raid_active = is_raid_active;
+    }
+
+    /// Whether a raid is due, it is not due anymore afterwards.
+    pub fn take_raid(&mut self) -> bool {
+        std::mem::replace(&mut self.is_raid_due, false)
+    }
+}
+
+pub fn herd_size(random: &mut Random) -> usize {
+    random.range(MIN_HERD_SIZE, MAX_HERD_SIZE) as usize
+}
+
+/// Supplies found near the colony and how common they are.
+pub fn resource_drops() -> ProbabilityTable<(ItemKind, u16)> {
+    ProbabilityTable::new(vec![
+        (0.3, (ItemKind::Log, 40)),
+        (0.2, (ItemKind::StoneBlock, 30)),
+        (0.2, (ItemKind::SimpleMeal, 10)),
+        (0.15, (ItemKind::Medicine, 6)),
+        (0.15, (ItemKind::Silver, 120)),
+    ])
+}
+
+/// Name and background of a wanderer of a random culture joining the
+/// colony.
+pub fn wanderer(
+    backgrounds: &Backgrounds,
+    names: &Names,
+    random: &mut Random,
+) -> (String, Background) {
+    let culture = names.culture(names.pick_culture(random));
+    let name = culture.expect("No cultures").person(random);
+    (name, backgrounds.generate(random))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn events_cool_down() {
+        let mut storyteller = Storyteller::new(Personality::Steady);
+        let mut random = Random::new(5);
+        assert_eq!(None, storyteller.tell(0, 0.0, &mut random));
+        let event = storyteller.tell(FIRST_EVENT_DAY, 0.0, &mut random);
+        let event = event.unwrap();
+        assert_ne!(StoryEvent::Raid, event);
+        assert!(!storyteller.available(FIRST_EVENT_DAY + 1).contains(&event));
+        let day = FIRST_EVENT_DAY + event.cooldown_days();
+        assert!(storyteller.available(day).contains(&event));
+    }
+
+    #[test]
+    fn no_raid_is_rolled_during_a_raid() {
+        let mut storyteller = Storyteller::new(Personality::Relentless);
+        let day = FIRST_RAID_DAY + StoryEvent::Raid.cooldown_days();
+        assert!(storyteller.available(day).contains(&StoryEvent::Raid));
+        storyteller.set_raid_active(true);
+        assert!(!storyteller.available(day).contains(&StoryEvent::Raid));
+        storyteller.set_raid_active(false);
+        assert!(storyteller.available(day).contains(&StoryEvent::Raid));
+    }
+
+    #[test]
+    fn raids_get_likelier() {
+        let events = [StoryEvent::Herd, StoryEvent::Raid];
+        let chance = |day, wealth| {
+            let table =
+                Personality::Steady.table(&events, day, wealth).unwrap();
+            (0..100)
+                .filter(|x| {
+                    table.choose(f64::from(*x) / 100.0) == StoryEvent::Raid
+                })
+                .count()
+        };
+        assert!(chance(5, 0.0) < chance(50, 0.0));
+        assert!(chance(5, 0.0) < chance(5, 5000.0));
+        assert!(Personality::Steady.table(&[], 5, 0.0).is_none());
+    }
+
+    #[test]
+    fn gentle_storytellers_prefer_good_news() {
+        let events = StoryEvent::ALL;
+        let threats = |personality: Personality| {
+            let table = personality.table(events, 10, 1000.0).unwrap();
+            (0..100)
+                .filter(|x| table.choose(f64::from(*x) / 100.0).is_threat())
+                .count()
+        };
+        assert!(threats(Personality::Gentle) < threats(Personality::Steady));
+        assert!(
+            threats(Personality::Steady) < threats(Personality::Relentless)
+        );
+    }
+}
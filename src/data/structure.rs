@@ -0,0 +1,132 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::ItemKind;
+use serde::Deserialize;
+use std::fmt::{Display, Formatter, Result};
+
+/// Something colonists build on the map from a blueprint.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub enum Structure {
+    Barricade,
+    Bed,
+    /// Building one can win the game.
+    Monument,
+    Sandbags,
+    /// Hidden from enemies, hurts the hostiles that step on it.
+    SpikeTrap,
+    Wall,
+}
+
+impl Structure {
+    /// Structures that defend the colony, they have hit points and are
+    /// repaired when damaged.
+    pub const DEFENSES: &'static [Structure] = &[
+        Structure::Barricade,
+        Structure::Sandbags,
+        Structure::SpikeTrap,
+    ];
+
+    /// Materials consumed by the construction.
+    pub fn cost(self) -> &'static [(ItemKind, u16)] {
+        match self {
+            Structure::Barricade => &[(ItemKind::Plank, 3)],
+            Structure::Bed => &[(ItemKind::Plank, 4)],
+            Structure::Monument => &[(ItemKind::StoneBlock, 60)],
+            Structure::Sandbags => &[(ItemKind::Stone, 2)],
+            Structure::SpikeTrap => &[(ItemKind::Plank, 2)],
+            Structure::Wall => &[(ItemKind::Plank, 2)],
+        }
+    }
+
+    /// Hit chance a ranged attack loses against a target behind or on the
+    /// structure.
+    pub fn cover(self) -> f64 {
+        match self {
+            Structure::Barricade => 0.25,
+            Structure::Sandbags => 0.35,
+            Structure::Bed
+            | Structure::Monument
+            | Structure::SpikeTrap
+            | Structure::Wall => 0.0,
+        }
+    }
+
+    pub fn is_defense(self) -> bool {
+        Structure::DEFENSES.contains(&self)
+    }
+
+    pub fn is_trap(self) -> bool {
+        self == Structure::SpikeTrap
+    }
+
+    /// Hit points of a defense when it is built or fully repaired, `None`
+    /// if the structure is not a defense.
+    pub fn max_hp(self) -> Option<u16> {
+        match self {
+            Structure::Barricade => Some(80),
+            Structure::Sandbags => Some(120),
+            Structure::SpikeTrap => Some(40),
+            Structure::Bed | Structure::Monument | Structure::Wall => None,
+        }
+    }
+
+    /// Game time it takes to build once the materials are delivered.
+    pub fn work_minutes(self) -> u32 {
+        match self {
+            Structure::Barricade => 40,
+            Structure::Bed => 60,
+            Structure::Monument => 1440,
+            Structure::Sandbags => 30,
+            Structure::SpikeTrap => 30,
+            Structure::Wall => 45,
+        }
+    }
+}
+
+impl Display for Structure {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Structure::SpikeTrap => write!(f, "Spike trap"),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_defenses_have_hit_points() {
+        for structure in [Structure::Bed, Structure::Wall].iter() {
+            assert!(!structure.is_defense());
+            assert_eq!(None, structure.max_hp());
+        }
+        for structure in Structure::DEFENSES {
+            assert!(structure.max_hp().is_some());
+        }
+    }
+
+    #[test]
+    fn traps_give_no_cover() {
+        assert!(Structure::Sandbags.cover() > Structure::Barricade.cover());
+        assert_eq!(0.0, Structure::SpikeTrap.cover());
+    }
+}
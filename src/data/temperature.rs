@@ -0,0 +1,69 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::Time;
+use std::f32::consts::PI;
+
+const MEAN_TEMPERATURE: f32 = 14.0;
+const DAILY_AMPLITUDE: f32 = 8.0;
+const WARMEST_HOUR: f32 = 15.0;
+const INDOOR_TEMPERATURE: f32 = 18.0;
+const ROOF_INSULATION: f32 = 0.5;
+
+/// Outdoor temperature in degrees Celsius.
+///
+/// It is the warmest in the afternoon and the coldest before the dawn.
+pub fn outdoor_temperature(time: Time) -> f32 {
+    let hour = f32::from(time.game_time_hours())
+        + f32::from(time.game_time_minutes()) / 60.0;
+    let phase = (hour - WARMEST_HOUR) / 24.0 * 2.0 * PI;
+    MEAN_TEMPERATURE + DAILY_AMPLITUDE * phase.cos()
+}
+
+/// Temperature under a roof, it is closer to a comfortable temperature than
+/// the outdoors.
+pub fn indoor_temperature(outdoor: f32) -> f32 {
+    outdoor + (INDOOR_TEMPERATURE - outdoor) * ROOF_INSULATION
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_at(hours: u8) -> Time {
+        let mut time = Time::default();
+        time.set_game_time(0, hours, 0, 0);
+        time
+    }
+
+    #[test]
+    fn afternoons_are_warmer_than_nights() {
+        let warmest = outdoor_temperature(time_at(15));
+        let coldest = outdoor_temperature(time_at(3));
+        assert_eq!(MEAN_TEMPERATURE + DAILY_AMPLITUDE, warmest);
+        assert_eq!(MEAN_TEMPERATURE - DAILY_AMPLITUDE, coldest);
+        assert!(outdoor_temperature(time_at(12)) > coldest);
+        assert!(outdoor_temperature(time_at(23)) < warmest);
+    }
+
+    #[test]
+    fn indoors_is_milder_than_outdoors() {
+        assert_eq!(14.0, indoor_temperature(10.0));
+        assert_eq!(22.0, indoor_temperature(26.0));
+        assert_eq!(INDOOR_TEMPERATURE, indoor_temperature(INDOOR_TEMPERATURE));
+    }
+}
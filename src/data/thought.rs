@@ -0,0 +1,195 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{MealQuality, MILLIS_PER_HOUR};
+use std::fmt::{Display, Formatter, Result};
+
+/// Mood of a colonist without any thoughts.
+const BASE_MOOD: i32 = 50;
+
+/// Things colonists think about, each one changes the mood for a while.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ThoughtKind {
+    AteMeal(MealQuality),
+    /// Relief after a mental break.
+    Catharsis,
+    Cold,
+    HasFriend,
+    HasRival,
+    Hungry,
+    Relaxed,
+    SawRot,
+    SleptOutside,
+    SmelledRot,
+    Tired,
+}
+
+impl ThoughtKind {
+    pub fn mood_effect(self) -> i32 {
+        match self {
+            ThoughtKind::AteMeal(quality) => quality.mood_effect(),
+            ThoughtKind::Catharsis => 10,
+            ThoughtKind::Cold => -5,
+            ThoughtKind::HasFriend => 5,
+            ThoughtKind::HasRival => -5,
+            ThoughtKind::Hungry => -6,
+            ThoughtKind::Relaxed => 4,
+            ThoughtKind::SawRot => -4,
+            ThoughtKind::SleptOutside => -3,
+            ThoughtKind::SmelledRot => -6,
+            ThoughtKind::Tired => -6,
+        }
+    }
+
+    /// How long the thought lasts, in game hours.
+    pub fn duration_hours(self) -> u32 {
+        match self {
+            ThoughtKind::AteMeal(_) => 24,
+            ThoughtKind::Catharsis => 48,
+            // Conditions are renewed as long as they last.
+            ThoughtKind::Cold
+            | ThoughtKind::HasFriend
+            | ThoughtKind::HasRival
+            | ThoughtKind::Hungry
+            | ThoughtKind::SawRot
+            | ThoughtKind::SmelledRot
+            | ThoughtKind::Tired => 1,
+            ThoughtKind::Relaxed => 12,
+            ThoughtKind::SleptOutside => 24,
+        }
+    }
+}
+
+impl Display for ThoughtKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            ThoughtKind::AteMeal(MealQuality::Raw) => {
+                write!(f, "Ate raw food")
+            }
+            ThoughtKind::AteMeal(MealQuality::Simple) => {
+                write!(f, "Ate simple meal")
+            }
+            ThoughtKind::AteMeal(MealQuality::Fine) => {
+                write!(f, "Ate fine meal")
+            }
+            ThoughtKind::HasFriend => write!(f, "Has a friend"),
+            ThoughtKind::HasRival => write!(f, "Has a rival"),
+            ThoughtKind::SawRot => write!(f, "Saw rot"),
+            ThoughtKind::SleptOutside => write!(f, "Slept outside"),
+            ThoughtKind::SmelledRot => write!(f, "Smelled rot"),
+            kind => write!(f, "{:?}", kind),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thought {
+    pub kind: ThoughtKind,
+    pub remaining_millis: u32,
+}
+
+/// Thoughts of a colonist, they add up to the mood.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Thoughts {
+    thoughts: Vec<Thought>,
+}
+
+impl Thoughts {
+    /// Add a thought, if the colonist already has it its duration starts
+    /// over.  Thoughts that do not affect the mood are ignored.
+    pub fn add(&mut self, kind: ThoughtKind) {
+        if kind.mood_effect() == 0 {
+            return;
+        }
+        let remaining_millis = kind.duration_hours() * MILLIS_PER_HOUR;
+        match self.thoughts.iter_mut().find(|t| t.kind == kind) {
+            Some(thought) => thought.remaining_millis = remaining_millis,
+            None => self.thoughts.push(Thought {
+                kind,
+                remaining_millis,
+            }),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Thought> {
+        self.thoughts.iter()
+    }
+
+    /// Mood between `0` and `100`, changed by `offset` on top of the
+    /// thoughts.
+    pub fn mood_with(&self, offset: i32) -> u8 {
+        let sum: i32 =
+            self.thoughts.iter().map(|t| t.kind.mood_effect()).sum();
+        (BASE_MOOD + offset + sum).clamp(0, 100) as u8
+    }
+
+    /// Forget the thoughts that expired.
+    pub fn tick(&mut self, game_dt_millis: u32) {
+        for thought in self.thoughts.iter_mut() {
+            thought.remaining_millis =
+                thought.remaining_millis.saturating_sub(game_dt_millis);
+        }
+        self.thoughts.retain(|t| t.remaining_millis > 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mood_is_the_sum_of_thoughts() {
+        let mut thoughts = Thoughts::default();
+        assert_eq!(50, thoughts.mood_with(0));
+        thoughts.add(ThoughtKind::AteMeal(MealQuality::Fine));
+        thoughts.add(ThoughtKind::SleptOutside);
+        assert_eq!(52, thoughts.mood_with(0));
+    }
+
+    #[test]
+    fn adding_a_thought_again_renews_it() {
+        let mut thoughts = Thoughts::default();
+        thoughts.add(ThoughtKind::Hungry);
+        thoughts.tick(MILLIS_PER_HOUR / 2);
+        thoughts.add(ThoughtKind::Hungry);
+        assert_eq!(1, thoughts.iter().count());
+        assert_eq!(
+            MILLIS_PER_HOUR,
+            thoughts.iter().next().unwrap().remaining_millis
+        );
+    }
+
+    #[test]
+    fn thoughts_expire() {
+        let mut thoughts = Thoughts::default();
+        thoughts.add(ThoughtKind::Relaxed);
+        thoughts.add(ThoughtKind::AteMeal(MealQuality::Raw));
+        thoughts.tick(12 * MILLIS_PER_HOUR);
+        let kinds: Vec<ThoughtKind> =
+            thoughts.iter().map(|t| t.kind).collect();
+        assert_eq!(vec![ThoughtKind::AteMeal(MealQuality::Raw)], kinds);
+        thoughts.tick(12 * MILLIS_PER_HOUR);
+        assert_eq!(50, thoughts.mood_with(0));
+    }
+
+    #[test]
+    fn neutral_thoughts_are_ignored() {
+        let mut thoughts = Thoughts::default();
+        thoughts.add(ThoughtKind::AteMeal(MealQuality::Simple));
+        assert_eq!(0, thoughts.iter().count());
+    }
+}
@@ -15,9 +15,16 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::data::season::{Season, DAYS_PER_SEASON};
+
+pub const MILLIS_PER_MINUTE: u32 = 60_000;
+pub const MILLIS_PER_HOUR: u32 = 60 * MILLIS_PER_MINUTE;
+pub const MILLIS_PER_DAY: u32 = 24 * MILLIS_PER_HOUR;
+
 #[derive(Clone, Copy, Debug, Default)]
 pub struct Time {
     dt_millis: u32,
+    game_dt_millis: u32,
     t_millis: u64,
     game_time_days: u16,
     game_time_hours: u8,
@@ -35,6 +42,12 @@ impl Time {
         self.dt_millis
     }
 
+    /// In-game milliseconds passed during the last update, this is zero
+    /// when the game is paused.
+    pub fn game_dt_millis(self) -> u32 {
+        self.game_dt_millis
+    }
+
     pub fn game_time_days(self) -> u16 {
         self.game_time_days
     }
@@ -51,6 +64,14 @@ impl Time {
         self.game_time_minutes
     }
 
+    pub fn season(self) -> Season {
+        Season::of_day(self.game_time_days)
+    }
+
+    pub fn set_game_dt(&mut self, game_dt_millis: u32) {
+        self.game_dt_millis = game_dt_millis;
+    }
+
     pub fn set_game_time(
         &mut self,
         days: u16,
@@ -67,6 +88,11 @@ impl Time {
     pub fn t_millis(self) -> u64 {
         self.t_millis
     }
+
+    /// Years passed since the start of the game.
+    pub fn year(self) -> u16 {
+        self.game_time_days / (DAYS_PER_SEASON * Season::ALL.len() as u16)
+    }
 }
 
 #[cfg(test)]
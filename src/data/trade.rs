@@ -0,0 +1,185 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Durability, Freshness, ItemKind, Random};
+use std::fmt::{Display, Formatter, Result};
+
+/// Game day the first caravan may come on.
+pub const FIRST_CARAVAN_DAY: u16 = 3;
+/// Game time a caravan stays at its camp.
+pub const CARAVAN_CAMP_HOURS: u32 = 16;
+const MAX_DAYS_BETWEEN_CARAVANS: i32 = 8;
+const MIN_DAYS_BETWEEN_CARAVANS: i32 = 4;
+
+/// What a caravan trades in and how much it charges.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraderKind {
+    BulkGoods,
+    CombatSupplier,
+    Exotic,
+}
+
+impl TraderKind {
+    pub const ALL: &'static [TraderKind] = &[
+        TraderKind::BulkGoods,
+        TraderKind::CombatSupplier,
+        TraderKind::Exotic,
+    ];
+
+    /// Fraction of the value the trader adds when selling and takes off
+    /// when buying.
+    pub fn markup(self) -> f64 {
+        match self {
+            TraderKind::BulkGoods => 0.2,
+            TraderKind::CombatSupplier => 0.3,
+            TraderKind::Exotic => 0.45,
+        }
+    }
+
+    /// Goods the caravan brings along, silver included.
+    pub fn stock(self) -> &'static [(ItemKind, u16)] {
+        match self {
+            TraderKind::BulkGoods => &[
+                (ItemKind::Berries, 40),
+                (ItemKind::Log, 30),
+                (ItemKind::Plank, 20),
+                (ItemKind::Stone, 20),
+                (ItemKind::Wool, 10),
+                (ItemKind::Silver, 300),
+            ],
+            TraderKind::CombatSupplier => &[
+                (ItemKind::Bow, 2),
+                (ItemKind::Helmet, 2),
+                (ItemKind::Knife, 3),
+                (ItemKind::Medicine, 4),
+                (ItemKind::Silver, 400),
+            ],
+            TraderKind::Exotic => &[
+                (ItemKind::FineMeal, 6),
+                (ItemKind::Medicine, 6),
+                (ItemKind::Parka, 2),
+                (ItemKind::Silver, 500),
+            ],
+        }
+    }
+}
+
+impl Display for TraderKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            TraderKind::BulkGoods => "Bulk goods trader",
+            TraderKind::CombatSupplier => "Combat supplier",
+            TraderKind::Exotic => "Exotic goods trader",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl ItemKind {
+    /// Silver a single item in perfect condition is worth.
+    pub fn value(self) -> f64 {
+        match self {
+            ItemKind::Berries => 1.0,
+            ItemKind::Bow => 60.0,
+            ItemKind::Corpse => 0.0,
+            ItemKind::Egg => 2.0,
+            ItemKind::FineMeal => 12.0,
+            ItemKind::Hammer => 30.0,
+            ItemKind::Helmet => 45.0,
+            ItemKind::Knife => 40.0,
+            ItemKind::Leather => 4.0,
+            ItemKind::Log => 1.5,
+            ItemKind::Medicine => 18.0,
+            ItemKind::Milk => 2.0,
+            ItemKind::Parka => 50.0,
+            ItemKind::Plank => 2.5,
+            ItemKind::RawMeat => 2.0,
+            ItemKind::SimpleMeal => 6.0,
+            ItemKind::Silver => 1.0,
+            ItemKind::Stone => 1.0,
+            ItemKind::StoneBlock => 2.0,
+            ItemKind::Wool => 3.0,
+        }
+    }
+}
+
+/// Fraction of its value an item is worth in its condition.  Rotten food
+/// is worthless.
+pub fn condition(
+    freshness: Option<Freshness>,
+    durability: Option<Durability>,
+) -> f64 {
+    match (freshness, durability) {
+        (Some(f), _) if f.is_rotten() => 0.0,
+        (Some(f), _) => f64::from(f.percent()) / 100.0,
+        (None, Some(d)) => f64::from(d.percent()) / 100.0,
+        (None, None) => 1.0,
+    }
+}
+
+/// Silver the colony pays for a single item bought from a trader.
+pub fn buy_price(kind: ItemKind, condition: f64, markup: f64) -> u32 {
+    (kind.value() * condition * (1.0 + markup)).ceil() as u32
+}
+
+/// Silver the colony gets for a single item sold to a trader.
+pub fn sell_price(kind: ItemKind, condition: f64, markup: f64) -> u32 {
+    (kind.value() * condition * (1.0 - markup)).floor() as u32
+}
+
+pub fn days_until_next_caravan(random: &mut Random) -> u16 {
+    random.range(MIN_DAYS_BETWEEN_CARAVANS, MAX_DAYS_BETWEEN_CARAVANS) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traders_buy_cheaper_than_they_sell() {
+        for trader in TraderKind::ALL {
+            let markup = trader.markup();
+            assert!(
+                sell_price(ItemKind::Bow, 1.0, markup)
+                    < buy_price(ItemKind::Bow, 1.0, markup)
+            );
+        }
+        assert!(
+            buy_price(ItemKind::Bow, 1.0, TraderKind::BulkGoods.markup())
+                < buy_price(ItemKind::Bow, 1.0, TraderKind::Exotic.markup())
+        );
+    }
+
+    #[test]
+    fn worn_items_are_worth_less() {
+        let worn = sell_price(ItemKind::Parka, 0.5, 0.2);
+        assert!(worn < sell_price(ItemKind::Parka, 1.0, 0.2));
+        assert_eq!(20, worn);
+        assert_eq!(1.0, condition(None, None));
+        assert_eq!(0, buy_price(ItemKind::Parka, 0.0, 0.2));
+    }
+
+    #[test]
+    fn every_trader_brings_silver() {
+        for trader in TraderKind::ALL {
+            assert!(trader
+                .stock()
+                .iter()
+                .any(|(kind, quantity)| *kind == ItemKind::Silver
+                    && *quantity > 0));
+        }
+    }
+}
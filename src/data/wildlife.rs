@@ -0,0 +1,398 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    ItemKind, Location, ProbabilityTable, Random, Season, VisibleObject,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter, Result};
+
+/// Ground tiles it takes to feed a single animal.
+const TILES_PER_ANIMAL: usize = 150;
+
+/// Biomes are decided by the ground tiles of the generated map, each has its
+/// own wildlife.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Biome {
+    Meadow,
+    Scrubland,
+}
+
+impl Biome {
+    pub const ALL: &'static [Biome] = &[Biome::Meadow, Biome::Scrubland];
+
+    /// Biome of a ground tile, `None` for objects that are not ground.
+    pub fn of_ground(ground: VisibleObject) -> Option<Biome> {
+        match ground {
+            VisibleObject::Grass => Some(Biome::Meadow),
+            VisibleObject::Soil => Some(Biome::Scrubland),
+            _ => None,
+        }
+    }
+
+    /// Animals living in the biome and how common they are.
+    pub fn animals(self) -> ProbabilityTable<AnimalKind> {
+        ProbabilityTable::new(match self {
+            Biome::Meadow => vec![
+                (0.4, AnimalKind::Hare),
+                (0.3, AnimalKind::Deer),
+                (0.3, AnimalKind::Sheep),
+            ],
+            Biome::Scrubland => vec![
+                (0.4, AnimalKind::Goat),
+                (0.3, AnimalKind::Boar),
+                (0.3, AnimalKind::Fowl),
+            ],
+        })
+    }
+}
+
+impl Display for Biome {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            Biome::Meadow => "Meadow",
+            Biome::Scrubland => "Scrubland",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// How an animal reacts to colonists.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Temperament {
+    /// Runs away as soon as a colonist comes into view.
+    Timid,
+    /// Only runs away from colonists coming very close.
+    Calm,
+    /// Stands its ground.
+    Aggressive,
+}
+
+impl Temperament {
+    /// Animals run away from colonists closer than this, zero means never.
+    pub fn flee_distance(self) -> u32 {
+        match self {
+            Temperament::Timid => 6,
+            Temperament::Calm => 2,
+            Temperament::Aggressive => 0,
+        }
+    }
+
+    /// Chance of a single handling succeeding in taming the animal.
+    pub fn tame_chance(self, skill_level: u8) -> f64 {
+        let base = match self {
+            Temperament::Timid => 0.15,
+            Temperament::Calm => 0.3,
+            Temperament::Aggressive => 0.05,
+        };
+        (base * (1.0 + f64::from(skill_level) * 0.2)).min(0.9)
+    }
+}
+
+impl Display for Temperament {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            Temperament::Timid => "Timid",
+            Temperament::Calm => "Calm",
+            Temperament::Aggressive => "Aggressive",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum AnimalKind {
+    Boar,
+    Deer,
+    Fowl,
+    Goat,
+    Hare,
+    Sheep,
+}
+
+impl AnimalKind {
+    pub fn stats(self) -> AnimalStats {
+        match self {
+            AnimalKind::Boar => AnimalStats {
+                biome: Biome::Scrubland,
+                millis_per_tile: 25000,
+                temperament: Temperament::Aggressive,
+                breeding_season: Season::Autumn,
+                max_litter_size: 3,
+                product: None,
+                meat: 30,
+                leather: 4,
+            },
+            AnimalKind::Deer => AnimalStats {
+                biome: Biome::Meadow,
+                millis_per_tile: 15000,
+                temperament: Temperament::Timid,
+                breeding_season: Season::Spring,
+                max_litter_size: 1,
+                product: None,
+                meat: 40,
+                leather: 5,
+            },
+            AnimalKind::Fowl => AnimalStats {
+                biome: Biome::Scrubland,
+                millis_per_tile: 20000,
+                temperament: Temperament::Timid,
+                breeding_season: Season::Summer,
+                max_litter_size: 3,
+                product: Some(AnimalProduct {
+                    kind: ItemKind::Egg,
+                    quantity: 1,
+                    interval_days: 1,
+                }),
+                meat: 5,
+                leather: 0,
+            },
+            AnimalKind::Goat => AnimalStats {
+                biome: Biome::Scrubland,
+                millis_per_tile: 30000,
+                temperament: Temperament::Calm,
+                breeding_season: Season::Summer,
+                max_litter_size: 2,
+                product: Some(AnimalProduct {
+                    kind: ItemKind::Milk,
+                    quantity: 2,
+                    interval_days: 1,
+                }),
+                meat: 20,
+                leather: 3,
+            },
+            AnimalKind::Hare => AnimalStats {
+                biome: Biome::Meadow,
+                millis_per_tile: 10000,
+                temperament: Temperament::Timid,
+                breeding_season: Season::Spring,
+                max_litter_size: 4,
+                product: None,
+                meat: 6,
+                leather: 1,
+            },
+            AnimalKind::Sheep => AnimalStats {
+                biome: Biome::Meadow,
+                millis_per_tile: 30000,
+                temperament: Temperament::Calm,
+                breeding_season: Season::Spring,
+                max_litter_size: 1,
+                product: Some(AnimalProduct {
+                    kind: ItemKind::Wool,
+                    quantity: 6,
+                    interval_days: 4,
+                }),
+                meat: 25,
+                leather: 3,
+            },
+        }
+    }
+}
+
+impl Display for AnimalKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            AnimalKind::Boar => "Boar",
+            AnimalKind::Deer => "Deer",
+            AnimalKind::Fowl => "Fowl",
+            AnimalKind::Goat => "Goat",
+            AnimalKind::Hare => "Hare",
+            AnimalKind::Sheep => "Sheep",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimalStats {
+    /// Where the animal is found and whose carrying capacity it counts
+    /// against.
+    pub biome: Biome,
+    /// Milliseconds of game time it takes to walk a single tile.
+    pub millis_per_tile: u32,
+    pub temperament: Temperament,
+    pub breeding_season: Season,
+    pub max_litter_size: u8,
+    /// What a well fed tame animal produces.
+    pub product: Option<AnimalProduct>,
+    /// Raw meat butchered when slaughtered.
+    pub meat: u16,
+    /// Leather butchered when slaughtered.
+    pub leather: u16,
+}
+
+/// Items a tame animal produces every `interval_days` days.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimalProduct {
+    pub kind: ItemKind,
+    pub quantity: u16,
+    pub interval_days: u16,
+}
+
+/// Work the player wants done with an animal.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Designation {
+    Tame,
+    Slaughter,
+}
+
+impl Display for Designation {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            Designation::Tame => "To be tamed",
+            Designation::Slaughter => "To be slaughtered",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// What an animal is doing at the moment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnimalState {
+    Grazing,
+    Wandering,
+    Fleeing,
+}
+
+impl Display for AnimalState {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let name = match self {
+            AnimalState::Grazing => "Grazing",
+            AnimalState::Wandering => "Wandering",
+            AnimalState::Fleeing => "Fleeing",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Biome of each ground tile on the map.
+#[derive(Debug, Default)]
+pub struct Biomes {
+    biomes: BTreeMap<Location, Biome>,
+    tiles: BTreeMap<Biome, Vec<Location>>,
+    /// Name of the region each biome of the map makes up.
+    names: BTreeMap<Biome, String>,
+}
+
+impl Biomes {
+    /// Number of animals living in `biome` the map can feed.
+    pub fn carrying_capacity(&self, biome: Biome) -> usize {
+        self.tiles.get(&biome).map_or(0, Vec::len) / TILES_PER_ANIMAL
+    }
+
+    pub fn get(&self, loc: Location) -> Option<Biome> {
+        self.biomes.get(&loc).copied()
+    }
+
+    pub fn insert(&mut self, loc: Location, biome: Biome) {
+        if let Some(old) = self.biomes.insert(loc, biome) {
+            if let Some(tiles) = self.tiles.get_mut(&old) {
+                tiles.retain(|l| *l != loc);
+            }
+        }
+        self.tiles.entry(biome).or_default().push(loc);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.biomes.is_empty()
+    }
+
+    /// Name of the region of `biome`.
+    pub fn name(&self, biome: Biome) -> Option<&str> {
+        self.names.get(&biome).map(String::as_str)
+    }
+
+    pub fn set_name(&mut self, biome: Biome, name: String) {
+        self.names.insert(biome, name);
+    }
+
+    /// A random tile of `biome`, `None` if there are none on the map.
+    pub fn random_tile(
+        &self,
+        biome: Biome,
+        random: &mut Random,
+    ) -> Option<Location> {
+        self.tiles
+            .get(&biome)
+            .and_then(|tiles| random.pick(tiles))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carrying_capacity_grows_with_the_area() {
+        let mut biomes = Biomes::default();
+        assert!(biomes.is_empty());
+        for x in 0..(TILES_PER_ANIMAL * 2) as i32 {
+            biomes.insert(Location::new(x, 0), Biome::Meadow);
+            biomes.insert(Location::new(x, 1), Biome::Scrubland);
+        }
+        assert_eq!(2, biomes.carrying_capacity(Biome::Meadow));
+        assert_eq!(2, biomes.carrying_capacity(Biome::Scrubland));
+        biomes.insert(Location::new(0, 0), Biome::Scrubland);
+        assert_eq!(1, biomes.carrying_capacity(Biome::Meadow));
+        assert_eq!(Some(Biome::Scrubland), biomes.get(Location::new(0, 0)));
+        assert_eq!(None, biomes.get(Location::new(0, 2)));
+    }
+
+    #[test]
+    fn random_tile_is_in_the_biome() {
+        let mut biomes = Biomes::default();
+        let mut random = Random::new(1);
+        assert_eq!(None, biomes.random_tile(Biome::Meadow, &mut random));
+        biomes.insert(Location::new(3, 4), Biome::Meadow);
+        biomes.insert(Location::new(5, 6), Biome::Scrubland);
+        assert_eq!(
+            Some(Location::new(3, 4)),
+            biomes.random_tile(Biome::Meadow, &mut random)
+        );
+    }
+
+    #[test]
+    fn skilled_handlers_tame_more_easily() {
+        for temperament in [
+            Temperament::Timid,
+            Temperament::Calm,
+            Temperament::Aggressive,
+        ]
+        .iter()
+        {
+            assert!(temperament.tame_chance(0) < temperament.tame_chance(10));
+            assert!(temperament.tame_chance(20) <= 0.9);
+        }
+        assert!(
+            Temperament::Aggressive.tame_chance(5)
+                < Temperament::Calm.tame_chance(5)
+        );
+    }
+
+    #[test]
+    fn animals_live_in_their_biome() {
+        let mut random = Random::new(2);
+        for biome in Biome::ALL {
+            let animals = biome.animals();
+            for _ in 0..20 {
+                let kind = animals.choose(random.unit());
+                assert_eq!(*biome, kind.stats().biome);
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter, Result};
+
+/// Highest priority, work with this priority is done first.
+const HIGHEST_PRIORITY: u8 = 1;
+/// Lowest priority before the work is turned off.
+const LOWEST_PRIORITY: u8 = 4;
+const DEFAULT_PRIORITY: u8 = 3;
+
+/// Kinds of work a colonist can be assigned to.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd,
+)]
+pub enum WorkType {
+    Construct,
+    Cook,
+    Craft,
+    Doctor,
+    Grow,
+    Handle,
+    Haul,
+    Research,
+}
+
+impl WorkType {
+    pub const ALL: &'static [WorkType] = &[
+        WorkType::Construct,
+        WorkType::Cook,
+        WorkType::Craft,
+        WorkType::Doctor,
+        WorkType::Grow,
+        WorkType::Handle,
+        WorkType::Haul,
+        WorkType::Research,
+    ];
+
+    /// Name that fits in a column of the work priorities table.
+    pub fn short_name(self) -> &'static str {
+        match self {
+            WorkType::Construct => "Build",
+            WorkType::Cook => "Cook",
+            WorkType::Craft => "Craft",
+            WorkType::Doctor => "Doctor",
+            WorkType::Grow => "Grow",
+            WorkType::Handle => "Handle",
+            WorkType::Haul => "Haul",
+            WorkType::Research => "Study",
+        }
+    }
+}
+
+impl Display for WorkType {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Priority of each work type for a single colonist.  Work types without a
+/// priority are not done at all.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkPriorities {
+    priorities: BTreeMap<WorkType, u8>,
+    /// Work the colonist will never do, because of their background.
+    disabled: BTreeSet<WorkType>,
+}
+
+impl WorkPriorities {
+    pub fn get(&self, work: WorkType) -> Option<u8> {
+        self.priorities.get(&work).copied()
+    }
+
+    /// Turn `work` off for good, it cannot be cycled back on.
+    pub fn disable(&mut self, work: WorkType) {
+        self.priorities.remove(&work);
+        self.disabled.insert(work);
+    }
+
+    pub fn is_disabled(&self, work: WorkType) -> bool {
+        self.disabled.contains(&work)
+    }
+
+    /// Lower the priority of `work` by one step: after the lowest priority
+    /// the work is turned off, and after off comes the highest priority.
+    /// Disabled work stays off.
+    pub fn cycle(&mut self, work: WorkType) {
+        if self.is_disabled(work) {
+            return;
+        }
+        match self.get(work) {
+            Some(LOWEST_PRIORITY) => {
+                self.priorities.remove(&work);
+            }
+            Some(priority) => {
+                self.priorities.insert(work, priority + 1);
+            }
+            None => {
+                self.priorities.insert(work, HIGHEST_PRIORITY);
+            }
+        }
+    }
+
+    /// Enabled work types, the most important first.  Work types with the
+    /// same priority are ordered as in [WorkType::ALL].
+    pub fn in_order(&self) -> Vec<WorkType> {
+        let mut works: Vec<(u8, WorkType)> = self
+            .priorities
+            .iter()
+            .map(|(work, priority)| (*priority, *work))
+            .collect();
+        works.sort();
+        works.into_iter().map(|(_, work)| work).collect()
+    }
+}
+
+impl Default for WorkPriorities {
+    fn default() -> Self {
+        Self {
+            priorities: WorkType::ALL
+                .iter()
+                .map(|work| (*work, DEFAULT_PRIORITY))
+                .collect(),
+            disabled: BTreeSet::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_work_is_enabled_by_default() {
+        let priorities = WorkPriorities::default();
+        for work in WorkType::ALL {
+            assert_eq!(Some(DEFAULT_PRIORITY), priorities.get(*work));
+        }
+        assert_eq!(WorkType::ALL.to_vec(), priorities.in_order());
+    }
+
+    #[test]
+    fn cycle_goes_through_off() {
+        let mut priorities = WorkPriorities::default();
+        priorities.cycle(WorkType::Haul);
+        assert_eq!(Some(4), priorities.get(WorkType::Haul));
+        priorities.cycle(WorkType::Haul);
+        assert_eq!(None, priorities.get(WorkType::Haul));
+        priorities.cycle(WorkType::Haul);
+        assert_eq!(Some(1), priorities.get(WorkType::Haul));
+    }
+
+    #[test]
+    fn disabled_work_stays_off() {
+        let mut priorities = WorkPriorities::default();
+        priorities.disable(WorkType::Cook);
+        assert!(priorities.is_disabled(WorkType::Cook));
+        priorities.cycle(WorkType::Cook);
+        assert_eq!(None, priorities.get(WorkType::Cook));
+        assert!(!priorities.in_order().contains(&WorkType::Cook));
+    }
+
+    #[test]
+    fn in_order_puts_important_work_first_and_skips_disabled_work() {
+        let mut priorities = WorkPriorities::default();
+        priorities.cycle(WorkType::Construct);
+        priorities.cycle(WorkType::Construct);
+        priorities.cycle(WorkType::Construct);
+        priorities.cycle(WorkType::Cook);
+        priorities.cycle(WorkType::Haul);
+        priorities.cycle(WorkType::Haul);
+        assert_eq!(
+            vec![
+                WorkType::Construct,
+                WorkType::Craft,
+                WorkType::Doctor,
+                WorkType::Grow,
+                WorkType::Handle,
+                WorkType::Research,
+                WorkType::Cook
+            ],
+            priorities.in_order()
+        );
+    }
+}
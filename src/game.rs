@@ -15,22 +15,83 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
+pub use self::caravans::{Caravan, CaravanSystem};
+pub use self::chronicle::{Chronicle, ChronicleSystem, Summary};
+pub use self::combat::{CombatSystem, Projectiles};
 pub use self::cursor::Cursor;
+pub use self::defenses::DefenseSystem;
+pub use self::equipment::EquipmentSystem;
+pub use self::equipment_table::{EquipmentColumn, EquipmentTable};
+pub use self::faction_table::FactionTable;
+pub use self::farming::FarmingSystem;
 pub use self::game_event::GameEvent;
 pub use self::game_time::GameTimeSystem;
+pub use self::health::HealthSystem;
 pub use self::input::InputSystem;
-pub use self::log::{GameLog, LogEntry};
-pub use self::map::MapSystem;
+pub use self::inspector::InspectorSystem;
+pub use self::jobs::{Job, JobSystem};
+pub use self::log::{GameLog, LogEntry, LogKind};
+pub use self::map::{create_colonist, MapSystem};
+pub use self::mood::MoodSystem;
+pub use self::movement::MovementSystem;
+pub use self::needs::NeedsSystem;
+pub use self::quests::{QuestSystem, Quests};
+pub use self::raids::RaidSystem;
+pub use self::relationship_table::RelationshipTable;
 pub use self::rendering::RenderingSystem;
-pub use self::scene_data::SceneData;
+pub use self::research_table::ResearchTable;
+pub use self::rooms::RoomSystem;
+pub use self::row_selection::{RowSelection, SelectRows};
+pub use self::scene_data::{SceneData, ScreenTables};
+pub use self::schedule_editor::ScheduleEditor;
+pub use self::screen::Screen;
+pub use self::selection::Selection;
+pub use self::skills::SkillSystem;
+pub use self::social::SocialSystem;
+pub use self::spoilage::SpoilageSystem;
+pub use self::storyteller::StorytellerSystem;
+pub use self::trade_table::TradeTable;
+pub use self::wildlife::WildlifeSystem;
+pub use self::work_table::WorkTable;
 
 pub mod components;
 
+mod caravans;
+mod chronicle;
+mod combat;
 mod cursor;
+mod defenses;
+mod equipment;
+mod equipment_table;
+mod faction_table;
+mod farming;
 mod game_event;
 mod game_time;
+mod health;
 mod input;
+mod inspector;
+mod items;
+mod jobs;
 mod log;
 mod map;
+mod mood;
+mod movement;
+mod needs;
+mod quests;
+mod raids;
+mod relationship_table;
 mod rendering;
+mod research_table;
+mod rooms;
+mod row_selection;
 mod scene_data;
+mod schedule_editor;
+mod screen;
+mod selection;
+mod skills;
+mod social;
+mod spoilage;
+mod storyteller;
+mod trade_table;
+mod wildlife;
+mod work_table;
@@ -0,0 +1,445 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    arrival_point, days_until_next_caravan, Direction, Factions, ItemKind,
+    Location as Loc, Random, Rectangle, Rooms, Time, TraderKind,
+    VisibleObject, CARAVAN_CAMP_HOURS, FIRST_CARAVAN_DAY, MILLIS_PER_HOUR,
+};
+use crate::game::components::{
+    Health, Location, Movement, Renderable, Trader,
+};
+use crate::game::{Cursor, GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::collections::BTreeMap;
+
+/// Caravans camp at most this far from the center of the colony.
+const CAMP_DISTANCE: u16 = 8;
+/// Caravans camp at least this far from the center of the colony.
+const MIN_CAMP_DISTANCE: u32 = 5;
+const TRADER_Z_INDEX: u16 = 4;
+const TRADERS_PER_CARAVAN: usize = 2;
+/// Traders that cannot reach the camp or the map edge in this long give up.
+const TRAVEL_HOURS: u32 = 6;
+
+/// The trade caravan visiting the colony.
+///
+/// Goods change hands at the camp of the caravan.  Goods the colony buys and
+/// the silver it earns are left at the camp for the colonists to haul away.
+/// Goods the colony sells and the silver it pays are owed until colonists
+/// deliver them.
+#[derive(Clone, Debug, Default)]
+pub struct Caravan {
+    trader: Option<TraderKind>,
+    /// Index of the faction the traders belong to.
+    faction: usize,
+    camp: Loc,
+    is_camped: bool,
+    stock: BTreeMap<ItemKind, u16>,
+    owed: BTreeMap<ItemKind, u16>,
+}
+
+impl Caravan {
+    /// Where to trade, `None` unless a caravan is camping.
+    pub fn camp(&self) -> Option<Loc> {
+        Some(self.camp).filter(|_| self.is_camped)
+    }
+
+    /// Goods delivered by a colonist, the rest stays owed.
+    pub fn deliver(&mut self, kind: ItemKind, quantity: u16) {
+        if let Some(owed) = self.owed.get_mut(&kind) {
+            *owed = owed.saturating_sub(quantity);
+        }
+        self.owed.retain(|_, owed| *owed > 0);
+        *self.stock.entry(kind).or_default() += quantity;
+    }
+
+    pub fn faction(&self) -> usize {
+        self.faction
+    }
+
+    pub fn owe(&mut self, kind: ItemKind, quantity: u16) {
+        *self.owed.entry(kind).or_default() += quantity;
+    }
+
+    pub fn owed(&self) -> &BTreeMap<ItemKind, u16> {
+        &self.owed
+    }
+
+    pub fn stock(&self) -> &BTreeMap<ItemKind, u16> {
+        &self.stock
+    }
+
+    /// Take up to `quantity` goods out of the stock, returns how many were
+    /// taken.
+    pub fn take(&mut self, kind: ItemKind, quantity: u16) -> u16 {
+        let taken = match self.stock.get_mut(&kind) {
+            Some(stock) => {
+                let taken = quantity.min(*stock);
+                *stock -= taken;
+                taken
+            }
+            None => 0,
+        };
+        self.stock.retain(|_, stock| *stock > 0);
+        taken
+    }
+
+    pub fn trader(&self) -> Option<TraderKind> {
+        self.trader
+    }
+
+    fn arrive(&mut self, trader: TraderKind, faction: usize, camp: Loc) {
+        *self = Caravan {
+            trader: Some(trader),
+            faction,
+            camp,
+            is_camped: false,
+            stock: trader.stock().iter().copied().collect(),
+            owed: BTreeMap::new(),
+        };
+    }
+}
+
+/// Sends trade caravans of factions that are not hostile every few days.  A
+/// caravan walks in from a map edge, camps for [CARAVAN_CAMP_HOURS] and
+/// walks back out.  Downed traders stay where they are until they are back
+/// on their feet.  A caravan that cannot reach its camp turns back, and
+/// traders that cannot find their way out are gone after [TRAVEL_HOURS].
+pub struct CaravanSystem {
+    next_caravan_day: u16,
+    /// Game time left until the camping caravan leaves.
+    camp_millis: u32,
+    /// Game time left for the traders to reach the camp or the map edge.
+    travel_millis: u32,
+    is_leaving: bool,
+}
+
+impl CaravanSystem {
+    pub fn new() -> CaravanSystem {
+        CaravanSystem {
+            next_caravan_day: FIRST_CARAVAN_DAY,
+            camp_millis: 0,
+            travel_millis: 0,
+            is_leaving: false,
+        }
+    }
+}
+
+impl<'a> System<'a> for CaravanSystem {
+    type SystemData = CaravanSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        let game_dt = sys_data.time.game_dt_millis();
+        let map = match sys_data.cursor.boundaries() {
+            Some(map) if game_dt > 0 => map,
+            _ => return,
+        };
+        let day = sys_data.time.game_time_days();
+        let trader = match sys_data.caravan.trader {
+            Some(trader) => trader,
+            None => {
+                if day >= self.next_caravan_day {
+                    sys_data.arrive(map);
+                    self.travel_millis = TRAVEL_HOURS * MILLIS_PER_HOUR;
+                    self.next_caravan_day =
+                        day + days_until_next_caravan(&mut sys_data.random);
+                }
+                return;
+            }
+        };
+        sys_data.halt_downed(self.is_leaving);
+        if sys_data.caravan.is_camped {
+            self.camp_millis = self.camp_millis.saturating_sub(game_dt);
+            if self.camp_millis == 0 {
+                sys_data.break_camp();
+                self.travel_millis = TRAVEL_HOURS * MILLIS_PER_HOUR;
+                self.is_leaving = true;
+            }
+        } else if self.is_leaving {
+            self.travel_millis = self.travel_millis.saturating_sub(game_dt);
+            if sys_data.remove_departed(map, self.travel_millis == 0) {
+                sys_data.caravan.trader = None;
+                self.is_leaving = false;
+                sys_data.game_log.push(LogEntry::new(
+                    format!("The {} caravan left", trader).as_str(),
+                ));
+            }
+        } else if sys_data.has_arrived() {
+            self.camp_millis = CARAVAN_CAMP_HOURS * MILLIS_PER_HOUR;
+            sys_data.caravan.is_camped = true;
+            let camp = sys_data.caravan.camp;
+            sys_data.game_log.push(LogEntry::alert(
+                format!(
+                    "The caravan camped at {}, {}, press y to trade",
+                    camp.x, camp.y
+                )
+                .as_str(),
+            ));
+        } else {
+            self.travel_millis = self.travel_millis.saturating_sub(game_dt);
+            if self.travel_millis == 0 {
+                sys_data.head_out();
+                self.travel_millis = TRAVEL_HOURS * MILLIS_PER_HOUR;
+                self.is_leaving = true;
+                sys_data.game_log.push(LogEntry::new(
+                    format!(
+                        "The {} caravan turned back, its camp is walled off",
+                        trader
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct CaravanSystemData<'a> {
+    entities: Entities<'a>,
+    caravan: Write<'a, Caravan>,
+    cursor: Read<'a, Cursor>,
+    factions: Read<'a, Factions>,
+    game_log: Read<'a, GameLog>,
+    random: Write<'a, Random>,
+    rooms: Read<'a, Rooms>,
+    time: Read<'a, Time>,
+    healths: WriteStorage<'a, Health>,
+    locations: WriteStorage<'a, Location>,
+    movements: WriteStorage<'a, Movement>,
+    renderables: WriteStorage<'a, Renderable>,
+    traders: WriteStorage<'a, Trader>,
+}
+
+impl<'a> CaravanSystemData<'a> {
+    /// Spawn the traders of a random caravan on a random map edge and send
+    /// them to a camp near the colony.  No caravan comes if every faction is
+    /// hostile.
+    fn arrive(&mut self, map: Rectangle) {
+        let faction = match self.factions.pick_traders(&mut self.random) {
+            Some(faction) => faction,
+            None => return,
+        };
+        let trader = *self.random.pick(TraderKind::ALL).unwrap();
+        let edge = *self.random.pick(Direction::ALL).unwrap();
+        let arrival = arrival_point(map, edge, &mut self.random);
+        let camp = self.find_camp(map);
+        for idx in 0..TRADERS_PER_CARAVAN {
+            let destination = camp.move_by(idx as i32, 0);
+            self.entities
+                .build_entity()
+                .with(Trader { edge: arrival }, &mut self.traders)
+                .with(Health::default(), &mut self.healths)
+                .with(Location::new(arrival), &mut self.locations)
+                .with(
+                    Movement {
+                        destination: Some(destination),
+                        ..Movement::for_colonist()
+                    },
+                    &mut self.movements,
+                )
+                .with(
+                    Renderable::new(VisibleObject::Trader, TRADER_Z_INDEX),
+                    &mut self.renderables,
+                )
+                .build();
+        }
+        self.caravan.arrive(trader, faction, camp);
+        let name = self.factions.get(faction).map_or("", |f| &f.name);
+        self.game_log.push(LogEntry::alert(
+            format!(
+                "A {} caravan of the {} is coming from the {}",
+                trader, name, edge
+            )
+            .as_str(),
+        ));
+    }
+
+    /// Send the traders back to the edge they came from.  Goods that were
+    /// not delivered are lost.
+    fn break_camp(&mut self) {
+        self.caravan.is_camped = false;
+        let undelivered: u16 = self.caravan.owed.values().sum();
+        self.caravan.owed.clear();
+        self.head_out();
+        let message = if undelivered > 0 {
+            format!(
+                "The caravan is leaving, {} goods were never delivered",
+                undelivered
+            )
+        } else {
+            "The caravan is leaving".to_owned()
+        };
+        self.game_log.push(LogEntry::new(message.as_str()));
+    }
+
+    /// A tile to camp on, not too close to the colony.
+    fn find_camp(&mut self, map: Rectangle) -> Loc {
+        let area = Rectangle::centered_around(
+            Loc::origin(),
+            CAMP_DISTANCE * 2 + 1,
+            CAMP_DISTANCE * 2 + 1,
+        );
+        let area = area.intersect(map).unwrap_or(map);
+        let mut camp = Loc::origin();
+        for _ in 0..20 {
+            camp = Loc::new(
+                self.random.range(area.min_x, area.max_x),
+                self.random.range(area.min_y, area.max_y),
+            );
+            if camp.distance_to(Loc::origin()) >= MIN_CAMP_DISTANCE
+                && !self.rooms.is_blocked(camp)
+            {
+                break;
+            }
+        }
+        camp
+    }
+
+    /// Downed traders stop walking, the others head back out once the
+    /// caravan is leaving.
+    fn halt_downed(&mut self, is_leaving: bool) {
+        for (trader, health, movement) in
+            (&self.traders, &self.healths, &mut self.movements).join()
+        {
+            if health.is_downed {
+                movement.destination = None;
+            } else if is_leaving && movement.destination.is_none() {
+                movement.destination = Some(trader.edge);
+            }
+        }
+    }
+
+    /// Send the traders back to the edge they came from.
+    fn head_out(&mut self) {
+        for (trader, movement) in (&self.traders, &mut self.movements).join() {
+            movement.destination = Some(trader.edge);
+        }
+    }
+
+    /// Whether all traders have reached the camp.
+    fn has_arrived(&self) -> bool {
+        (&self.traders, &self.movements)
+            .join()
+            .all(|(_, m)| m.destination.is_none())
+    }
+
+    /// Remove the traders that reached the edge of the map, or every trader
+    /// still on their feet when `is_stranded`.  Returns `true` once all of
+    /// them are gone.
+    fn remove_departed(&mut self, map: Rectangle, is_stranded: bool) -> bool {
+        let left: Vec<Entity> = (
+            &self.entities,
+            &self.traders,
+            &self.locations,
+            &self.healths,
+        )
+            .join()
+            .filter(|(_, _, l, h)| {
+                let loc = l.location;
+                (is_stranded && !h.is_downed)
+                    || loc.x == map.min_x
+                    || loc.x == map.max_x
+                    || loc.y == map.min_y
+                    || loc.y == map.max_y
+            })
+            .map(|(e, _, _, _)| e)
+            .collect();
+        for entity in left {
+            self.traders.remove(entity);
+            self.healths.remove(entity);
+            self.locations.remove(entity);
+            self.movements.remove(entity);
+            self.renderables.remove(entity);
+            self.entities.delete(entity).unwrap();
+        }
+        self.traders.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trading_needs_a_camping_caravan() {
+        let mut caravan = Caravan::default();
+        assert_eq!(None, caravan.camp());
+        caravan.arrive(TraderKind::BulkGoods, 0, Loc::new(5, 5));
+        assert_eq!(None, caravan.camp());
+        caravan.is_camped = true;
+        assert_eq!(Some(Loc::new(5, 5)), caravan.camp());
+    }
+
+    #[test]
+    fn goods_are_taken_from_the_stock_and_owed_until_delivered() {
+        let mut caravan = Caravan::default();
+        caravan.arrive(TraderKind::BulkGoods, 0, Loc::origin());
+        assert_eq!(40, caravan.take(ItemKind::Berries, 50));
+        assert_eq!(0, caravan.take(ItemKind::Berries, 1));
+        assert_eq!(None, caravan.stock().get(&ItemKind::Berries));
+        caravan.owe(ItemKind::Egg, 3);
+        caravan.deliver(ItemKind::Egg, 2);
+        assert_eq!(Some(&1), caravan.owed().get(&ItemKind::Egg));
+        caravan.deliver(ItemKind::Egg, 1);
+        assert!(caravan.owed().is_empty());
+        assert_eq!(Some(&3), caravan.stock().get(&ItemKind::Egg));
+    }
+
+    #[test]
+    fn caravans_that_cannot_reach_their_camp_turn_back_and_leave() {
+        let mut world = World::new();
+        let mut system = CaravanSystem::new();
+        System::setup(&mut system, &mut world.res);
+        world
+            .write_resource::<Cursor>()
+            .set_boundaries(Rectangle::centered_around(Loc::origin(), 41, 41))
+            .unwrap();
+        world.write_resource::<Time>().set_game_dt(MILLIS_PER_HOUR);
+        world.write_resource::<Caravan>().arrive(
+            TraderKind::BulkGoods,
+            0,
+            Loc::new(5, 5),
+        );
+        let edge = Loc::new(-20, 0);
+        let trader = world
+            .create_entity()
+            .with(Trader { edge })
+            .with(Health::default())
+            .with(Location::new(Loc::new(-10, 0)))
+            .with(Movement {
+                destination: Some(Loc::new(5, 5)),
+                ..Movement::for_colonist()
+            })
+            .build();
+        for _ in 0..TRAVEL_HOURS {
+            system.run_now(&world.res);
+        }
+        {
+            let movements = world.read_storage::<Movement>();
+            let movement = movements.get(trader).unwrap();
+            assert_eq!(Some(edge), movement.destination);
+        }
+        assert_eq!(None, world.read_resource::<Caravan>().camp());
+        for _ in 0..TRAVEL_HOURS {
+            system.run_now(&world.res);
+        }
+        world.maintain();
+        assert!(!world.is_alive(trader));
+        assert_eq!(None, world.read_resource::<Caravan>().trader());
+    }
+}
@@ -0,0 +1,151 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Goal, Outcome, Time};
+use crate::game::components::{Colonist, Item, Monument};
+use crate::game::Cursor;
+use specs::prelude::*;
+
+/// How a game went, shown once it is over.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    pub colony: String,
+    pub outcome: Outcome,
+    pub days: u16,
+    /// Names of the colonists alive at the end.
+    pub colonists: Vec<String>,
+    /// Names of the colonists who died.
+    pub dead: Vec<String>,
+    pub peak_wealth: f64,
+    pub events: Vec<String>,
+}
+
+/// Notable events of the game, the colonists, the wealth of the colony and
+/// how the game ended once it is over.
+#[derive(Debug, Default)]
+pub struct Chronicle {
+    colony: String,
+    goal: Goal,
+    days: u16,
+    events: Vec<String>,
+    colonists: Vec<String>,
+    dead: Vec<String>,
+    wealth: f64,
+    peak_wealth: f64,
+    outcome: Option<Outcome>,
+}
+
+impl Chronicle {
+    pub fn new(goal: Goal, colony: &str) -> Chronicle {
+        Chronicle {
+            colony: colony.to_owned(),
+            goal,
+            ..Chronicle::default()
+        }
+    }
+
+    pub fn record(&mut self, day: u16, event: &str) {
+        self.events.push(format!("Day {}: {}", day, event));
+    }
+
+    pub fn record_death(&mut self, day: u16, name: &str) {
+        self.record(day, format!("{} died.", name).as_str());
+        self.dead.push(name.to_owned());
+    }
+
+    /// Value of all the items of the colony.
+    pub fn wealth(&self) -> f64 {
+        self.wealth
+    }
+
+    /// `None` until the game is over.
+    pub fn summary(&self) -> Option<Summary> {
+        self.outcome.map(|outcome| Summary {
+            colony: self.colony.clone(),
+            outcome,
+            days: self.days,
+            colonists: self.colonists.clone(),
+            dead: self.dead.clone(),
+            peak_wealth: self.peak_wealth,
+            events: self.events.clone(),
+        })
+    }
+}
+
+/// Keeps the [Chronicle] up to date and decides whether the game is lost or
+/// won.
+pub struct ChronicleSystem;
+
+impl ChronicleSystem {
+    pub fn new() -> ChronicleSystem {
+        ChronicleSystem
+    }
+}
+
+impl<'a> System<'a> for ChronicleSystem {
+    type SystemData = (
+        Write<'a, Chronicle>,
+        Read<'a, Cursor>,
+        Read<'a, Time>,
+        Entities<'a>,
+        ReadStorage<'a, Colonist>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, Monument>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (
+            mut chronicle,
+            cursor,
+            time,
+            entities,
+            colonists,
+            items,
+            monuments,
+        ) = sys_data;
+        if cursor.boundaries().is_none()
+            || time.game_dt_millis() == 0
+            || chronicle.outcome.is_some()
+        {
+            return;
+        }
+        let names: Vec<String> = (&entities, &colonists)
+            .join()
+            .map(|(_, c)| c.name.clone())
+            .collect();
+        // Colonists are created after the map.
+        if names.is_empty() && chronicle.colonists.is_empty() {
+            return;
+        }
+        chronicle.colonists = names;
+        chronicle.days = time.game_time_days();
+        chronicle.wealth = items
+            .join()
+            .map(|i| i.kind.value() * f64::from(i.quantity))
+            .sum();
+        chronicle.peak_wealth = chronicle.peak_wealth.max(chronicle.wealth);
+        chronicle.outcome = Outcome::of(
+            chronicle.goal,
+            chronicle.days,
+            chronicle.colonists.len(),
+            monuments.join().next().is_some(),
+        );
+        if let Some(outcome) = chronicle.outcome {
+            let day = chronicle.days;
+            chronicle.record(day, outcome.to_string().as_str());
+        }
+    }
+}
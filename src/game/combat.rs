@@ -0,0 +1,356 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    cover_tiles, has_line_of_fire, line, Attack, BodyPart, Location as Loc,
+    Random, Rooms, Temperament, Time,
+};
+use crate::game::components::{
+    Animal, Colonist, Combatant, Defense, Health, Location, Movement, Raider,
+    Rock, Trader, Tree, Worker,
+};
+use crate::game::{GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::collections::BTreeSet;
+
+/// Skill level animals attack with.
+const ANIMAL_SKILL_LEVEL: u8 = 4;
+/// Real time a projectile takes to reach its target on the map.
+const PROJECTILE_MILLIS: u32 = 300;
+const XP_PER_ATTACK: f32 = 10.0;
+
+#[derive(Clone, Debug)]
+struct Projectile {
+    path: Vec<Loc>,
+    elapsed_millis: u32,
+}
+
+/// Projectiles in flight.  They are only drawn on the map, the damage is
+/// dealt as soon as they are shot.
+#[derive(Debug, Default)]
+pub struct Projectiles {
+    projectiles: Vec<Projectile>,
+}
+
+impl Projectiles {
+    pub fn launch(&mut self, from: Loc, to: Loc) {
+        self.projectiles.push(Projectile {
+            path: line(from, to),
+            elapsed_millis: 0,
+        });
+    }
+
+    /// Tiles the projectiles are flying over.
+    pub fn locations(&self) -> impl Iterator<Item = Loc> + '_ {
+        self.projectiles.iter().map(|p| {
+            let idx = p.elapsed_millis as usize * p.path.len()
+                / PROJECTILE_MILLIS as usize;
+            p.path[idx.min(p.path.len() - 1)]
+        })
+    }
+
+    fn tick(&mut self, dt_millis: u32) {
+        for projectile in self.projectiles.iter_mut() {
+            projectile.elapsed_millis += dt_millis;
+        }
+        self.projectiles
+            .retain(|p| p.elapsed_millis < PROJECTILE_MILLIS);
+    }
+}
+
+/// Colonists and animals with a target close in and attack it whenever
+/// their cooldown allows.  Ranged attacks need a line of fire that is not
+/// blocked by trees, rocks or walls.  Barricades and sandbags next to the
+/// target take the ranged attacks they cover it from.  The victim fights
+/// back, unless it is a timid animal.
+pub struct CombatSystem {}
+
+impl CombatSystem {
+    pub fn new() -> CombatSystem {
+        CombatSystem {}
+    }
+}
+
+impl<'a> System<'a> for CombatSystem {
+    type SystemData = CombatSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        sys_data.projectiles.tick(sys_data.time.dt_millis());
+        let game_dt = sys_data.time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        let blocking: BTreeSet<Loc> = (&sys_data.trees, &sys_data.locations)
+            .join()
+            .map(|(_, l)| l.location)
+            .chain(
+                (&sys_data.rocks, &sys_data.locations)
+                    .join()
+                    .map(|(_, l)| l.location),
+            )
+            .collect();
+        let fighters: Vec<(Entity, Entity)> =
+            (&sys_data.entities, &sys_data.combatants)
+                .join()
+                .filter_map(|(e, c)| c.target.map(|t| (e, t)))
+                .collect();
+        for (attacker, target) in fighters {
+            let locations =
+                (sys_data.location_of(attacker), sys_data.location_of(target));
+            let (from, to) = match locations {
+                (Some(from), Some(to))
+                    if sys_data.can_fight(attacker)
+                        && sys_data.can_fight(target) =>
+                {
+                    (from, to)
+                }
+                _ => {
+                    sys_data.stop_fighting(attacker);
+                    continue;
+                }
+            };
+            let attack = sys_data.attack_of(attacker);
+            let rooms = &sys_data.rooms;
+            let is_in_range = from.distance_to(to) <= attack.range
+                && (!attack.is_ranged()
+                    || has_line_of_fire(from, to, |l| {
+                        blocking.contains(&l) || rooms.is_blocked(l)
+                    }));
+            if let Some(movement) = sys_data.movements.get_mut(attacker) {
+                movement.destination =
+                    if is_in_range { None } else { Some(to) };
+            }
+            let is_ready = match sys_data.combatants.get_mut(attacker) {
+                Some(combatant) => {
+                    combatant.cooldown_millis =
+                        combatant.cooldown_millis.saturating_sub(game_dt);
+                    combatant.cooldown_millis == 0
+                }
+                None => false,
+            };
+            if is_in_range && is_ready {
+                sys_data.strike(attacker, target, attack, from, to);
+                if let Some(c) = sys_data.combatants.get_mut(attacker) {
+                    c.cooldown_millis = attack.cooldown_millis;
+                }
+            }
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct CombatSystemData<'a> {
+    entities: Entities<'a>,
+    game_log: Read<'a, GameLog>,
+    projectiles: Write<'a, Projectiles>,
+    random: Write<'a, Random>,
+    rooms: Read<'a, Rooms>,
+    time: Read<'a, Time>,
+    animals: ReadStorage<'a, Animal>,
+    colonists: ReadStorage<'a, Colonist>,
+    combatants: WriteStorage<'a, Combatant>,
+    defenses: WriteStorage<'a, Defense>,
+    healths: WriteStorage<'a, Health>,
+    locations: ReadStorage<'a, Location>,
+    movements: WriteStorage<'a, Movement>,
+    raiders: ReadStorage<'a, Raider>,
+    traders: ReadStorage<'a, Trader>,
+    rocks: ReadStorage<'a, Rock>,
+    trees: ReadStorage<'a, Tree>,
+    workers: WriteStorage<'a, Worker>,
+}
+
+impl<'a> CombatSystemData<'a> {
+    /// Attack of the weapon in hand, the natural attack of an animal, or
+    /// fists.
+    fn attack_of(&self, entity: Entity) -> Attack {
+        if let Some(animal) = self.animals.get(entity) {
+            return animal.kind.attack();
+        }
+        if let Some(raider) = self.raiders.get(entity) {
+            return raider
+                .weapon
+                .and_then(|w| w.weapon())
+                .unwrap_or(Attack::FISTS);
+        }
+        self.workers
+            .get(entity)
+            .and_then(|w| w.loadout.weapon())
+            .unwrap_or(Attack::FISTS)
+    }
+
+    fn can_fight(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+            && self.healths.get(entity).is_some_and(|h| !h.is_downed)
+    }
+
+    fn location_of(&self, entity: Entity) -> Option<Loc> {
+        self.locations.get(entity).map(|l| l.location)
+    }
+
+    fn name_of(&self, entity: Entity) -> String {
+        match (self.colonists.get(entity), self.animals.get(entity)) {
+            (Some(colonist), _) => colonist.name.clone(),
+            (None, Some(animal)) => format!("the {}", animal.kind),
+            (None, None) if self.raiders.contains(entity) => {
+                "a raider".to_owned()
+            }
+            (None, None) if self.traders.contains(entity) => {
+                "a trader".to_owned()
+            }
+            (None, None) => "someone".to_owned(),
+        }
+    }
+
+    /// The defense giving a target at `to` the best cover against a ranged
+    /// attack from `from`, along with the hit chance it takes away.
+    fn cover_of(&self, from: Loc, to: Loc) -> Option<(Entity, f64)> {
+        let tiles = cover_tiles(from, to);
+        (&self.entities, &self.defenses, &self.locations)
+            .join()
+            .filter(|(_, d, l)| {
+                d.structure.cover() > 0.0 && tiles.contains(&l.location)
+            })
+            .map(|(e, d, _)| (e, d.structure.cover()))
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+    }
+
+    fn stop_fighting(&mut self, entity: Entity) {
+        if let Some(combatant) = self.combatants.get_mut(entity) {
+            combatant.target = None;
+        }
+        if let Some(movement) = self.movements.get_mut(entity) {
+            movement.destination = None;
+        }
+    }
+
+    /// Roll to hit and injure a random body part of the target, armor
+    /// absorbs some of the damage.  The target fights back.
+    fn strike(
+        &mut self,
+        attacker: Entity,
+        target: Entity,
+        attack: Attack,
+        from: Loc,
+        to: Loc,
+    ) {
+        let skill = attack.skill();
+        let level = self
+            .workers
+            .get(attacker)
+            .map_or(ANIMAL_SKILL_LEVEL, |w| w.skills.level(skill));
+        if attack.is_ranged() {
+            self.projectiles.launch(from, to);
+        }
+        let attacker_name = capitalize(&self.name_of(attacker));
+        let target_name = self.name_of(target);
+        let hit_chance = attack.hit_chance(from.distance_to(to), level);
+        let cover = if attack.is_ranged() {
+            self.cover_of(from, to)
+        } else {
+            None
+        };
+        let roll = self.random.unit();
+        let damage = attack.min_damage
+            + (attack.max_damage - attack.min_damage)
+                * self.random.unit() as f32;
+        let message = if roll < hit_chance - cover.map_or(0.0, |(_, c)| c) {
+            let part = *self.random.pick(BodyPart::ALL).unwrap();
+            let armor = self
+                .workers
+                .get(target)
+                .map_or(0.0, |w| w.loadout.armor(part));
+            let toughness = self
+                .colonists
+                .get(target)
+                .map_or(1.0, |c| c.background.damage_taken());
+            let damage = damage * (1.0 - armor) * toughness;
+            if let Some(health) = self.healths.get_mut(target) {
+                health
+                    .injuries
+                    .add(part, attack.damage_type.injury(), damage);
+            }
+            format!(
+                "{} {} {}'s {}",
+                attacker_name, attack.verb, target_name, part
+            )
+        } else if let Some((defense, _)) = cover.filter(|_| roll < hit_chance)
+        {
+            let defense = self.defenses.get_mut(defense).unwrap();
+            defense.damage(damage.round() as u16);
+            format!(
+                "{} hit the {} covering {}",
+                attacker_name,
+                defense.structure.to_string().to_lowercase(),
+                target_name
+            )
+        } else {
+            format!("{} missed {}", attacker_name, target_name)
+        };
+        self.game_log.push(LogEntry::combat(message.as_str()));
+        if let Some(w) = self.workers.get_mut(attacker) {
+            if let Some(level) = w.skills.gain(skill, XP_PER_ATTACK) {
+                self.game_log.push(LogEntry::new(
+                    format!(
+                        "{}'s {} skill is now {}",
+                        attacker_name, skill, level
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+        let fights_back = self
+            .animals
+            .get(target)
+            .is_none_or(|a| a.kind.stats().temperament != Temperament::Timid);
+        if let Some(combatant) = self.combatants.get_mut(target) {
+            if fights_back && combatant.target.is_none() {
+                combatant.target = Some(attacker);
+            }
+        }
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn projectiles_fly_to_the_target_and_disappear() {
+        let mut projectiles = Projectiles::default();
+        projectiles.launch(Loc::new(0, 0), Loc::new(5, 0));
+        assert_eq!(
+            vec![Loc::new(0, 0)],
+            projectiles.locations().collect::<Vec<_>>()
+        );
+        projectiles.tick(PROJECTILE_MILLIS - 1);
+        assert_eq!(
+            vec![Loc::new(5, 0)],
+            projectiles.locations().collect::<Vec<_>>()
+        );
+        projectiles.tick(1);
+        assert_eq!(0, projectiles.locations().count());
+    }
+}
@@ -15,11 +15,373 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::{Location as Loc, VisibleObject};
+use crate::data::{
+    AnimalKind, AnimalState, Background, Bill, Designation, Disposal,
+    Durability, Freshness, Injuries, ItemKind, Loadout, Location as Loc,
+    MentalBreak, Opinions, RaiderPlan, Schedule, Skills, Structure, Thoughts,
+    VisibleObject, WorkPriorities, WorkbenchKind,
+};
+use crate::game::Job;
 use specs::prelude::*;
-use specs::storage::{DenseVecStorage, HashMapStorage};
+use specs::storage::{DenseVecStorage, HashMapStorage, NullStorage};
 use specs_derive::*;
 
+/// Milliseconds of game time it takes a colonist to walk a single tile.
+const COLONIST_MILLIS_PER_TILE: u32 = 30000;
+const HUNGRY_THRESHOLD: f32 = 0.3;
+const TIRED_THRESHOLD: f32 = 0.25;
+
+/// Hunger below this stops tame animals from producing.
+const ANIMAL_HUNGRY_THRESHOLD: f32 = 0.3;
+
+/// A wild or tame animal.  It grazes until `state_millis` runs out, then
+/// wanders somewhere else.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Animal {
+    pub kind: AnimalKind,
+    pub state: AnimalState,
+    /// Game time left in the current state.
+    pub state_millis: u32,
+    /// Year the animal last had young.
+    pub bred_in_year: Option<u16>,
+    pub designation: Option<Designation>,
+    pub is_tame: bool,
+    /// `1.0` means fully fed, only tame animals get hungry.
+    pub food: f32,
+    /// Game time since the animal last produced, in milliseconds.
+    pub produce_millis: u32,
+}
+
+impl Animal {
+    pub fn new(kind: AnimalKind) -> Self {
+        Self {
+            kind,
+            state: AnimalState::Grazing,
+            state_millis: 0,
+            bred_in_year: None,
+            designation: None,
+            is_tame: false,
+            food: 1.0,
+            produce_millis: 0,
+        }
+    }
+
+    pub fn is_hungry(&self) -> bool {
+        self.food < ANIMAL_HUNGRY_THRESHOLD
+    }
+}
+
+/// Downed colonists are carried to beds to be treated.
+#[derive(Component, Debug, Default)]
+#[storage(NullStorage)]
+pub struct Bed;
+
+/// A structure waiting to be built by a worker.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Blueprint {
+    pub structure: Structure,
+}
+
+impl Blueprint {
+    pub fn new(structure: Structure) -> Self {
+        Self { structure }
+    }
+}
+
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Colonist {
+    pub name: String,
+    pub background: Background,
+}
+
+impl Colonist {
+    pub fn new(name: &str, background: Background) -> Self {
+        Self {
+            name: name.to_owned(),
+            background,
+        }
+    }
+}
+
+/// Colonists and animals that can fight.  Attacks can be made once
+/// `cooldown_millis` runs out.
+#[derive(Component, Debug, Default)]
+#[storage(HashMapStorage)]
+pub struct Combatant {
+    pub target: Option<Entity>,
+    pub cooldown_millis: u32,
+}
+
+/// A built barricade, sandbags or trap.  It is destroyed once its hit points
+/// run out.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Defense {
+    pub structure: Structure,
+    pub hp: u16,
+}
+
+impl Defense {
+    pub fn new(structure: Structure) -> Self {
+        Self {
+            structure,
+            hp: structure.max_hp().expect("Structure is not a defense"),
+        }
+    }
+
+    pub fn damage(&mut self, amount: u16) {
+        self.hp = self.hp.saturating_sub(amount);
+    }
+
+    pub fn is_damaged(&self) -> bool {
+        self.hp < self.max_hp()
+    }
+
+    pub fn is_destroyed(&self) -> bool {
+        self.hp == 0
+    }
+
+    pub fn max_hp(&self) -> u16 {
+        self.structure.max_hp().unwrap_or_default()
+    }
+}
+
+/// Name and disposal order of a dead colonist, raider or animal, kept next
+/// to the [Item] of the corpse.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Corpse {
+    pub name: String,
+    pub disposal: Option<Disposal>,
+    /// Kind of the animal, `None` for people.
+    pub animal: Option<AnimalKind>,
+}
+
+impl Corpse {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            disposal: None,
+            animal: None,
+        }
+    }
+
+    pub fn of_animal(kind: AnimalKind) -> Self {
+        Self {
+            animal: Some(kind),
+            ..Self::new(&format!("{} carcass", kind))
+        }
+    }
+}
+
+/// A tile a crop is grown on.  `growth` is `None` until the field is
+/// sown, the crop is ripe once it reaches `1.0`.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Field {
+    pub crop: ItemKind,
+    pub growth: Option<f32>,
+}
+
+impl Field {
+    pub fn new(crop: ItemKind) -> Self {
+        Self { crop, growth: None }
+    }
+
+    pub fn is_ripe(&self) -> bool {
+        self.growth.is_some_and(|g| g >= 1.0)
+    }
+}
+
+/// A grave someone was buried in.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Grave {
+    pub name: String,
+}
+
+/// Injuries of a colonist or an animal.  `is_downed` is kept up to date by the
+/// [HealthSystem](crate::game::HealthSystem).
+#[derive(Component, Debug, Default)]
+#[storage(HashMapStorage)]
+pub struct Health {
+    pub injuries: Injuries,
+    pub is_downed: bool,
+}
+
+/// A stack of items, either lying on the map or carried by a worker.
+///
+/// Food and corpses have freshness, equipment and items the weather
+/// harms have durability.
+#[derive(Clone, Component, Debug)]
+#[storage(DenseVecStorage)]
+pub struct Item {
+    pub kind: ItemKind,
+    pub quantity: u16,
+    pub freshness: Option<Freshness>,
+    pub durability: Option<Durability>,
+}
+
+impl Item {
+    pub fn new(kind: ItemKind, quantity: u16) -> Self {
+        let freshness = kind.shelf_life_days().map(|_| Freshness::default());
+        let durability = (kind.equipment().is_some()
+            || kind.weathering_days().is_some())
+        .then(Durability::default);
+        Self {
+            kind,
+            quantity,
+            freshness,
+            durability,
+        }
+    }
+
+    /// Add the items in `other` to this stack, `other` must be of the same
+    /// kind.
+    pub fn merge(&mut self, other: Item) {
+        debug_assert!(self.kind == other.kind);
+        self.freshness = match (self.freshness, other.freshness) {
+            (Some(a), Some(b)) => {
+                Some(a.merge(self.quantity, b, other.quantity))
+            }
+            (a, b) => a.or(b),
+        };
+        self.durability = match (self.durability, other.durability) {
+            (Some(a), Some(b)) => {
+                Some(a.merge(self.quantity, b, other.quantity))
+            }
+            (a, b) => a.or(b),
+        };
+        self.quantity = self.quantity.saturating_add(other.quantity);
+    }
+
+    /// Take up to `max_quantity` items from this stack.
+    pub fn split(&mut self, max_quantity: u16) -> Item {
+        let quantity = self.quantity.min(max_quantity);
+        self.quantity -= quantity;
+        Item {
+            kind: self.kind,
+            quantity,
+            freshness: self.freshness,
+            durability: self.durability,
+        }
+    }
+}
+
+/// How the colonist feels, `50` is neutral.  Colonists in a very bad mood
+/// have mental breaks.
+#[derive(Component, Debug, Default)]
+#[storage(HashMapStorage)]
+pub struct Mood {
+    pub thoughts: Thoughts,
+    pub mental_break: Option<MentalBreak>,
+    /// Permanent change to the mood from traits.
+    pub traits: i32,
+}
+
+impl Mood {
+    pub fn new(traits: i32) -> Self {
+        Self {
+            traits,
+            ..Self::default()
+        }
+    }
+
+    pub fn value(&self) -> u8 {
+        self.thoughts.mood_with(self.traits)
+    }
+}
+
+/// What a colonist thinks of the other colonists.
+#[derive(Component, Debug, Default)]
+#[storage(HashMapStorage)]
+pub struct Social {
+    pub opinions: Opinions<Entity>,
+}
+
+/// Entities that walk towards a destination, one tile at a time.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Movement {
+    pub destination: Option<Loc>,
+    pub millis_per_tile: u32,
+    /// Tiles left to walk towards the destination, the next one is last.
+    pub path: Vec<Loc>,
+    pub progress_millis: u32,
+}
+
+impl Movement {
+    pub fn new(millis_per_tile: u32) -> Self {
+        Self {
+            destination: None,
+            millis_per_tile,
+            path: vec![],
+            progress_millis: 0,
+        }
+    }
+
+    pub fn for_colonist() -> Self {
+        Self::new(COLONIST_MILLIS_PER_TILE)
+    }
+}
+
+/// Needs of a colonist, `1.0` means fully satisfied.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Needs {
+    pub food: f32,
+    pub rest: f32,
+}
+
+impl Needs {
+    pub fn is_hungry(&self) -> bool {
+        self.food < HUNGRY_THRESHOLD
+    }
+
+    pub fn is_tired(&self) -> bool {
+        self.rest < TIRED_THRESHOLD
+    }
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Self {
+            food: 1.0,
+            rest: 1.0,
+        }
+    }
+}
+
+/// A monument the colonists built.
+#[derive(Component, Debug, Default)]
+#[storage(NullStorage)]
+pub struct Monument;
+
+/// Marks a tile tame animals are kept in.
+#[derive(Component, Debug, Default)]
+#[storage(NullStorage)]
+pub struct Pen;
+
+/// Blocks the line of fire, like trees and walls do.
+#[derive(Component, Debug, Default)]
+#[storage(NullStorage)]
+pub struct Rock;
+
+/// Marks a tile where items are stored.
+#[derive(Component, Debug, Default)]
+#[storage(NullStorage)]
+pub struct Stockpile;
+
+/// A member of a trade caravan, it leaves the map at `edge`.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Trader {
+    pub edge: Loc,
+}
+
 #[derive(Component, Debug)]
 #[storage(HashMapStorage)]
 pub struct Tree {
@@ -44,6 +406,52 @@ impl Location {
     }
 }
 
+/// A member of a hostile faction raiding the colony.  Raiders fight the
+/// colonists they see, otherwise they carry out their plan and leave through
+/// the map edge they arrived from.
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Raider {
+    /// Index of the faction in [Factions](crate::data::Factions).
+    pub faction: usize,
+    pub plan: RaiderPlan,
+    pub weapon: Option<ItemKind>,
+    /// Items stolen from the stockpiles.
+    pub loot: Option<Item>,
+    pub is_retreating: bool,
+    /// Where the raider arrived.
+    pub edge: Loc,
+    /// Game time spent wrecking the current structure, in milliseconds.
+    pub work_millis: u32,
+    /// Game time spent downed outside of a bed, `None` if the raider was
+    /// never downed.
+    pub downed_millis: Option<u32>,
+    /// Carried to a bed while downed, the raider goes home once back on
+    /// their feet.
+    pub is_rescued: bool,
+}
+
+impl Raider {
+    pub fn new(
+        faction: usize,
+        plan: RaiderPlan,
+        weapon: Option<ItemKind>,
+        edge: Loc,
+    ) -> Self {
+        Self {
+            faction,
+            plan,
+            weapon,
+            loot: None,
+            is_retreating: false,
+            edge,
+            work_millis: 0,
+            downed_millis: None,
+            is_rescued: false,
+        }
+    }
+}
+
 #[derive(Component, Debug)]
 #[storage(DenseVecStorage)]
 pub struct Renderable {
@@ -57,8 +465,68 @@ impl Renderable {
     }
 }
 
+/// Blocks movement and encloses rooms.
+#[derive(Component, Debug, Default)]
+#[storage(NullStorage)]
+pub struct Wall;
+
+#[derive(Component, Debug)]
+#[storage(HashMapStorage)]
+pub struct Workbench {
+    pub kind: WorkbenchKind,
+    pub bills: Vec<Bill>,
+}
+
+impl Workbench {
+    pub fn new(kind: WorkbenchKind) -> Self {
+        Self {
+            kind,
+            bills: vec![],
+        }
+    }
+}
+
+/// Colonists that can take jobs, along with the items they are carrying
+/// & wearing, when & what kind of work they do and how good they are at it.
+#[derive(Component, Debug, Default)]
+#[storage(HashMapStorage)]
+pub struct Worker {
+    pub job: Option<Job>,
+    /// Drafted colonists take no jobs and follow the player's orders.
+    pub is_drafted: bool,
+    pub carrying: Vec<Item>,
+    pub loadout: Loadout,
+    pub priorities: WorkPriorities,
+    pub schedule: Schedule,
+    pub skills: Skills,
+}
+
 pub fn register_with(world: &mut World) {
+    world.register::<Animal>();
+    world.register::<Bed>();
+    world.register::<Blueprint>();
+    world.register::<Colonist>();
+    world.register::<Combatant>();
+    world.register::<Corpse>();
+    world.register::<Defense>();
+    world.register::<Field>();
+    world.register::<Grave>();
+    world.register::<Health>();
+    world.register::<Item>();
     world.register::<Location>();
+    world.register::<Monument>();
+    world.register::<Mood>();
+    world.register::<Movement>();
+    world.register::<Needs>();
+    world.register::<Pen>();
+    world.register::<Raider>();
     world.register::<Renderable>();
+    world.register::<Rock>();
+    world.register::<Social>();
+    world.register::<Stockpile>();
+    world.register::<Trader>();
     world.register::<Tree>();
+    world.register::<Wall>();
+    world.register::<Workbench>();
+    world.register::<Worker>();
 }
@@ -24,6 +24,21 @@ pub struct Cursor {
 }
 
 impl Cursor {
+    /// Boundaries of the map, `None` until the map is generated.
+    pub fn boundaries(&self) -> Option<Rectangle> {
+        self.boundaries
+    }
+
+    /// Move the cursor to `location`, if it is within the boundaries.
+    pub fn jump_to(&mut self, location: Location) {
+        if self
+            .boundaries
+            .is_none_or(|bounds| bounds.contains(location))
+        {
+            self.location = location;
+        }
+    }
+
     pub fn location(&self) -> Location {
         self.location
     }
@@ -49,12 +64,6 @@ impl Cursor {
             self.location = new_location
         }
     }
-
-    // This method is for testing only
-    #[allow(dead_code)]
-    fn boundaries(&self) -> Option<Rectangle> {
-        self.boundaries
-    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -109,4 +118,15 @@ mod tests {
         cursor.move_towards(Direction::East);
         assert_eq!(Location::origin(), cursor.location);
     }
+
+    #[test]
+    fn jump_to_stays_within_the_boundaries() {
+        let mut cursor = Cursor::default();
+        let bounds = Rectangle::centered_around(Location::origin(), 5, 5);
+        assert!(cursor.set_boundaries(bounds).is_ok());
+        cursor.jump_to(Location::new(2, -2));
+        assert_eq!(Location::new(2, -2), cursor.location());
+        cursor.jump_to(Location::new(3, 0));
+        assert_eq!(Location::new(2, -2), cursor.location());
+    }
 }
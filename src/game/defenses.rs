@@ -0,0 +1,156 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Attack, BodyPart, Location as Loc, Random, Time};
+use crate::game::components::{
+    Animal, Colonist, Combatant, Defense, Health, Location, Raider, Renderable,
+};
+use crate::game::{GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::collections::BTreeMap;
+
+/// Hit points a trap loses each time it is triggered.
+const TRAP_WEAR: u16 = 10;
+
+/// Springs the traps hostiles step on and removes the defenses whose hit
+/// points have run out.
+///
+/// Raiders, unless rescued, and the animals attacking colonists are
+/// hostile.  Traps are hidden from them, so they do not avoid or wreck
+/// traps.  A trap is only triggered when a hostile moves into its tile.
+pub struct DefenseSystem {
+    /// Where each hostile was the last time the system ran.
+    hostiles: BTreeMap<Entity, Loc>,
+}
+
+impl DefenseSystem {
+    pub fn new() -> DefenseSystem {
+        DefenseSystem {
+            hostiles: BTreeMap::new(),
+        }
+    }
+}
+
+impl<'a> System<'a> for DefenseSystem {
+    type SystemData = DefenseSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        if sys_data.time.game_dt_millis() == 0 {
+            return;
+        }
+        let hostiles = sys_data.hostiles();
+        let traps: BTreeMap<Loc, Entity> =
+            (&sys_data.entities, &sys_data.defenses, &sys_data.locations)
+                .join()
+                .filter(|(_, d, _)| d.structure.is_trap())
+                .map(|(e, _, l)| (l.location, e))
+                .collect();
+        for (hostile, loc) in hostiles.iter() {
+            if self.hostiles.get(hostile) == Some(loc) {
+                continue;
+            }
+            if let Some(trap) = traps.get(loc) {
+                sys_data.spring(*trap, *hostile);
+            }
+        }
+        self.hostiles = hostiles;
+        sys_data.remove_destroyed();
+    }
+}
+
+#[derive(SystemData)]
+pub struct DefenseSystemData<'a> {
+    entities: Entities<'a>,
+    game_log: Read<'a, GameLog>,
+    random: Write<'a, Random>,
+    time: Read<'a, Time>,
+    animals: ReadStorage<'a, Animal>,
+    colonists: ReadStorage<'a, Colonist>,
+    combatants: ReadStorage<'a, Combatant>,
+    defenses: WriteStorage<'a, Defense>,
+    healths: WriteStorage<'a, Health>,
+    locations: WriteStorage<'a, Location>,
+    raiders: ReadStorage<'a, Raider>,
+    renderables: WriteStorage<'a, Renderable>,
+}
+
+impl<'a> DefenseSystemData<'a> {
+    fn hostiles(&self) -> BTreeMap<Entity, Loc> {
+        (&self.entities, &self.locations)
+            .join()
+            .filter(|(e, _)| {
+                self.raiders.get(*e).is_some_and(|r| !r.is_rescued)
+                    || (self.animals.contains(*e)
+                        && self
+                            .combatants
+                            .get(*e)
+                            .and_then(|c| c.target)
+                            .is_some_and(|t| self.colonists.contains(t)))
+            })
+            .map(|(e, l)| (e, l.location))
+            .collect()
+    }
+
+    fn name_of(&self, entity: Entity) -> String {
+        match self.animals.get(entity) {
+            Some(animal) => format!("the {}", animal.kind),
+            None => "a raider".to_owned(),
+        }
+    }
+
+    fn remove_destroyed(&mut self) {
+        let destroyed: Vec<(Entity, String)> =
+            (&self.entities, &self.defenses)
+                .join()
+                .filter(|(_, d)| d.is_destroyed())
+                .map(|(e, d)| (e, d.structure.to_string().to_lowercase()))
+                .collect();
+        for (entity, name) in destroyed {
+            self.defenses.remove(entity);
+            self.locations.remove(entity);
+            self.renderables.remove(entity);
+            self.entities.delete(entity).unwrap();
+            self.game_log.push(LogEntry::alert(
+                format!("A {} was destroyed", name).as_str(),
+            ));
+        }
+    }
+
+    /// Injure the hostile stepping on the trap, which wears the trap down.
+    fn spring(&mut self, trap: Entity, hostile: Entity) {
+        let attack = Attack::SPIKE_TRAP;
+        let name = self.name_of(hostile);
+        let message = if self.random.chance(attack.hit_chance(1, 0)) {
+            let part = *self.random.pick(BodyPart::ALL).unwrap();
+            let damage = attack.min_damage
+                + (attack.max_damage - attack.min_damage)
+                    * self.random.unit() as f32;
+            if let Some(health) = self.healths.get_mut(hostile) {
+                health
+                    .injuries
+                    .add(part, attack.damage_type.injury(), damage);
+            }
+            format!("A spike trap {} {}'s {}", attack.verb, name, part)
+        } else {
+            format!("A spike trap missed {}", name)
+        };
+        self.game_log.push(LogEntry::combat(message.as_str()));
+        if let Some(defense) = self.defenses.get_mut(trap) {
+            defense.damage(TRAP_WEAR);
+        }
+    }
+}
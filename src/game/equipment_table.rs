@@ -0,0 +1,118 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Loadout, Slot};
+use crate::game::{RowSelection, SelectRows};
+use crate::menu::Menu;
+use specs::Entity;
+use std::slice::Iter;
+
+/// Columns of the [EquipmentTable].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EquipmentColumn {
+    Slot(Slot),
+    Policy,
+}
+
+impl EquipmentColumn {
+    pub const ALL: &'static [EquipmentColumn] = &[
+        EquipmentColumn::Slot(Slot::Head),
+        EquipmentColumn::Slot(Slot::Body),
+        EquipmentColumn::Slot(Slot::Hand),
+        EquipmentColumn::Policy,
+    ];
+}
+
+/// A colonist and their equipment, as shown in the [EquipmentTable].
+#[derive(Clone, Debug)]
+pub struct EquipmentRow {
+    pub entity: Entity,
+    pub name: String,
+    pub loadout: Loadout,
+}
+
+/// Full-screen table of colonists, what they wear and what they should
+/// wear.
+///
+/// Colonists are rows, slots and the equip policy are columns.  Columns are
+/// selected through [Menu], rows through [SelectRows].
+#[derive(Clone, Debug, Default)]
+pub struct EquipmentTable {
+    rows: RowSelection<EquipmentRow>,
+    selected_column: usize,
+}
+
+impl EquipmentTable {
+    /// Entity of the colonist in the selected row.
+    pub fn selected_entity(&self) -> Option<Entity> {
+        self.rows.selected().map(|row| row.entity)
+    }
+}
+
+impl SelectRows for EquipmentTable {
+    type Row = EquipmentRow;
+
+    fn row_selection(&self) -> &RowSelection<EquipmentRow> {
+        &self.rows
+    }
+
+    fn row_selection_mut(&mut self) -> &mut RowSelection<EquipmentRow> {
+        &mut self.rows
+    }
+}
+
+pub struct EquipmentTableIterator<'a> {
+    i: Iter<'a, EquipmentColumn>,
+}
+
+impl<'a> Iterator for EquipmentTableIterator<'a> {
+    type Item = &'a EquipmentColumn;
+
+    fn next(&mut self) -> Option<&'a EquipmentColumn> {
+        self.i.next()
+    }
+}
+
+impl<'a> Menu<'a> for EquipmentTable {
+    type Item = EquipmentColumn;
+    type IterMenu = EquipmentTableIterator<'a>;
+
+    fn iter(&self) -> Self::IterMenu {
+        EquipmentTableIterator {
+            i: EquipmentColumn::ALL.iter(),
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.selected_column + 1 < EquipmentColumn::ALL.len() {
+            self.selected_column += 1;
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if self.selected_column > 0 {
+            self.selected_column -= 1;
+        }
+    }
+
+    fn is_selected(&self, item: &EquipmentColumn) -> bool {
+        self.selected() == *item
+    }
+
+    fn selected(&self) -> EquipmentColumn {
+        EquipmentColumn::ALL[self.selected_column]
+    }
+}
@@ -0,0 +1,54 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::Relation;
+use crate::game::{RowSelection, SelectRows};
+
+/// A faction and its feelings toward the colony, as shown in the
+/// [FactionTable].
+#[derive(Clone, Debug)]
+pub struct FactionRow {
+    pub name: String,
+    pub culture: String,
+    pub goodwill: i32,
+    pub relation: Relation,
+    /// Past changes of the goodwill, the latest last.
+    pub history: Vec<String>,
+}
+
+/// Full-screen list of the factions, with the history of the selected one.
+#[derive(Clone, Debug, Default)]
+pub struct FactionTable {
+    rows: RowSelection<FactionRow>,
+}
+
+impl FactionTable {
+    pub fn selected(&self) -> Option<&FactionRow> {
+        self.rows.selected()
+    }
+}
+
+impl SelectRows for FactionTable {
+    type Row = FactionRow;
+
+    fn row_selection(&self) -> &RowSelection<FactionRow> {
+        &self.rows
+    }
+
+    fn row_selection_mut(&mut self) -> &mut RowSelection<FactionRow> {
+        &mut self.rows
+    }
+}
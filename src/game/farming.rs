@@ -0,0 +1,94 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{is_growing_season, Time, MILLIS_PER_DAY};
+use crate::game::components::Field;
+use crate::game::{GameLog, LogEntry};
+use specs::prelude::*;
+
+/// Sown fields grow as game time passes, except in winter.
+pub struct FarmingSystem {}
+
+impl FarmingSystem {
+    pub fn new() -> FarmingSystem {
+        FarmingSystem {}
+    }
+}
+
+impl<'a> System<'a> for FarmingSystem {
+    type SystemData =
+        (Read<'a, GameLog>, Read<'a, Time>, WriteStorage<'a, Field>);
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (game_log, time, mut fields) = sys_data;
+        let game_dt = time.game_dt_millis();
+        if game_dt == 0 || !is_growing_season(time.season()) {
+            return;
+        }
+        for field in (&mut fields).join() {
+            let growing_days = match field.crop.crop() {
+                Some(crop) => crop.growing_days,
+                None => continue,
+            };
+            let growth = match field.growth.as_mut() {
+                Some(growth) if *growth < 1.0 => growth,
+                _ => continue,
+            };
+            *growth += game_dt as f32
+                / (u32::from(growing_days) * MILLIS_PER_DAY) as f32;
+            if *growth >= 1.0 {
+                *growth = 1.0;
+                game_log.push(LogEntry::new(
+                    format!("A field of {} is ripe", field.crop).as_str(),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{ItemKind, DAYS_PER_SEASON};
+
+    fn grow_for_a_day(day: u16) -> f32 {
+        let mut world = World::new();
+        let mut system = FarmingSystem::new();
+        System::setup(&mut system, &mut world.res);
+        {
+            let mut time = world.write_resource::<Time>();
+            time.set_game_time(day, 0, 0, 0);
+            time.set_game_dt(MILLIS_PER_DAY);
+        }
+        let field = world
+            .create_entity()
+            .with(Field {
+                growth: Some(0.0),
+                ..Field::new(ItemKind::Berries)
+            })
+            .build();
+        system.run_now(&world.res);
+        let fields = world.read_storage::<Field>();
+        fields.get(field).unwrap().growth.unwrap()
+    }
+
+    #[test]
+    fn crops_grow_but_not_in_winter() {
+        let growing_days = ItemKind::Berries.crop().unwrap().growing_days;
+        assert_eq!(1.0 / f32::from(growing_days), grow_for_a_day(0));
+        assert_eq!(0.0, grow_for_a_day(3 * DAYS_PER_SEASON));
+    }
+}
@@ -15,10 +15,39 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::Direction;
+use crate::data::{Designation, Direction, Structure, WorkbenchKind};
+use crate::game::Screen;
 
 #[derive(Clone, Copy, Debug)]
 pub enum GameEvent {
+    AddBill,
+    CycleBillMode,
+    /// Place a defense blueprint, or change the one under the cursor to
+    /// the next defense.
+    CycleDefense,
+    CycleActivity,
+    CycleBillRecipe,
+    /// Order the corpse under the cursor buried, cremated or left alone.
+    CycleDisposal,
+    DesignateAnimal(Designation),
     Move(Direction),
+    /// Order the selected drafted colonists to the cursor.
+    Order,
+    PlaceBlueprint(Structure),
+    PlaceWorkbench(WorkbenchKind),
+    RemoveBill,
+    Select,
+    /// Start or finish selecting colonists with the cursor.
+    SelectArea,
     Spacebar,
+    ToggleDraft,
+    /// Place a field of a researched crop, or remove the one under the
+    /// cursor.
+    ToggleField,
+    ToggleRoof,
+    ToggleRoomOverlay,
+    ToggleMark,
+    TogglePen,
+    ToggleScreen(Screen),
+    ToggleStockpile,
 }
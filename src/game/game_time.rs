@@ -37,12 +37,14 @@ impl<'a> System<'a> for GameTimeSystem {
 
     fn run(&mut self, sys_data: Self::SystemData) {
         let (pause, mut time) = sys_data;
-        if !pause.is_paused {
+        if pause.is_paused {
+            time.set_game_dt(0);
+        } else {
+            let game_dt = time.dt_millis() * self.time_scale;
             let mut days = time.game_time_days();
             let mut hours = time.game_time_hours();
             let mut minutes = time.game_time_minutes();
-            let mut millis: u32 =
-                time.game_time_millis() + time.dt_millis() * self.time_scale;
+            let mut millis: u32 = time.game_time_millis() + game_dt;
             // 1 minute is 60000 milliseconds.
             if millis >= 60000 {
                 millis -= 60000;
@@ -57,6 +59,7 @@ impl<'a> System<'a> for GameTimeSystem {
                 days += 1;
             }
             time.set_game_time(days, hours, minutes, millis);
+            time.set_game_dt(game_dt);
         }
     }
 }
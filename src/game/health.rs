@@ -0,0 +1,135 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{ItemKind, Location as Loc, Slot, Time, VisibleObject};
+use crate::game::components::{
+    Bed, Colonist, Corpse, Health, Item, Location, Renderable, Worker,
+};
+use crate::game::jobs::equipped_item;
+use crate::game::{Chronicle, GameLog, LogEntry};
+use specs::prelude::*;
+use std::collections::BTreeSet;
+
+const ITEM_Z_INDEX: u16 = 3;
+
+/// Injuries bleed and heal as game time passes, badly hurt colonists and
+/// animals are downed.  Colonists with fatal injuries die, leaving their
+/// corpse and dropping what they carry and wear.
+pub struct HealthSystem {}
+
+impl HealthSystem {
+    pub fn new() -> HealthSystem {
+        HealthSystem {}
+    }
+}
+
+impl<'a> System<'a> for HealthSystem {
+    type SystemData = (
+        Entities<'a>,
+        Write<'a, Chronicle>,
+        Read<'a, GameLog>,
+        Read<'a, LazyUpdate>,
+        Read<'a, Time>,
+        ReadStorage<'a, Bed>,
+        ReadStorage<'a, Colonist>,
+        WriteStorage<'a, Health>,
+        ReadStorage<'a, Location>,
+        ReadStorage<'a, Worker>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (
+            entities,
+            mut chronicle,
+            game_log,
+            lazy_update,
+            time,
+            beds,
+            colonists,
+            mut healths,
+            locations,
+            workers,
+        ) = sys_data;
+        let game_dt = time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        let bed_tiles: BTreeSet<Loc> = (&beds, &locations)
+            .join()
+            .map(|(_, l)| l.location)
+            .collect();
+        for (entity, colonist, health, location) in
+            (&entities, colonists.maybe(), &mut healths, &locations).join()
+        {
+            let is_in_bed = bed_tiles.contains(&location.location);
+            health.injuries.tick(game_dt, is_in_bed);
+            if let Some(colonist) = colonist {
+                if health.injuries.is_fatal() {
+                    let day = time.game_time_days();
+                    let message = format!("{} died.", colonist.name);
+                    game_log.push(LogEntry::alert(message.as_str()));
+                    chronicle.record_death(day, &colonist.name);
+                    lazy_update
+                        .create_entity(&entities)
+                        .with(Item::new(ItemKind::Corpse, 1))
+                        .with(Corpse::new(&colonist.name))
+                        .with(Location::new(location.location))
+                        .with(Renderable::new(
+                            VisibleObject::Item(ItemKind::Corpse),
+                            ITEM_Z_INDEX,
+                        ))
+                        .build();
+                    if let Some(worker) = workers.get(entity) {
+                        let equipped = Slot::ALL
+                            .iter()
+                            .filter_map(|s| worker.loadout.equipped(*s))
+                            .map(equipped_item);
+                        for item in
+                            worker.carrying.iter().cloned().chain(equipped)
+                        {
+                            let kind = item.kind;
+                            lazy_update
+                                .create_entity(&entities)
+                                .with(item)
+                                .with(Location::new(location.location))
+                                .with(Renderable::new(
+                                    VisibleObject::Item(kind),
+                                    ITEM_Z_INDEX,
+                                ))
+                                .build();
+                        }
+                    }
+                    entities.delete(entity).unwrap();
+                    continue;
+                }
+            }
+            let is_downed = health.injuries.is_downed();
+            if is_downed == health.is_downed {
+                continue;
+            }
+            health.is_downed = is_downed;
+            // Downed animals die, see WildlifeSystem.
+            if let Some(colonist) = colonist {
+                let message = if is_downed {
+                    format!("{} is downed", colonist.name)
+                } else {
+                    format!("{} is back on their feet", colonist.name)
+                };
+                game_log.push(LogEntry::new(message.as_str()));
+            }
+        }
+    }
+}
@@ -15,12 +15,41 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::Pause;
-use crate::game::{Cursor, GameEvent, GameLog, LogEntry};
+use crate::data::Direction;
+use crate::data::{
+    buy_price, condition, gift_goodwill, sell_price, trade_goodwill, Bill,
+    Designation, Disposal, Factions, ItemKind, Location, Pause, Recipe,
+    RecipeBook, Research, RoomOverlay, Rooms, Structure, Time, Unlock,
+    VisibleObject, WorkbenchKind,
+};
+use crate::game::equipment_table::EquipmentRow;
+use crate::game::faction_table::FactionRow;
+use crate::game::relationship_table::RelationshipRow;
+use crate::game::research_table::ResearchRow;
+use crate::game::schedule_editor::ScheduleRow;
+use crate::game::trade_table::TradeRow;
+use crate::game::work_table::WorkTableRow;
+use crate::game::{
+    components, items, Caravan, Cursor, EquipmentColumn, EquipmentTable,
+    FactionTable, GameEvent, GameLog, LogEntry, RelationshipTable,
+    ResearchTable, ScheduleEditor, Screen, SelectRows, Selection, TradeTable,
+    WorkTable,
+};
+use crate::menu::Menu;
 use log::debug;
+use shred_derive::*;
 use specs::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::TryFrom;
 use std::sync::mpsc::Receiver;
 
+const FIELD_Z_INDEX: u16 = 1;
+const PEN_Z_INDEX: u16 = 1;
+const STOCKPILE_Z_INDEX: u16 = 1;
+const BLUEPRINT_Z_INDEX: u16 = 2;
+const ITEM_Z_INDEX: u16 = 3;
+const WORKBENCH_Z_INDEX: u16 = 2;
+
 pub struct InputSystem {
     event_source: Receiver<GameEvent>,
 }
@@ -32,29 +61,1051 @@ impl InputSystem {
 }
 
 impl<'a> System<'a> for InputSystem {
-    type SystemData = (Write<'a, Cursor>, Read<'a, GameLog>, Write<'a, Pause>);
+    type SystemData = InputSystemData<'a>;
 
-    fn run(&mut self, system_data: Self::SystemData) {
-        let (mut cursor, game_log, mut pause) = system_data;
+    fn run(&mut self, mut sys_data: Self::SystemData) {
         for e in self.event_source.try_iter() {
             debug!("Received game event {:?}", e);
-            match e {
-                GameEvent::Move(direction) => {
-                    cursor.move_towards(direction);
-                    game_log.push(LogEntry::new(
-                        format!(
-                            "moved {:?}, new location is {:?}",
-                            direction,
-                            cursor.location()
-                        )
-                        .as_str(),
+            match (*sys_data.screen, e) {
+                (Screen::Map, GameEvent::ToggleScreen(Screen::Trade))
+                    if sys_data.caravan.camp().is_none() =>
+                {
+                    sys_data.game_log.push(LogEntry::new(
+                        "There is no caravan to trade with.",
                     ));
                 }
-                GameEvent::Spacebar => {
-                    pause.is_paused = !pause.is_paused;
-                    game_log.push(LogEntry::new("Spacebar pressed"));
+                (_, GameEvent::ToggleScreen(screen)) => {
+                    sys_data.screen.toggle(screen)
+                }
+                (_, GameEvent::Spacebar) => {
+                    sys_data.pause.is_paused = !sys_data.pause.is_paused;
+                    sys_data.game_log.push(LogEntry::new("Spacebar pressed"));
+                }
+                (Screen::Equipment, e) => {
+                    sys_data.handle_equipment_table_event(e)
+                }
+                (Screen::Factions, e) => sys_data.handle_faction_event(e),
+                (Screen::Map, e) => sys_data.handle_map_event(e),
+                (Screen::Relationships, _) => (),
+                (Screen::Research, e) => sys_data.handle_research_event(e),
+                (Screen::Schedules, e) => sys_data.handle_schedule_event(e),
+                (Screen::Trade, e) => sys_data.handle_trade_event(e),
+                (Screen::WorkPriorities, e) => {
+                    sys_data.handle_work_table_event(e)
+                }
+            }
+        }
+        match *sys_data.screen {
+            Screen::Equipment => sys_data.update_equipment_table(),
+            Screen::Factions => sys_data.update_faction_table(),
+            Screen::Map => (),
+            Screen::Relationships => sys_data.update_relationship_table(),
+            Screen::Research => sys_data.update_research_table(),
+            Screen::Schedules => sys_data.update_schedule_editor(),
+            Screen::Trade => sys_data.update_trade_table(),
+            Screen::WorkPriorities => sys_data.update_work_table(),
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct InputSystemData<'a> {
+    caravan: Write<'a, Caravan>,
+    cursor: Write<'a, Cursor>,
+    entities: Entities<'a>,
+    equipment_table: Write<'a, EquipmentTable>,
+    faction_table: Write<'a, FactionTable>,
+    factions: Write<'a, Factions>,
+    game_log: Read<'a, GameLog>,
+    lazy_update: Read<'a, LazyUpdate>,
+    pause: Write<'a, Pause>,
+    recipe_book: ReadExpect<'a, RecipeBook>,
+    relationship_table: Write<'a, RelationshipTable>,
+    research: Write<'a, Research>,
+    research_table: Write<'a, ResearchTable>,
+    room_overlay: Write<'a, RoomOverlay>,
+    rooms: Write<'a, Rooms>,
+    schedule_editor: Write<'a, ScheduleEditor>,
+    screen: Write<'a, Screen>,
+    selection: Write<'a, Selection>,
+    time: Read<'a, Time>,
+    trade_table: Write<'a, TradeTable>,
+    work_table: Write<'a, WorkTable>,
+    animals: WriteStorage<'a, components::Animal>,
+    beds: ReadStorage<'a, components::Bed>,
+    blueprints: ReadStorage<'a, components::Blueprint>,
+    colonists: ReadStorage<'a, components::Colonist>,
+    combatants: WriteStorage<'a, components::Combatant>,
+    corpses: WriteStorage<'a, components::Corpse>,
+    defenses: ReadStorage<'a, components::Defense>,
+    fields: ReadStorage<'a, components::Field>,
+    items: ReadStorage<'a, components::Item>,
+    locations: ReadStorage<'a, components::Location>,
+    monuments: ReadStorage<'a, components::Monument>,
+    movements: WriteStorage<'a, components::Movement>,
+    socials: ReadStorage<'a, components::Social>,
+    pens: ReadStorage<'a, components::Pen>,
+    stockpiles: ReadStorage<'a, components::Stockpile>,
+    workbenches: WriteStorage<'a, components::Workbench>,
+    workers: WriteStorage<'a, components::Worker>,
+}
+
+impl<'a> InputSystemData<'a> {
+    fn cycle_selected_priority(&mut self) {
+        let work = self.work_table.selected();
+        if let Some(worker) = self
+            .work_table
+            .selected_entity()
+            .and_then(|e| self.workers.get_mut(e))
+        {
+            worker.priorities.cycle(work);
+        }
+    }
+
+    /// Change the wanted item of the selected slot, or the equip policy.
+    fn cycle_selected_equipment(&mut self) {
+        let column = self.equipment_table.selected();
+        if let Some(worker) = self
+            .equipment_table
+            .selected_entity()
+            .and_then(|e| self.workers.get_mut(e))
+        {
+            match column {
+                EquipmentColumn::Slot(slot) => {
+                    worker.loadout.cycle_wanted(slot)
+                }
+                EquipmentColumn::Policy => worker.loadout.toggle_policy(),
+            }
+        }
+    }
+
+    /// Place a barricade blueprint under the cursor, or change the defense
+    /// blueprint that is already there to the next defense.  The last one
+    /// is cancelled instead.
+    fn cycle_defense(&mut self) {
+        let location = self.cursor.location();
+        let blueprint = entity_at(
+            &self.entities,
+            &self.locations,
+            &self.blueprints,
+            location,
+        );
+        let structure = blueprint
+            .and_then(|e| self.blueprints.get(e))
+            .map(|b| b.structure);
+        let research = &self.research;
+        let defenses: Vec<Structure> = Structure::DEFENSES
+            .iter()
+            .copied()
+            .filter(|s| research.is_unlocked(&Unlock::Structure(*s)))
+            .collect();
+        match (blueprint, structure) {
+            (Some(blueprint), Some(structure)) if structure.is_defense() => {
+                self.entities.delete(blueprint).unwrap();
+                match defenses.iter().position(|s| *s == structure) {
+                    Some(idx) if idx + 1 < defenses.len() => {
+                        self.place_blueprint(location, defenses[idx + 1])
+                    }
+                    _ => self.game_log.push(LogEntry::new(
+                        format!("{} cancelled.", structure).as_str(),
+                    )),
+                }
+            }
+            (Some(_), _) => self
+                .game_log
+                .push(LogEntry::new("There is already a blueprint here.")),
+            (None, _) if self.is_occupied(location) => self
+                .game_log
+                .push(LogEntry::new("There is no room to build here.")),
+            (None, _) => match defenses.first() {
+                Some(defense) => self.place_blueprint(location, *defense),
+                None => self
+                    .game_log
+                    .push(LogEntry::new("No defense has been researched.")),
+            },
+        }
+    }
+
+    /// Order the corpse under the cursor disposed of the next way, only
+    /// animal corpses are butchered.
+    fn cycle_disposal(&mut self) {
+        let location = self.cursor.location();
+        let corpse = entity_at(
+            &self.entities,
+            &self.locations,
+            &self.corpses,
+            location,
+        );
+        let corpses = &mut self.corpses;
+        let message = match corpse.and_then(|e| corpses.get_mut(e)) {
+            None => "There is no corpse here.".to_owned(),
+            Some(c) => {
+                c.disposal = Disposal::cycle(c.disposal, c.animal.is_some());
+                match c.disposal {
+                    Some(disposal) => format!("{}: {}.", c.name, disposal),
+                    None => format!("{} is left alone.", c.name),
                 }
             }
+        };
+        self.game_log.push(LogEntry::new(message.as_str()));
+    }
+
+    /// Designate the animal under the cursor, or cancel its designation.
+    /// Only wild animals are tamed and only tame ones are slaughtered.
+    fn designate_animal(&mut self, designation: Designation) {
+        let location = self.cursor.location();
+        let animal = entity_at(
+            &self.entities,
+            &self.locations,
+            &self.animals,
+            location,
+        );
+        let animals = &mut self.animals;
+        let message = match animal.and_then(|e| animals.get_mut(e)) {
+            None => "There is no animal here.".to_owned(),
+            Some(a) => match designation {
+                Designation::Tame if a.is_tame => {
+                    format!("The {} is already tame.", a.kind)
+                }
+                Designation::Slaughter if !a.is_tame => {
+                    "Only tame animals can be slaughtered.".to_owned()
+                }
+                _ if a.designation == Some(designation) => {
+                    a.designation = None;
+                    format!("The {} is left alone.", a.kind)
+                }
+                _ => {
+                    a.designation = Some(designation);
+                    format!("{}: {}.", a.kind, designation)
+                }
+            },
+        };
+        self.game_log.push(LogEntry::new(message.as_str()));
+    }
+
+    /// Selected colonists that are drafted.
+    fn drafted(&self) -> Vec<Entity> {
+        self.selection
+            .selected()
+            .iter()
+            .copied()
+            .filter(|e| self.workers.get(*e).is_some_and(|w| w.is_drafted))
+            .collect()
+    }
+
+    /// Call `f` with the workbench under the cursor.
+    fn edit_bills<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut components::Workbench, &RecipeBook, &Research),
+    {
+        let location = self.cursor.location();
+        let workbench = entity_at(
+            &self.entities,
+            &self.locations,
+            &self.workbenches,
+            location,
+        );
+        let workbenches = &mut self.workbenches;
+        match workbench.and_then(|e| workbenches.get_mut(e)) {
+            Some(workbench) => f(workbench, &self.recipe_book, &self.research),
+            None => self
+                .game_log
+                .push(LogEntry::new("There is no workbench here.")),
         }
     }
+
+    fn handle_map_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::Move(direction) => {
+                self.cursor.move_towards(direction);
+                self.game_log.push(LogEntry::new(
+                    format!(
+                        "moved {:?}, new location is {:?}",
+                        direction,
+                        self.cursor.location()
+                    )
+                    .as_str(),
+                ));
+            }
+            GameEvent::PlaceWorkbench(kind) => self.place_workbench(kind),
+            GameEvent::PlaceBlueprint(structure) => {
+                self.toggle_blueprint(structure)
+            }
+            GameEvent::CycleDefense => self.cycle_defense(),
+            GameEvent::CycleDisposal => self.cycle_disposal(),
+            GameEvent::ToggleField => self.toggle_field(),
+            GameEvent::ToggleRoof => self.toggle_roof(),
+            GameEvent::ToggleRoomOverlay => {
+                self.room_overlay.is_visible = !self.room_overlay.is_visible;
+            }
+            GameEvent::ToggleStockpile => self.toggle_stockpile(),
+            GameEvent::TogglePen => self.toggle_pen(),
+            GameEvent::DesignateAnimal(designation) => {
+                self.designate_animal(designation)
+            }
+            GameEvent::AddBill => {
+                self.edit_bills(|workbench, recipe_book, research| {
+                    if let Some(recipe) = recipe_book.next_recipe_for(
+                        workbench.kind,
+                        None,
+                        |r| is_known(research, r),
+                    ) {
+                        workbench.bills.push(Bill::new(recipe));
+                    }
+                })
+            }
+            GameEvent::CycleBillMode => self.edit_bills(|workbench, _, _| {
+                if let Some(bill) = workbench.bills.last_mut() {
+                    bill.cycle_mode();
+                }
+            }),
+            GameEvent::CycleBillRecipe => {
+                self.edit_bills(|workbench, recipe_book, research| {
+                    let kind = workbench.kind;
+                    if let Some(bill) = workbench.bills.last_mut() {
+                        if let Some(recipe) = recipe_book.next_recipe_for(
+                            kind,
+                            Some(bill.recipe),
+                            |r| is_known(research, r),
+                        ) {
+                            bill.set_recipe(recipe);
+                        }
+                    }
+                })
+            }
+            GameEvent::RemoveBill => self.edit_bills(|workbench, _, _| {
+                workbench.bills.pop();
+            }),
+            GameEvent::Order => self.order(),
+            GameEvent::SelectArea => self.select_area(),
+            GameEvent::ToggleDraft => self.toggle_draft(),
+            _ => (),
+        }
+    }
+
+    /// Order the drafted colonists to attack the hostile under the cursor,
+    /// or to move to the cursor.
+    fn order(&mut self) {
+        let drafted = self.drafted();
+        if drafted.is_empty() {
+            self.game_log
+                .push(LogEntry::new("No drafted colonists are selected."));
+            return;
+        }
+        let location = self.cursor.location();
+        let target = (&self.entities, &self.locations, &self.combatants)
+            .join()
+            .find(|(e, loc, _)| {
+                loc.location == location && !self.colonists.contains(*e)
+            })
+            .map(|(e, _, _)| e);
+        if target.is_none() && self.rooms.is_blocked(location) {
+            self.game_log
+                .push(LogEntry::new("Colonists cannot move there."));
+            return;
+        }
+        for colonist in drafted {
+            if let Some(combatant) = self.combatants.get_mut(colonist) {
+                combatant.target = target;
+            }
+            if let Some(movement) = self.movements.get_mut(colonist) {
+                movement.destination = match target {
+                    Some(_) => None,
+                    None => Some(location),
+                };
+            }
+        }
+    }
+
+    /// Anchor the selection rectangle at the cursor, or select the
+    /// colonists inside it.
+    fn select_area(&mut self) {
+        let cursor = self.cursor.location();
+        match self.selection.area(cursor) {
+            None => self.selection.start(cursor),
+            Some(area) => {
+                let selected: Vec<Entity> =
+                    (&self.entities, &self.locations, &self.colonists)
+                        .join()
+                        .filter(|(_, loc, _)| area.contains(loc.location))
+                        .map(|(e, _, _)| e)
+                        .collect();
+                self.game_log.push(LogEntry::new(
+                    format!("Selected {} colonist(s).", selected.len())
+                        .as_str(),
+                ));
+                self.selection.finish(selected);
+            }
+        }
+    }
+
+    /// Draft the selected colonists, or undraft them if all of them are
+    /// already drafted.
+    fn toggle_draft(&mut self) {
+        let selected = self.selection.selected().to_vec();
+        if selected.is_empty() {
+            self.game_log
+                .push(LogEntry::new("No colonists are selected."));
+            return;
+        }
+        let is_drafted = self.drafted().len() < selected.len();
+        let mut names = vec![];
+        for colonist in selected {
+            if let Some(worker) = self.workers.get_mut(colonist) {
+                worker.is_drafted = is_drafted;
+            }
+            if !is_drafted {
+                if let Some(combatant) = self.combatants.get_mut(colonist) {
+                    combatant.target = None;
+                }
+            }
+            if let Some(movement) = self.movements.get_mut(colonist) {
+                movement.destination = None;
+            }
+            if let Some(c) = self.colonists.get(colonist) {
+                names.push(c.name.clone());
+            }
+        }
+        let message = format!(
+            "{} {}.",
+            names.join(", "),
+            if is_drafted { "drafted" } else { "undrafted" }
+        );
+        self.game_log.push(LogEntry::new(message.as_str()));
+    }
+
+    fn handle_schedule_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::CycleActivity => self.schedule_editor.select_next(),
+            GameEvent::Move(direction) => {
+                self.schedule_editor.move_selection(direction);
+                if self.schedule_editor.is_painting() {
+                    self.paint_schedules();
+                }
+            }
+            GameEvent::Select => {
+                self.schedule_editor.toggle_painting();
+                if self.schedule_editor.is_painting() {
+                    self.paint_schedules();
+                }
+            }
+            GameEvent::ToggleMark => self.schedule_editor.toggle_mark(),
+            _ => (),
+        }
+    }
+
+    fn handle_equipment_table_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::Move(direction) => {
+                let table = &mut self.equipment_table;
+                match direction {
+                    Direction::North => table.select_above(),
+                    Direction::East => table.select_next(),
+                    Direction::South => table.select_below(),
+                    Direction::West => table.select_previous(),
+                }
+            }
+            GameEvent::Select => self.cycle_selected_equipment(),
+            _ => (),
+        }
+    }
+
+    fn handle_trade_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::Move(direction) => match direction {
+                Direction::North => self.trade_table.select_above(),
+                Direction::East => self.trade_table.buy_more(),
+                Direction::South => self.trade_table.select_below(),
+                Direction::West => self.trade_table.sell_more(),
+            },
+            GameEvent::Select => self.trade(),
+            GameEvent::ToggleMark => self.gift(),
+            _ => (),
+        }
+    }
+
+    fn handle_faction_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::Move(Direction::North) => {
+                self.faction_table.select_above()
+            }
+            GameEvent::Move(Direction::South) => {
+                self.faction_table.select_below()
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_research_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::Move(Direction::North) => {
+                self.research_table.select_above()
+            }
+            GameEvent::Move(Direction::South) => {
+                self.research_table.select_below()
+            }
+            GameEvent::Select => {
+                if let Some(project) = self.research_table.selected_project() {
+                    self.research.toggle_queued(project);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn handle_work_table_event(&mut self, e: GameEvent) {
+        match e {
+            GameEvent::Move(direction) => {
+                self.move_work_table_selection(direction)
+            }
+            GameEvent::Select => self.cycle_selected_priority(),
+            _ => (),
+        }
+    }
+
+    fn move_work_table_selection(&mut self, direction: Direction) {
+        let table = &mut self.work_table;
+        match direction {
+            Direction::North => table.select_above(),
+            Direction::East => table.select_next(),
+            Direction::South => table.select_below(),
+            Direction::West => table.select_previous(),
+        }
+    }
+
+    /// Set the selected hour of the targeted colonists' schedules to the
+    /// selected activity.
+    fn paint_schedules(&mut self) {
+        let hour = self.schedule_editor.selected_hour();
+        let activity = self.schedule_editor.selected();
+        for entity in self.schedule_editor.targets() {
+            if let Some(worker) = self.workers.get_mut(entity) {
+                worker.schedule.set(hour, activity);
+            }
+        }
+    }
+
+    fn place_workbench(&mut self, kind: WorkbenchKind) {
+        let location = self.cursor.location();
+        if entity_at(
+            &self.entities,
+            &self.locations,
+            &self.workbenches,
+            location,
+        )
+        .is_some()
+        {
+            self.game_log
+                .push(LogEntry::new("There is already a workbench here."));
+        } else {
+            self.lazy_update
+                .create_entity(&self.entities)
+                .with(components::Location::new(location))
+                .with(components::Renderable::new(
+                    VisibleObject::Workbench(kind),
+                    WORKBENCH_Z_INDEX,
+                ))
+                .with(components::Workbench::new(kind))
+                .build();
+            self.game_log
+                .push(LogEntry::new(format!("{} placed.", kind).as_str()));
+        }
+    }
+
+    /// Place a blueprint under the cursor, or cancel the one that is
+    /// already there.
+    fn toggle_blueprint(&mut self, structure: Structure) {
+        let location = self.cursor.location();
+        let existing = entity_at(
+            &self.entities,
+            &self.locations,
+            &self.blueprints,
+            location,
+        )
+        .and_then(|e| self.blueprints.get(e).map(|b| (e, b.structure)));
+        if let Some((blueprint, cancelled)) = existing {
+            self.entities.delete(blueprint).unwrap();
+            self.game_log.push(LogEntry::new(
+                format!("{} cancelled.", cancelled).as_str(),
+            ));
+        } else if self.is_occupied(location) {
+            self.game_log
+                .push(LogEntry::new("There is no room to build here."));
+        } else if structure == Structure::Wall && self.is_anyone_at(location) {
+            self.game_log
+                .push(LogEntry::new("Someone is standing in the way."));
+        } else if !self.research.is_unlocked(&Unlock::Structure(structure)) {
+            self.game_log.push(LogEntry::new(
+                format!("{} needs research.", structure).as_str(),
+            ));
+        } else {
+            self.place_blueprint(location, structure);
+        }
+    }
+
+    /// Whether a colonist, an animal or anyone else that moves around is at
+    /// `location`.
+    fn is_anyone_at(&self, location: Location) -> bool {
+        entity_at(&self.entities, &self.locations, &self.movements, location)
+            .is_some()
+    }
+
+    /// Whether there is something at `location` that keeps a structure
+    /// from being built there.
+    fn is_occupied(&self, location: Location) -> bool {
+        self.rooms.is_blocked(location)
+            || entity_at(&self.entities, &self.locations, &self.beds, location)
+                .is_some()
+            || entity_at(
+                &self.entities,
+                &self.locations,
+                &self.defenses,
+                location,
+            )
+            .is_some()
+            || entity_at(
+                &self.entities,
+                &self.locations,
+                &self.workbenches,
+                location,
+            )
+            .is_some()
+            || entity_at(
+                &self.entities,
+                &self.locations,
+                &self.monuments,
+                location,
+            )
+            .is_some()
+            || entity_at(
+                &self.entities,
+                &self.locations,
+                &self.items,
+                location,
+            )
+            .is_some()
+            || entity_at(
+                &self.entities,
+                &self.locations,
+                &self.stockpiles,
+                location,
+            )
+            .is_some()
+            || entity_at(
+                &self.entities,
+                &self.locations,
+                &self.fields,
+                location,
+            )
+            .is_some()
+    }
+
+    fn place_blueprint(&mut self, location: Location, structure: Structure) {
+        self.lazy_update
+            .create_entity(&self.entities)
+            .with(components::Location::new(location))
+            .with(components::Renderable::new(
+                VisibleObject::Blueprint(structure),
+                BLUEPRINT_Z_INDEX,
+            ))
+            .with(components::Blueprint::new(structure))
+            .build();
+    }
+
+    /// Place a field under the cursor, sown with the first researched crop,
+    /// or remove the field that is already there.
+    fn toggle_field(&mut self) {
+        let location = self.cursor.location();
+        let research = &self.research;
+        let crop = ItemKind::ALL.iter().copied().find(|kind| {
+            kind.crop().is_some() && research.is_unlocked(&Unlock::Crop(*kind))
+        });
+        let field =
+            entity_at(&self.entities, &self.locations, &self.fields, location);
+        match (field, crop) {
+            (Some(field), _) => {
+                self.entities.delete(field).unwrap();
+                self.game_log.push(LogEntry::new("Field removed."));
+            }
+            (None, _) if self.is_occupied(location) => self
+                .game_log
+                .push(LogEntry::new("There is no room for a field here.")),
+            (None, None) => self
+                .game_log
+                .push(LogEntry::new("No crop has been researched.")),
+            (None, Some(crop)) => {
+                self.lazy_update
+                    .create_entity(&self.entities)
+                    .with(components::Location::new(location))
+                    .with(components::Renderable::new(
+                        VisibleObject::Field,
+                        FIELD_Z_INDEX,
+                    ))
+                    .with(components::Field::new(crop))
+                    .build();
+                self.game_log.push(LogEntry::new(
+                    format!("{} field placed.", crop).as_str(),
+                ));
+            }
+        }
+    }
+
+    fn toggle_roof(&mut self) {
+        let location = self.cursor.location();
+        let message = if self.rooms.toggle_roof(location) {
+            "Roof built."
+        } else {
+            "Roof removed."
+        };
+        self.game_log.push(LogEntry::new(message));
+    }
+
+    fn toggle_pen(&mut self) {
+        let location = self.cursor.location();
+        match entity_at(&self.entities, &self.locations, &self.pens, location)
+        {
+            Some(pen) => {
+                self.entities.delete(pen).unwrap();
+            }
+            None => {
+                self.lazy_update
+                    .create_entity(&self.entities)
+                    .with(components::Location::new(location))
+                    .with(components::Renderable::new(
+                        VisibleObject::Pen,
+                        PEN_Z_INDEX,
+                    ))
+                    .with(components::Pen)
+                    .build();
+            }
+        }
+    }
+
+    fn toggle_stockpile(&mut self) {
+        let location = self.cursor.location();
+        match entity_at(
+            &self.entities,
+            &self.locations,
+            &self.stockpiles,
+            location,
+        ) {
+            Some(stockpile) => {
+                self.entities.delete(stockpile).unwrap();
+            }
+            None => {
+                self.lazy_update
+                    .create_entity(&self.entities)
+                    .with(components::Location::new(location))
+                    .with(components::Renderable::new(
+                        VisibleObject::Stockpile,
+                        STOCKPILE_Z_INDEX,
+                    ))
+                    .with(components::Stockpile)
+                    .build();
+            }
+        }
+    }
+
+    /// Copy the colonists' loadouts into the equipment table.
+    fn update_equipment_table(&mut self) {
+        let rows = (&self.entities, &self.colonists, &self.workers)
+            .join()
+            .map(|(entity, colonist, worker)| EquipmentRow {
+                entity,
+                name: colonist.name.clone(),
+                loadout: worker.loadout.clone(),
+            })
+            .collect();
+        self.equipment_table.set_rows(rows);
+    }
+
+    /// List the factions with their goodwill toward the colony.
+    fn update_faction_table(&mut self) {
+        let rows = self
+            .factions
+            .iter()
+            .map(|faction| FactionRow {
+                name: faction.name.clone(),
+                culture: faction.culture.clone(),
+                goodwill: faction.goodwill(),
+                relation: faction.relation(),
+                history: faction.history().to_vec(),
+            })
+            .collect();
+        self.faction_table.set_rows(rows);
+    }
+
+    /// List the projects of the tech tree with their progress.
+    fn update_research_table(&mut self) {
+        let research = &self.research;
+        let tree = research.tree();
+        let rows = tree
+            .projects()
+            .iter()
+            .enumerate()
+            .map(|(idx, project)| ResearchRow {
+                project: idx,
+                name: project.name.clone(),
+                depth: tree.depth(idx),
+                cost: project.cost,
+                progress: research.progress(idx),
+                is_available: research.is_available(idx),
+                is_completed: research.is_completed(idx),
+                queued: research.queue().iter().position(|p| *p == idx),
+                prerequisites: project.prerequisites.clone(),
+                unlocks: project
+                    .unlocks
+                    .iter()
+                    .map(|unlock| unlock.to_string())
+                    .collect(),
+            })
+            .collect();
+        self.research_table.set_rows(rows);
+    }
+
+    /// List the friends and rivals of each colonist.
+    fn update_relationship_table(&mut self) {
+        let colonists = &self.colonists;
+        let with_names = |opinions: Vec<(Entity, i32)>| {
+            opinions
+                .into_iter()
+                .filter_map(|(other, opinion)| {
+                    colonists.get(other).map(|c| (c.name.clone(), opinion))
+                })
+                .collect()
+        };
+        let rows = (&self.colonists, &self.socials)
+            .join()
+            .map(|(colonist, social)| RelationshipRow {
+                name: colonist.name.clone(),
+                friends: with_names(social.opinions.friends()),
+                rivals: with_names(social.opinions.rivals()),
+            })
+            .collect();
+        self.relationship_table.set_rows(rows);
+    }
+
+    /// Give the goods offered for sale on the trade screen to the caravan
+    /// for nothing.  The faction of the caravan is pleased by the gift.
+    fn gift(&mut self) {
+        if self.caravan.camp().is_none() {
+            return;
+        }
+        if self.trade_table.rows().iter().any(|row| row.offer > 0) {
+            self.game_log
+                .push(LogEntry::new("Gifts cannot include goods to buy."));
+            return;
+        }
+        let value = u32::try_from(-self.trade_table.balance()).unwrap_or(0);
+        let mut given = vec![];
+        for row in self.trade_table.rows().iter().filter(|row| row.offer < 0) {
+            let quantity = u16::try_from(-row.offer).unwrap_or_default();
+            self.caravan.owe(row.kind, quantity);
+            given.push(format!("{} {}", quantity, row.kind));
+        }
+        if given.is_empty() {
+            self.game_log.push(LogEntry::new("Nothing to give."));
+            return;
+        }
+        let day = self.time.game_time_days();
+        if let Some(faction) = self.factions.get_mut(self.caravan.faction()) {
+            faction.change_goodwill(gift_goodwill(value), "Gift", day);
+            self.game_log.push(LogEntry::new(
+                format!("Gave {} to the {}.", given.join(", "), faction.name)
+                    .as_str(),
+            ));
+        }
+        self.trade_table.clear_offers();
+    }
+
+    /// Strike the deal offered on the trade screen.  Bought goods and the
+    /// silver the colony earns are left at the camp of the caravan, sold
+    /// goods and the silver the colony pays are owed to it.  Trading pleases
+    /// the faction of the caravan a little.
+    fn trade(&mut self) {
+        let camp = match self.caravan.camp() {
+            Some(camp) => camp,
+            None => return,
+        };
+        if !self.trade_table.can_afford() {
+            self.game_log.push(LogEntry::new("Not enough silver."));
+            return;
+        }
+        let balance = self.trade_table.balance();
+        let mut dropped = vec![];
+        let mut bought = vec![];
+        let mut sold = vec![];
+        let mut value = 0;
+        for row in self.trade_table.rows() {
+            let quantity = u16::try_from(row.offer.abs()).unwrap_or_default();
+            if row.offer > 0 {
+                let taken = self.caravan.take(row.kind, quantity);
+                dropped.push((row.kind, taken));
+                bought.push(format!("{} {}", taken, row.kind));
+                value += u32::from(taken) * row.buy_price;
+            } else if row.offer < 0 {
+                self.caravan.owe(row.kind, quantity);
+                sold.push(format!("{} {}", quantity, row.kind));
+                value += u32::from(quantity) * row.sell_price;
+            }
+        }
+        if bought.is_empty() && sold.is_empty() {
+            self.game_log.push(LogEntry::new("Nothing to trade."));
+            return;
+        }
+        let silver = u16::try_from(balance.abs()).unwrap_or(u16::MAX);
+        if balance > 0 {
+            self.caravan.owe(ItemKind::Silver, silver);
+        } else if balance < 0 {
+            let taken = self.caravan.take(ItemKind::Silver, silver);
+            dropped.push((ItemKind::Silver, taken));
+        }
+        for (kind, quantity) in dropped.into_iter().filter(|(_, q)| *q > 0) {
+            self.lazy_update
+                .create_entity(&self.entities)
+                .with(components::Item::new(kind, quantity))
+                .with(components::Location::new(camp))
+                .with(components::Renderable::new(
+                    VisibleObject::Item(kind),
+                    ITEM_Z_INDEX,
+                ))
+                .build();
+        }
+        let deal = match (bought.is_empty(), sold.is_empty()) {
+            (false, false) => {
+                format!(
+                    "Bought {}, sold {}",
+                    bought.join(", "),
+                    sold.join(", ")
+                )
+            }
+            (false, true) => format!("Bought {}", bought.join(", ")),
+            _ => format!("Sold {}", sold.join(", ")),
+        };
+        self.game_log.push(LogEntry::new(
+            format!("{} for {} silver.", deal, balance.abs()).as_str(),
+        ));
+        let day = self.time.game_time_days();
+        if let Some(faction) = self.factions.get_mut(self.caravan.faction()) {
+            faction.change_goodwill(trade_goodwill(value), "Traded", day);
+        }
+        self.trade_table.clear_offers();
+    }
+
+    /// List the stockpiled goods of the colony and the goods of the
+    /// caravan, priced by their condition, the trader and the goodwill of
+    /// its faction.  Goes back to the map once the caravan leaves.
+    fn update_trade_table(&mut self) {
+        let trader = match (self.caravan.camp(), self.caravan.trader()) {
+            (Some(_), Some(trader)) => trader,
+            _ => {
+                *self.screen = Screen::Map;
+                return;
+            }
+        };
+        let stockpile_tiles =
+            items::stockpile_tiles(&self.locations, &self.stockpiles);
+        // Quantity and total condition of the stockpiled goods of a kind.
+        let mut colony: BTreeMap<ItemKind, (u32, f64)> = BTreeMap::new();
+        for (item, _) in (&self.items, &self.locations)
+            .join()
+            .filter(|(_, l)| stockpile_tiles.contains(&l.location))
+        {
+            let (quantity, total) = colony.entry(item.kind).or_default();
+            *quantity += u32::from(item.quantity);
+            *total += f64::from(item.quantity)
+                * condition(item.freshness, item.durability);
+        }
+        let caravan = &self.caravan;
+        let available = |kind: ItemKind| {
+            let stockpiled = colony.get(&kind).map_or(0, |(q, _)| *q);
+            let owed = caravan.owed().get(&kind).copied().unwrap_or(0);
+            stockpiled.saturating_sub(u32::from(owed))
+        };
+        let markup = self
+            .factions
+            .get(caravan.faction())
+            .map_or(0.0, |f| f.markup());
+        let markup = (trader.markup() + markup).max(0.0);
+        let kinds: BTreeSet<ItemKind> = colony
+            .keys()
+            .chain(caravan.stock().keys())
+            .copied()
+            .filter(|kind| *kind != ItemKind::Silver)
+            .collect();
+        let rows = kinds
+            .into_iter()
+            .map(|kind| {
+                let average = colony
+                    .get(&kind)
+                    .filter(|(q, _)| *q > 0)
+                    .map_or(1.0, |(q, total)| total / f64::from(*q));
+                TradeRow {
+                    kind,
+                    colony: u16::try_from(available(kind)).unwrap_or(u16::MAX),
+                    caravan: caravan.stock().get(&kind).copied().unwrap_or(0),
+                    buy_price: buy_price(kind, 1.0, markup),
+                    sell_price: sell_price(kind, average, markup),
+                    offer: 0,
+                }
+            })
+            .collect();
+        let colony_silver = available(ItemKind::Silver);
+        let caravan_silver =
+            caravan.stock().get(&ItemKind::Silver).copied().unwrap_or(0);
+        self.trade_table.set_goods(
+            trader,
+            rows,
+            colony_silver,
+            u32::from(caravan_silver),
+        );
+    }
+
+    /// Copy the colonists' schedules into the schedule editor.
+    fn update_schedule_editor(&mut self) {
+        let rows = (&self.entities, &self.colonists, &self.workers)
+            .join()
+            .map(|(entity, colonist, worker)| ScheduleRow {
+                entity,
+                name: colonist.name.clone(),
+                schedule: worker.schedule.clone(),
+            })
+            .collect();
+        self.schedule_editor.set_rows(rows);
+    }
+
+    /// Copy the colonists' priorities into the work table.
+    fn update_work_table(&mut self) {
+        let rows = (&self.entities, &self.colonists, &self.workers)
+            .join()
+            .map(|(entity, colonist, worker)| WorkTableRow {
+                entity,
+                name: colonist.name.clone(),
+                priorities: worker.priorities.clone(),
+            })
+            .collect();
+        self.work_table.set_rows(rows);
+    }
+}
+
+/// Whether bills can be added for the recipe, it may need research.
+fn is_known(research: &Research, recipe: &Recipe) -> bool {
+    research.is_unlocked(&Unlock::Recipe(recipe.name.clone()))
+}
+
+/// Find an entity at `location` that has a component in `storage`.
+fn entity_at<T, D>(
+    entities: &Entities,
+    locations: &ReadStorage<components::Location>,
+    storage: &Storage<T, D>,
+    location: Location,
+) -> Option<Entity>
+where
+    T: Component,
+    D: std::ops::Deref<Target = specs::storage::MaskedStorage<T>>,
+{
+    (entities, locations, storage)
+        .join()
+        .find(|(_, loc, _)| loc.location == location)
+        .map(|(e, _, _)| e)
 }
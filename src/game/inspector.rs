@@ -0,0 +1,337 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{
+    Biomes, Factions, RecipeBook, Research, Rooms, SkillKind, Slot,
+    WorkbenchKind,
+};
+use crate::game::{components, Caravan, Cursor, SceneData};
+use shred_derive::*;
+use specs::prelude::*;
+
+/// Describes the entities under the cursor for the side panel.
+pub struct InspectorSystem {}
+
+impl InspectorSystem {
+    pub fn new() -> InspectorSystem {
+        InspectorSystem {}
+    }
+}
+
+impl<'a> System<'a> for InspectorSystem {
+    type SystemData = InspectorSystemData<'a>;
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let cursor_location = sys_data.cursor.location();
+        let mut lines: Vec<String> = vec![];
+        for (entity, _) in (&sys_data.entities, &sys_data.locations)
+            .join()
+            .filter(|(_, loc)| loc.location == cursor_location)
+        {
+            if let Some(colonist) = sys_data.colonists.get(entity) {
+                let background = &colonist.background;
+                lines.push(colonist.name.clone());
+                lines.push(format!(" Age {}", background.age));
+                lines.push(format!(" {}", background.childhood.title));
+                lines.push(format!(" {}", background.adulthood.title));
+                for t in background.traits.iter() {
+                    lines.push(format!(" * {}", t.name));
+                }
+                for work in background.disabled() {
+                    lines.push(format!(" No {}", work));
+                }
+            }
+            if let Some(animal) = sys_data.animals.get(entity) {
+                let stats = animal.kind.stats();
+                lines.push(format!("{}", animal.kind));
+                lines.push(format!(" {}", stats.temperament));
+                let is_fighting = sys_data
+                    .combatants
+                    .get(entity)
+                    .is_some_and(|c| c.target.is_some());
+                if is_fighting {
+                    lines.push(" Fighting".to_owned());
+                } else {
+                    lines.push(format!(" {}", animal.state));
+                }
+                if animal.is_tame {
+                    lines.push(" Tame".to_owned());
+                    lines.push(format!(" Food {:.0}%", animal.food * 100.0));
+                }
+                if let Some(designation) = animal.designation {
+                    lines.push(format!(" {}", designation));
+                }
+            }
+            if let Some(raider) = sys_data.raiders.get(entity) {
+                lines.push("Raider".to_owned());
+                if let Some(faction) = sys_data.factions.get(raider.faction) {
+                    lines.push(format!(" of the {}", faction.name));
+                }
+                if let Some(weapon) = raider.weapon {
+                    lines.push(format!(" {}", weapon));
+                }
+                let is_fighting = sys_data
+                    .combatants
+                    .get(entity)
+                    .is_some_and(|c| c.target.is_some());
+                if raider.is_rescued {
+                    lines.push(" Rescued".to_owned());
+                } else if raider.is_retreating {
+                    lines.push(" Retreating".to_owned());
+                } else if is_fighting {
+                    lines.push(" Fighting".to_owned());
+                } else {
+                    lines.push(format!(" {}", raider.plan));
+                }
+                if let Some(loot) = &raider.loot {
+                    lines.push(format!(
+                        " Loot: {} {}",
+                        loot.quantity, loot.kind
+                    ));
+                }
+            }
+            if sys_data.traders.get(entity).is_some() {
+                lines.push("Trader".to_owned());
+                if let Some(trader) = sys_data.caravan.trader() {
+                    lines.push(format!(" {} caravan", trader));
+                }
+                if let Some(faction) =
+                    sys_data.factions.get(sys_data.caravan.faction())
+                {
+                    lines.push(format!(" of the {}", faction.name));
+                }
+            }
+            if let Some(needs) = sys_data.needs.get(entity) {
+                lines.push(format!(" Food {:.0}%", needs.food * 100.0));
+                lines.push(format!(" Rest {:.0}%", needs.rest * 100.0));
+            }
+            if let Some(health) = sys_data.healths.get(entity) {
+                if health.is_downed {
+                    lines.push(" Downed".to_owned());
+                }
+                let injuries = &health.injuries;
+                if injuries.iter().next().is_some() {
+                    lines.push(format!(
+                        " Pain {:.0}%",
+                        injuries.pain() * 100.0
+                    ));
+                    lines.push(format!(
+                        " Blood loss {:.0}%",
+                        injuries.blood_loss() * 100.0
+                    ));
+                }
+                for injury in injuries.iter() {
+                    let status = match (injury.treatment, injury.is_infected) {
+                        (_, true) => "infected",
+                        (Some(_), false) => "tended",
+                        (None, false) if injury.kind.bleeding() > 0.0 => {
+                            "bleeding"
+                        }
+                        (None, false) => "",
+                    };
+                    lines.push(format!(
+                        " {} {} {}",
+                        injury.kind, injury.part, status
+                    ));
+                }
+            }
+            if let Some(mood) = sys_data.moods.get(entity) {
+                lines.push(format!(" Mood {}", mood.value()));
+                if let Some(mental_break) = mood.mental_break {
+                    lines.push(format!(" Break: {}", mental_break.kind));
+                }
+                if mood.traits != 0 {
+                    lines
+                        .push(format!(" {:<14}{:>+3}", "Traits", mood.traits));
+                }
+                for thought in mood.thoughts.iter() {
+                    lines.push(format!(
+                        " {:<14}{:>+3}",
+                        thought.kind.to_string(),
+                        thought.kind.mood_effect()
+                    ));
+                }
+            }
+            if let Some(worker) = sys_data.workers.get(entity) {
+                lines.push(
+                    worker
+                        .job
+                        .as_ref()
+                        .map_or("Idle", |job| job.description())
+                        .to_owned(),
+                );
+                for item in worker.carrying.iter() {
+                    lines.push(format!(" {} x{}", item.kind, item.quantity));
+                }
+                for slot in Slot::ALL {
+                    if let Some(item) = worker.loadout.equipped(*slot) {
+                        lines.push(format!(
+                            " {}: {} {}%",
+                            slot,
+                            item.kind,
+                            item.durability.percent()
+                        ));
+                    }
+                }
+                lines.push("Skills".to_owned());
+                for skill in SkillKind::ALL {
+                    lines.push(format!(
+                        " {:<12}{:>2}{:>3}%",
+                        skill.to_string(),
+                        worker.skills.level(*skill),
+                        worker.skills.progress_percent(*skill)
+                    ));
+                }
+            }
+            if let Some(workbench) = sys_data.workbenches.get(entity) {
+                lines.push(format!("{}", workbench.kind));
+                if workbench.kind == WorkbenchKind::Research {
+                    let project = sys_data
+                        .research
+                        .current()
+                        .and_then(|idx| sys_data.research.tree().get(idx));
+                    lines.push(
+                        project
+                            .map_or(" Nothing to research".to_owned(), |p| {
+                                format!(" Researching {}", p.name)
+                            }),
+                    );
+                } else if workbench.bills.is_empty() {
+                    lines.push(" No bills".to_owned());
+                }
+                for (idx, bill) in workbench.bills.iter().enumerate() {
+                    let name = sys_data
+                        .recipe_book
+                        .get(bill.recipe)
+                        .map_or("?", |recipe| recipe.name.as_str());
+                    lines.push(format!("{}.{}", idx + 1, name));
+                    lines.push(format!(
+                        "  {} ({} done)",
+                        bill.mode,
+                        bill.done()
+                    ));
+                }
+            }
+            if let Some(item) = sys_data.items.get(entity) {
+                lines.push(format!("{} x{}", item.kind, item.quantity));
+                if let Some(freshness) = item.freshness {
+                    lines.push(format!(" Fresh {}%", freshness.percent()));
+                }
+                if let Some(durability) = item.durability {
+                    lines.push(format!(
+                        " Durability {}%",
+                        durability.percent()
+                    ));
+                }
+            }
+            if let Some(corpse) = sys_data.corpses.get(entity) {
+                lines.push(format!(" {}", corpse.name));
+                if let Some(disposal) = corpse.disposal {
+                    lines.push(format!(" {}", disposal));
+                }
+            }
+            if let Some(grave) = sys_data.graves.get(entity) {
+                lines.push("Grave".to_owned());
+                lines.push(format!(" {}", grave.name));
+            }
+            if sys_data.stockpiles.get(entity).is_some() {
+                lines.push("Stockpile".to_owned());
+            }
+            if let Some(field) = sys_data.fields.get(entity) {
+                lines.push(format!("{} field", field.crop));
+                lines.push(match field.growth {
+                    Some(growth) => {
+                        format!(" Grown {}%", (growth * 100.0) as u8)
+                    }
+                    None => " Not sown".to_owned(),
+                });
+            }
+            if sys_data.pens.get(entity).is_some() {
+                lines.push("Pen".to_owned());
+            }
+            if let Some(blueprint) = sys_data.blueprints.get(entity) {
+                lines.push(format!("{} blueprint", blueprint.structure));
+            }
+            if sys_data.beds.get(entity).is_some() {
+                lines.push("Bed".to_owned());
+            }
+            if sys_data.walls.get(entity).is_some() {
+                lines.push("Wall".to_owned());
+            }
+            if let Some(defense) = sys_data.defenses.get(entity) {
+                lines.push(format!("{}", defense.structure));
+                lines.push(format!(" {}/{} HP", defense.hp, defense.max_hp()));
+            }
+        }
+        let rooms = &sys_data.rooms;
+        lines.push(
+            if rooms.is_indoors(cursor_location) {
+                "Indoors"
+            } else {
+                "Outdoors"
+            }
+            .to_owned(),
+        );
+        if let Some(room) = rooms.room_at(cursor_location) {
+            lines.push(format!("Room {} tiles", room.area()));
+            lines.push(format!(" {}", room.quality()));
+        }
+        let biomes = &sys_data.biomes;
+        if let Some(region) = biomes
+            .get(cursor_location)
+            .and_then(|biome| biomes.name(biome))
+        {
+            lines.push(region.to_owned());
+        }
+        let mut scene_data = sys_data.scene_data;
+        scene_data.set_inspector_lines(lines);
+    }
+}
+
+#[derive(SystemData)]
+pub struct InspectorSystemData<'a> {
+    biomes: Read<'a, Biomes>,
+    caravan: Read<'a, Caravan>,
+    cursor: Read<'a, Cursor>,
+    entities: Entities<'a>,
+    factions: Read<'a, Factions>,
+    recipe_book: ReadExpect<'a, RecipeBook>,
+    research: Read<'a, Research>,
+    rooms: Read<'a, Rooms>,
+    scene_data: Write<'a, SceneData>,
+    animals: ReadStorage<'a, components::Animal>,
+    beds: ReadStorage<'a, components::Bed>,
+    blueprints: ReadStorage<'a, components::Blueprint>,
+    defenses: ReadStorage<'a, components::Defense>,
+    colonists: ReadStorage<'a, components::Colonist>,
+    combatants: ReadStorage<'a, components::Combatant>,
+    corpses: ReadStorage<'a, components::Corpse>,
+    fields: ReadStorage<'a, components::Field>,
+    graves: ReadStorage<'a, components::Grave>,
+    healths: ReadStorage<'a, components::Health>,
+    items: ReadStorage<'a, components::Item>,
+    locations: ReadStorage<'a, components::Location>,
+    moods: ReadStorage<'a, components::Mood>,
+    needs: ReadStorage<'a, components::Needs>,
+    pens: ReadStorage<'a, components::Pen>,
+    raiders: ReadStorage<'a, components::Raider>,
+    stockpiles: ReadStorage<'a, components::Stockpile>,
+    traders: ReadStorage<'a, components::Trader>,
+    walls: ReadStorage<'a, components::Wall>,
+    workbenches: ReadStorage<'a, components::Workbench>,
+    workers: ReadStorage<'a, components::Worker>,
+}
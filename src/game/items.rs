@@ -0,0 +1,170 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{ItemKind, Location as Loc, VisibleObject};
+use crate::game::components::{Item, Location, Renderable, Stockpile};
+use specs::prelude::*;
+use specs::storage::MaskedStorage;
+use std::collections::BTreeSet;
+use std::ops::Deref;
+
+const ITEM_Z_INDEX: u16 = 3;
+
+/// Drop `item` on `loc`, merging it into a stack of the same kind already
+/// lying there.
+pub fn drop_item(
+    entities: &Entities,
+    items: &mut WriteStorage<Item>,
+    locations: &mut WriteStorage<Location>,
+    renderables: &mut WriteStorage<Renderable>,
+    loc: Loc,
+    item: Item,
+) {
+    let kind = item.kind;
+    let existing = (entities, &*items, &*locations)
+        .join()
+        .find(|(_, i, l)| i.kind == kind && l.location == loc)
+        .map(|(e, _, _)| e);
+    match existing.and_then(|e| items.get_mut(e)) {
+        Some(stack) => stack.merge(item),
+        None => {
+            entities
+                .build_entity()
+                .with(item, items)
+                .with(Location::new(loc), locations)
+                .with(
+                    Renderable::new(VisibleObject::Item(kind), ITEM_Z_INDEX),
+                    renderables,
+                )
+                .build();
+        }
+    }
+}
+
+/// Number of items of `kind` lying in the stockpiles, not counting the
+/// stacks in `reserved`.
+pub fn count_stockpiled<I, L, S>(
+    entities: &Entities,
+    items: &Storage<Item, I>,
+    locations: &Storage<Location, L>,
+    stockpiles: &Storage<Stockpile, S>,
+    kind: ItemKind,
+    reserved: &BTreeSet<Entity>,
+) -> u32
+where
+    I: Deref<Target = MaskedStorage<Item>>,
+    L: Deref<Target = MaskedStorage<Location>>,
+    S: Deref<Target = MaskedStorage<Stockpile>>,
+{
+    let stockpile_tiles = stockpile_tiles(locations, stockpiles);
+    (entities, items, locations)
+        .join()
+        .filter(|(e, item, l)| {
+            item.kind == kind
+                && !reserved.contains(e)
+                && stockpile_tiles.contains(&l.location)
+        })
+        .map(|(_, item, _)| u32::from(item.quantity))
+        .sum()
+}
+
+pub fn stockpile_tiles<L, S>(
+    locations: &Storage<Location, L>,
+    stockpiles: &Storage<Stockpile, S>,
+) -> BTreeSet<Loc>
+where
+    L: Deref<Target = MaskedStorage<Location>>,
+    S: Deref<Target = MaskedStorage<Stockpile>>,
+{
+    (stockpiles, locations)
+        .join()
+        .map(|(_, l)| l.location)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::components;
+
+    #[test]
+    fn items_of_a_kind_stack_on_a_tile() {
+        let mut world = World::new();
+        components::register_with(&mut world);
+        let loc = Loc::new(2, 3);
+        world.exec(
+            |(entities, mut items, mut locations, mut renderables): (
+                Entities,
+                WriteStorage<Item>,
+                WriteStorage<Location>,
+                WriteStorage<Renderable>,
+            )| {
+                for (kind, quantity) in [
+                    (ItemKind::Log, 3),
+                    (ItemKind::Log, 4),
+                    (ItemKind::Stone, 2),
+                ]
+                .iter()
+                {
+                    drop_item(
+                        &entities,
+                        &mut items,
+                        &mut locations,
+                        &mut renderables,
+                        loc,
+                        Item::new(*kind, *quantity),
+                    );
+                }
+            },
+        );
+        world.maintain();
+        let items = world.read_storage::<Item>();
+        let mut stacks: Vec<(ItemKind, u16)> =
+            items.join().map(|i| (i.kind, i.quantity)).collect();
+        stacks.sort_by_key(|(_, quantity)| *quantity);
+        assert_eq!(vec![(ItemKind::Stone, 2), (ItemKind::Log, 7)], stacks);
+    }
+
+    #[test]
+    fn only_unreserved_items_in_the_stockpiles_count() {
+        let mut world = World::new();
+        components::register_with(&mut world);
+        world
+            .create_entity()
+            .with(Stockpile)
+            .with(Location::new(Loc::new(0, 0)))
+            .build();
+        let mut stack = |loc: Loc| {
+            world
+                .create_entity()
+                .with(Item::new(ItemKind::Log, 5))
+                .with(Location::new(loc))
+                .build()
+        };
+        stack(Loc::new(0, 0));
+        let reserved = stack(Loc::new(0, 0));
+        stack(Loc::new(1, 0));
+        let count = count_stockpiled(
+            &world.entities(),
+            &world.read_storage::<Item>(),
+            &world.read_storage::<Location>(),
+            &world.read_storage::<Stockpile>(),
+            ItemKind::Log,
+            &[reserved].iter().copied().collect(),
+        );
+        assert_eq!(5, count);
+    }
+}
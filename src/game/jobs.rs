@@ -0,0 +1,2488 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{
+    is_growing_season, Activity, BodyPart, Designation, Disposal, Equipped,
+    InjuryKind, ItemKind, Location as Loc, MentalBreakKind, Random, Recipe,
+    RecipeBook, Research, Rooms, SkillKind, Slot, Structure, Temperament,
+    ThoughtKind, Time, Treatment, VisibleObject, WorkType, WorkbenchKind,
+    MILLIS_PER_DAY, MILLIS_PER_HOUR, MILLIS_PER_MINUTE,
+};
+use crate::game::components::Item;
+use crate::game::{components, items, Caravan, GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+
+/// Chance of getting hurt in a game hour of skilled work.
+const ACCIDENT_CHANCE_PER_WORK_HOUR: f64 = 0.01;
+const ACCIDENT_MIN_SEVERITY: i32 = 2;
+const ACCIDENT_MAX_SEVERITY: i32 = 10;
+/// Tame animals are fed berries from the stockpiles.
+const ANIMAL_FEED: ItemKind = ItemKind::Berries;
+const BED_Z_INDEX: u16 = 2;
+const BURIAL_MINUTES: u32 = 60;
+const BUTCHERING_MINUTES: u32 = 30;
+/// Logs burned to cremate a corpse.
+const CREMATION_FUEL: (ItemKind, u16) = (ItemKind::Log, 10);
+const CREMATION_MINUTES: u32 = 90;
+const DEFENSE_Z_INDEX: u16 = 2;
+const EATING_MINUTES: u32 = 20;
+const FIELD_Z_INDEX: u16 = 1;
+/// Food of a tame animal restored by each berry fed.
+const FEED_NUTRITION: f32 = 0.2;
+const FEED_QUANTITY: u16 = 5;
+const FEEDING_MINUTES: u32 = 10;
+const GRAVE_Z_INDEX: u16 = 1;
+const HARVESTING_MINUTES: u32 = 30;
+const MONUMENT_Z_INDEX: u16 = 2;
+/// How far colonists wander away during a mental break.
+const WANDER_DISTANCE: i32 = 8;
+/// Game time it takes to restore a hit point of a defense.
+const REPAIR_MINUTES_PER_HP: u32 = 1;
+/// Game time spent at a research bench before looking for other work.
+const RESEARCH_SESSION_MINUTES: u32 = 120;
+/// Fraction of the rest need restored by sleeping a whole game day.
+const REST_RECOVERY_PER_DAY: f32 = 3.5;
+const SLAUGHTER_MINUTES: u32 = 30;
+const SOWING_MINUTES: u32 = 30;
+const TAMING_MINUTES: u32 = 30;
+const TENDING_MINUTES: u32 = 30;
+const WALL_Z_INDEX: u16 = 2;
+const XP_PER_WORK_MINUTE: f32 = 1.0;
+
+/// Work a [Worker](components::Worker) is busy with.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Job {
+    /// Dig a grave next to a corpse and bury it.
+    Bury { corpse: Entity, step: CraftStep },
+    /// Cut up the corpse of an animal for its meat and leather.
+    Butcher { corpse: Entity, step: CraftStep },
+    /// Build the structure of a blueprint, standing next to it.
+    Construct { blueprint: Entity, step: CraftStep },
+    /// Stand by for the player's orders.
+    Drafted,
+    /// Carry out a bill of a workbench.
+    Craft {
+        workbench: Entity,
+        bill: usize,
+        recipe: usize,
+        step: CraftStep,
+    },
+    /// Bring firewood to a corpse and burn it.
+    Cremate { corpse: Entity, step: CraftStep },
+    /// Go to a food stack, take one and eat it.  `remaining` is the game
+    /// time left until finishing eating, in milliseconds.
+    Eat {
+        food: Entity,
+        remaining: Option<u32>,
+    },
+    /// Go to an item and put it on.
+    Equip { item: Entity },
+    /// Bring food to a hungry tame animal.
+    Feed { animal: Entity, step: CraftStep },
+    /// Attack the target of the worker, moving and attacking is left to
+    /// the [CombatSystem](crate::game::CombatSystem).
+    Fight { target: Entity },
+    /// Gather the crop of a ripe field.
+    Harvest { field: Entity, step: CraftStep },
+    /// Carry an item stack to a stockpile.
+    Haul {
+        item: Entity,
+        to: Loc,
+        picked_up: bool,
+    },
+    /// Spend the scheduled recreation time.
+    Relax,
+    /// Restore the hit points of a defense, standing next to it.
+    /// `remaining` is `None` until the worker gets there.
+    Repair {
+        defense: Entity,
+        remaining: Option<u32>,
+    },
+    /// Make research points for the queued projects at a research bench.
+    /// `remaining` is `None` until the worker gets there.
+    Research {
+        bench: Entity,
+        remaining: Option<u32>,
+    },
+    /// Carry a downed colonist to a bed.
+    Rescue {
+        patient: Entity,
+        bed: Entity,
+        carrying: bool,
+    },
+    /// Sleep on the spot until rested and it is no longer sleeping time.
+    Sleep,
+    /// Kill a tame animal for its meat and leather.
+    Slaughter { animal: Entity, step: CraftStep },
+    /// Sow a field that has nothing growing on it.
+    Sow { field: Entity, step: CraftStep },
+    /// Do nothing until the mental break is over.
+    Sulk,
+    /// Handle a wild animal, which may tame it.
+    Tame { animal: Entity, step: CraftStep },
+    /// Treat the injuries of a colonist, using medicine if there is any.
+    Tend { patient: Entity, step: CraftStep },
+    /// Carry goods sold to the caravan to its camp.
+    Trade {
+        kind: ItemKind,
+        quantity: u16,
+        step: CraftStep,
+    },
+    /// Walk to a random place during a mental break.
+    Wander { to: Loc },
+}
+
+impl Job {
+    pub fn description(&self) -> &'static str {
+        match self {
+            Job::Bury { .. } => "Burying",
+            Job::Butcher { .. } => "Butchering",
+            Job::Construct { .. } => "Constructing",
+            Job::Craft { .. } => "Crafting",
+            Job::Cremate { .. } => "Cremating",
+            Job::Drafted => "Drafted",
+            Job::Eat { .. } => "Eating",
+            Job::Equip { .. } => "Equipping",
+            Job::Feed { .. } => "Feeding",
+            Job::Fight { .. } => "Fighting",
+            Job::Harvest { .. } => "Harvesting",
+            Job::Haul { .. } => "Hauling",
+            Job::Relax => "Relaxing",
+            Job::Repair { .. } => "Repairing",
+            Job::Research { .. } => "Researching",
+            Job::Rescue { .. } => "Rescuing",
+            Job::Sleep => "Sleeping",
+            Job::Slaughter { .. } => "Slaughtering",
+            Job::Sow { .. } => "Sowing",
+            Job::Sulk => "Sulking",
+            Job::Tame { .. } => "Taming",
+            Job::Tend { .. } => "Tending",
+            Job::Trade { .. } => "Trading",
+            Job::Wander { .. } => "Wandering",
+        }
+    }
+
+    /// Item stack the job is about to take, nobody else should take it.
+    pub fn reserved_item(&self) -> Option<Entity> {
+        match self {
+            Job::Construct {
+                step: CraftStep::Fetch(Some(item)),
+                ..
+            }
+            | Job::Craft {
+                step: CraftStep::Fetch(Some(item)),
+                ..
+            }
+            | Job::Cremate {
+                step: CraftStep::Fetch(Some(item)),
+                ..
+            }
+            | Job::Feed {
+                step: CraftStep::Fetch(Some(item)),
+                ..
+            }
+            | Job::Tend {
+                step: CraftStep::Fetch(Some(item)),
+                ..
+            }
+            | Job::Trade {
+                step: CraftStep::Fetch(Some(item)),
+                ..
+            }
+            | Job::Eat {
+                food: item,
+                remaining: None,
+            }
+            | Job::Equip { item }
+            | Job::Haul { item, .. } => Some(*item),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CraftStep {
+    /// Collect materials from stockpiles, one stack at a time.
+    Fetch(Option<Entity>),
+    /// Carry the materials to the workbench, the blueprint, the patient,
+    /// the animal or the corpse, or walk to the field.
+    Deliver,
+    /// Work on the recipe, the structure, the patient, the animal, the
+    /// corpse or the field, remaining game time is in milliseconds.
+    Work(u32),
+}
+
+/// Assigns jobs to idle workers and carries out the assigned jobs.
+pub struct JobSystem {}
+
+impl JobSystem {
+    pub fn new() -> JobSystem {
+        JobSystem {}
+    }
+}
+
+impl<'a> System<'a> for JobSystem {
+    type SystemData = JobSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        let workers: Vec<Entity> = (&sys_data.entities, &sys_data.workers)
+            .join()
+            .map(|(e, _)| e)
+            .collect();
+        for worker in workers {
+            if sys_data.is_downed(worker) {
+                sys_data.take_job(worker);
+                sys_data.drop_carried(worker);
+                continue;
+            }
+            // Fighting comes before any other job.
+            if let Some(target) = sys_data.target_of(worker) {
+                sys_data.replace_job(worker, Job::Fight { target });
+                continue;
+            }
+            if sys_data.is_drafted(worker) {
+                sys_data.replace_job(worker, Job::Drafted);
+                continue;
+            }
+            let job = match sys_data.take_job(worker) {
+                Some(job) if sys_data.suits_mental_state(worker, &job) => {
+                    sys_data.progress(worker, job)
+                }
+                Some(_) => {
+                    sys_data.drop_carried(worker);
+                    sys_data.assign(worker)
+                }
+                None => sys_data.assign(worker),
+            };
+            if job.is_none() {
+                sys_data.drop_carried(worker);
+            }
+            if let Some(w) = sys_data.workers.get_mut(worker) {
+                w.job = job;
+            }
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct JobSystemData<'a> {
+    caravan: Write<'a, Caravan>,
+    entities: Entities<'a>,
+    game_log: Read<'a, GameLog>,
+    random: Write<'a, Random>,
+    recipe_book: ReadExpect<'a, RecipeBook>,
+    research: Write<'a, Research>,
+    rooms: Read<'a, Rooms>,
+    time: Read<'a, Time>,
+    animals: WriteStorage<'a, components::Animal>,
+    beds: WriteStorage<'a, components::Bed>,
+    blueprints: WriteStorage<'a, components::Blueprint>,
+    colonists: ReadStorage<'a, components::Colonist>,
+    combatants: WriteStorage<'a, components::Combatant>,
+    corpses: WriteStorage<'a, components::Corpse>,
+    defenses: WriteStorage<'a, components::Defense>,
+    fields: WriteStorage<'a, components::Field>,
+    graves: WriteStorage<'a, components::Grave>,
+    healths: WriteStorage<'a, components::Health>,
+    items: WriteStorage<'a, components::Item>,
+    locations: WriteStorage<'a, components::Location>,
+    monuments: WriteStorage<'a, components::Monument>,
+    moods: WriteStorage<'a, components::Mood>,
+    movements: WriteStorage<'a, components::Movement>,
+    needs: WriteStorage<'a, components::Needs>,
+    renderables: WriteStorage<'a, components::Renderable>,
+    stockpiles: ReadStorage<'a, components::Stockpile>,
+    walls: WriteStorage<'a, components::Wall>,
+    workbenches: WriteStorage<'a, components::Workbench>,
+    workers: WriteStorage<'a, components::Worker>,
+}
+
+impl<'a> JobSystemData<'a> {
+    fn assign(&mut self, worker: Entity) -> Option<Job> {
+        let location = self.location_of(worker)?;
+        match self.mental_break_of(worker) {
+            Some(MentalBreakKind::FoodBinge) => {
+                return self.find_food(location).or(Some(Job::Sulk));
+            }
+            Some(MentalBreakKind::Sulk) => return Some(Job::Sulk),
+            Some(MentalBreakKind::Wander) => {
+                let to = location.move_by(
+                    self.random.range(-WANDER_DISTANCE, WANDER_DISTANCE),
+                    self.random.range(-WANDER_DISTANCE, WANDER_DISTANCE),
+                );
+                return Some(Job::Wander {
+                    to: if self.rooms.is_blocked(to) {
+                        location
+                    } else {
+                        to
+                    },
+                });
+            }
+            None => (),
+        }
+        let is_hungry = self
+            .needs
+            .get(worker)
+            .is_some_and(components::Needs::is_hungry);
+        if is_hungry {
+            if let Some(job) = self.find_food(location) {
+                return Some(job);
+            }
+        }
+        let is_tired = self
+            .needs
+            .get(worker)
+            .is_some_and(components::Needs::is_tired);
+        match self.activity_of(worker) {
+            Activity::Sleep => return Some(Job::Sleep),
+            Activity::Anything if is_tired => return Some(Job::Sleep),
+            Activity::Recreation => return Some(Job::Relax),
+            Activity::Anything | Activity::Work => (),
+        }
+        self.drop_unwanted_equipment(worker);
+        if let Some(job) = self.find_equip_job(worker) {
+            return Some(job);
+        }
+        let works = self
+            .workers
+            .get(worker)
+            .map(|w| w.priorities.in_order())
+            .unwrap_or_default();
+        works.into_iter().find_map(|work| match work {
+            WorkType::Construct => {
+                self.find_construct_job().or_else(|| self.find_repair_job())
+            }
+            WorkType::Cook => self.find_craft_job(WorkbenchKind::Stove),
+            WorkType::Craft => self.find_craft_job(WorkbenchKind::Crafting),
+            WorkType::Doctor => self.find_doctor_job(worker),
+            WorkType::Grow => self.find_grow_job(),
+            WorkType::Handle => self.find_handle_job(),
+            WorkType::Haul => self
+                .find_trade_job()
+                .or_else(|| self.find_dispose_job())
+                .or_else(|| self.find_haul_job(location)),
+            WorkType::Research => self.find_research_job(),
+        })
+    }
+
+    fn progress(&mut self, worker: Entity, job: Job) -> Option<Job> {
+        match job {
+            Job::Bury { corpse, step } => {
+                let step =
+                    self.dispose(worker, corpse, Disposal::Bury, step)?;
+                if step == CraftStep::Work(0) {
+                    self.finish_burial(worker, corpse);
+                    return None;
+                }
+                Some(Job::Bury { corpse, step })
+            }
+            Job::Butcher { corpse, step } => {
+                let step =
+                    self.dispose(worker, corpse, Disposal::Butcher, step)?;
+                if step == CraftStep::Work(0) {
+                    self.finish_butchering(worker, corpse);
+                    return None;
+                }
+                Some(Job::Butcher { corpse, step })
+            }
+            Job::Cremate { corpse, step } => {
+                let step =
+                    self.dispose(worker, corpse, Disposal::Cremate, step)?;
+                if step == CraftStep::Work(0) {
+                    self.finish_cremation(worker, corpse);
+                    return None;
+                }
+                Some(Job::Cremate { corpse, step })
+            }
+            Job::Construct { blueprint, step } => {
+                let structure = self.blueprints.get(blueprint)?.structure;
+                let blueprint_loc = self.location_of(blueprint)?;
+                let step = match step {
+                    CraftStep::Fetch(target) => {
+                        self.fetch(worker, structure.cost(), target)?
+                    }
+                    CraftStep::Deliver => {
+                        if self.walk_next_to(worker, blueprint_loc) {
+                            CraftStep::Work(
+                                structure.work_minutes() * MILLIS_PER_MINUTE,
+                            )
+                        } else {
+                            CraftStep::Deliver
+                        }
+                    }
+                    CraftStep::Work(remaining) => {
+                        let remaining = match remaining {
+                            0 => 0,
+                            _ => remaining.saturating_sub(
+                                self.practice(worker, SkillKind::Construction),
+                            ),
+                        };
+                        // A wall is not finished on top of anyone, the
+                        // worker waits for them to step away.
+                        let is_blocked = structure == Structure::Wall
+                            && self.is_anyone_at(blueprint_loc);
+                        if remaining > 0 || is_blocked {
+                            CraftStep::Work(remaining)
+                        } else {
+                            self.finish_construct(worker, blueprint);
+                            return None;
+                        }
+                    }
+                };
+                Some(Job::Construct { blueprint, step })
+            }
+            Job::Craft {
+                workbench,
+                bill,
+                recipe,
+                step,
+            } => {
+                let is_valid = self
+                    .workbenches
+                    .get(workbench)
+                    .and_then(|w| w.bills.get(bill))
+                    .is_some_and(|b| b.recipe == recipe);
+                if !is_valid {
+                    return None;
+                }
+                let recipe_def: Recipe =
+                    self.recipe_book.get(recipe).cloned()?;
+                let workbench_loc = self.location_of(workbench)?;
+                let step = match step {
+                    CraftStep::Fetch(target) => {
+                        self.fetch(worker, &recipe_def.ingredients, target)?
+                    }
+                    CraftStep::Deliver => {
+                        if self.walk_to(worker, workbench_loc) {
+                            CraftStep::Work(
+                                recipe_def.work_minutes * MILLIS_PER_MINUTE,
+                            )
+                        } else {
+                            CraftStep::Deliver
+                        }
+                    }
+                    CraftStep::Work(remaining) => {
+                        let skill = self
+                            .workbenches
+                            .get(workbench)
+                            .map_or(SkillKind::Crafting, |w| w.kind.skill());
+                        let work = self.practice(worker, skill);
+                        if remaining > work {
+                            CraftStep::Work(remaining - work)
+                        } else {
+                            self.finish_craft(
+                                worker,
+                                workbench,
+                                bill,
+                                &recipe_def,
+                            );
+                            return None;
+                        }
+                    }
+                };
+                Some(Job::Craft {
+                    workbench,
+                    bill,
+                    recipe,
+                    step,
+                })
+            }
+            Job::Eat {
+                food,
+                remaining: None,
+            } => {
+                let food_loc = self.location_of(food)?;
+                if self.walk_to(worker, food_loc) {
+                    self.pick_up(worker, food, 1);
+                    Some(Job::Eat {
+                        food,
+                        remaining: Some(EATING_MINUTES * MILLIS_PER_MINUTE),
+                    })
+                } else {
+                    Some(job)
+                }
+            }
+            Job::Eat {
+                food,
+                remaining: Some(remaining),
+            } => {
+                let dt = self.time.game_dt_millis();
+                if remaining > dt {
+                    Some(Job::Eat {
+                        food,
+                        remaining: Some(remaining - dt),
+                    })
+                } else {
+                    self.finish_eating(worker);
+                    None
+                }
+            }
+            Job::Equip { item } => {
+                if self.walk_to(worker, self.location_of(item)?) {
+                    self.equip(worker, item);
+                    None
+                } else {
+                    Some(job)
+                }
+            }
+            Job::Feed { animal, step } => {
+                let is_hungry = self
+                    .animals
+                    .get(animal)
+                    .is_some_and(|a| a.is_tame && a.is_hungry());
+                if !is_hungry {
+                    return None;
+                }
+                let animal_loc = self.location_of(animal)?;
+                let step = match step {
+                    CraftStep::Fetch(target) => self.fetch(
+                        worker,
+                        &[(ANIMAL_FEED, FEED_QUANTITY)],
+                        target,
+                    )?,
+                    step => self.handle_animal(
+                        worker,
+                        animal_loc,
+                        step,
+                        FEEDING_MINUTES,
+                    ),
+                };
+                if step == CraftStep::Work(0) {
+                    self.finish_feeding(worker, animal);
+                    return None;
+                }
+                Some(Job::Feed { animal, step })
+            }
+            Job::Harvest { field, step } => {
+                if !self.fields.get(field).is_some_and(|f| f.is_ripe()) {
+                    return None;
+                }
+                let step =
+                    self.work_field(worker, field, step, HARVESTING_MINUTES)?;
+                if step == CraftStep::Work(0) {
+                    self.finish_harvest(worker, field);
+                    return None;
+                }
+                Some(Job::Harvest { field, step })
+            }
+            Job::Haul {
+                item,
+                to,
+                picked_up: false,
+            } => {
+                let item_loc = self.location_of(item)?;
+                if self.walk_to(worker, item_loc) {
+                    self.pick_up(worker, item, u16::MAX);
+                    Some(Job::Haul {
+                        item,
+                        to,
+                        picked_up: true,
+                    })
+                } else {
+                    Some(job)
+                }
+            }
+            Job::Haul {
+                picked_up: true,
+                to,
+                ..
+            } => {
+                if self.walk_to(worker, to) {
+                    None
+                } else {
+                    Some(job)
+                }
+            }
+            Job::Relax => match self.activity_of(worker) {
+                Activity::Recreation => Some(job),
+                _ => {
+                    self.add_thought(worker, ThoughtKind::Relaxed);
+                    None
+                }
+            },
+            Job::Rescue {
+                patient,
+                bed,
+                carrying: false,
+            } => {
+                if !self.is_downed(patient) {
+                    return None;
+                }
+                if self.walk_to(worker, self.location_of(patient)?) {
+                    Some(Job::Rescue {
+                        patient,
+                        bed,
+                        carrying: true,
+                    })
+                } else {
+                    Some(job)
+                }
+            }
+            Job::Rescue {
+                patient,
+                bed,
+                carrying: true,
+            } => {
+                let arrived = self.walk_to(worker, self.location_of(bed)?);
+                let location = self.location_of(worker)?;
+                self.locations.get_mut(patient)?.location = location;
+                if arrived {
+                    self.game_log.push(LogEntry::new(
+                        format!(
+                            "{} carried {} to a bed",
+                            self.name_of(worker),
+                            self.name_of(patient)
+                        )
+                        .as_str(),
+                    ));
+                    None
+                } else {
+                    Some(job)
+                }
+            }
+            Job::Sleep => {
+                let days =
+                    self.time.game_dt_millis() as f32 / MILLIS_PER_DAY as f32;
+                let rest = self.needs.get_mut(worker).map_or(1.0, |n| {
+                    n.rest = (n.rest + days * REST_RECOVERY_PER_DAY).min(1.0);
+                    n.rest
+                });
+                match self.activity_of(worker) {
+                    Activity::Sleep => Some(job),
+                    Activity::Anything if rest < 1.0 => Some(job),
+                    _ => {
+                        let is_indoors = self
+                            .location_of(worker)
+                            .is_some_and(|l| self.rooms.is_indoors(l));
+                        if !is_indoors {
+                            self.add_thought(
+                                worker,
+                                ThoughtKind::SleptOutside,
+                            );
+                        }
+                        None
+                    }
+                }
+            }
+            Job::Slaughter { animal, step } => {
+                let is_designated =
+                    self.animals.get(animal).is_some_and(|a| {
+                        a.designation == Some(Designation::Slaughter)
+                    });
+                if !is_designated {
+                    return None;
+                }
+                let animal_loc = self.location_of(animal)?;
+                let step = self.handle_animal(
+                    worker,
+                    animal_loc,
+                    step,
+                    SLAUGHTER_MINUTES,
+                );
+                if step == CraftStep::Work(0) {
+                    self.finish_slaughter(worker, animal);
+                    return None;
+                }
+                Some(Job::Slaughter { animal, step })
+            }
+            Job::Sow { field, step } => {
+                let is_bare =
+                    self.fields.get(field).is_some_and(|f| f.growth.is_none());
+                if !is_bare {
+                    return None;
+                }
+                let step =
+                    self.work_field(worker, field, step, SOWING_MINUTES)?;
+                if step == CraftStep::Work(0) {
+                    self.finish_sowing(worker, field);
+                    return None;
+                }
+                Some(Job::Sow { field, step })
+            }
+            // The worker is no longer drafted or has no target.
+            Job::Drafted | Job::Fight { .. } => None,
+            Job::Sulk => match self.mental_break_of(worker) {
+                Some(MentalBreakKind::Sulk) => Some(job),
+                _ => None,
+            },
+            Job::Tame { animal, step } => {
+                let is_designated =
+                    self.animals.get(animal).is_some_and(|a| {
+                        !a.is_tame && a.designation == Some(Designation::Tame)
+                    });
+                if !is_designated {
+                    return None;
+                }
+                let animal_loc = self.location_of(animal)?;
+                let step = self.handle_animal(
+                    worker,
+                    animal_loc,
+                    step,
+                    TAMING_MINUTES,
+                );
+                if step == CraftStep::Work(0) {
+                    self.finish_taming(worker, animal);
+                    return None;
+                }
+                Some(Job::Tame { animal, step })
+            }
+            Job::Tend { patient, step } => {
+                let needs_tending = self
+                    .healths
+                    .get(patient)
+                    .is_some_and(|h| h.injuries.needs_tending());
+                if !needs_tending {
+                    return None;
+                }
+                let patient_loc = self.location_of(patient)?;
+                let step = match step {
+                    CraftStep::Fetch(target) => self
+                        .fetch(worker, &[(ItemKind::Medicine, 1)], target)
+                        .unwrap_or(CraftStep::Deliver),
+                    CraftStep::Deliver => {
+                        if self.walk_next_to(worker, patient_loc) {
+                            CraftStep::Work(
+                                TENDING_MINUTES * MILLIS_PER_MINUTE,
+                            )
+                        } else {
+                            CraftStep::Deliver
+                        }
+                    }
+                    CraftStep::Work(_)
+                        if !self.walk_next_to(worker, patient_loc) =>
+                    {
+                        CraftStep::Deliver
+                    }
+                    CraftStep::Work(remaining) => {
+                        let dt = self.time.game_dt_millis();
+                        if remaining > dt {
+                            CraftStep::Work(remaining - dt)
+                        } else {
+                            self.finish_tending(worker, patient);
+                            return None;
+                        }
+                    }
+                };
+                Some(Job::Tend { patient, step })
+            }
+            Job::Repair { defense, remaining } => {
+                let missing = self
+                    .defenses
+                    .get(defense)
+                    .map(|d| d.max_hp() - d.hp)
+                    .filter(|missing| *missing > 0)?;
+                let defense_loc = self.location_of(defense)?;
+                let remaining = match remaining {
+                    None if self.walk_next_to(worker, defense_loc) => Some(
+                        u32::from(missing)
+                            * REPAIR_MINUTES_PER_HP
+                            * MILLIS_PER_MINUTE,
+                    ),
+                    None => None,
+                    Some(remaining) => {
+                        let work =
+                            self.practice(worker, SkillKind::Construction);
+                        if remaining > work {
+                            Some(remaining - work)
+                        } else {
+                            self.finish_repair(worker, defense);
+                            return None;
+                        }
+                    }
+                };
+                Some(Job::Repair { defense, remaining })
+            }
+            Job::Research { bench, remaining } => {
+                self.research.current()?;
+                let bench_loc = self.location_of(bench)?;
+                let remaining = match remaining {
+                    None if self.walk_to(worker, bench_loc) => {
+                        Some(RESEARCH_SESSION_MINUTES * MILLIS_PER_MINUTE)
+                    }
+                    None => None,
+                    Some(remaining) => {
+                        let work = self.practice(worker, SkillKind::Research);
+                        self.finish_research(work);
+                        if remaining > work {
+                            Some(remaining - work)
+                        } else {
+                            return None;
+                        }
+                    }
+                };
+                Some(Job::Research { bench, remaining })
+            }
+            Job::Trade {
+                kind,
+                quantity,
+                step,
+            } => {
+                let camp = self.caravan.camp()?;
+                let step = match step {
+                    CraftStep::Fetch(target) => {
+                        self.fetch(worker, &[(kind, quantity)], target)?
+                    }
+                    CraftStep::Deliver | CraftStep::Work(_) => {
+                        if self.walk_next_to(worker, camp) {
+                            self.finish_trade(worker, kind);
+                            return None;
+                        }
+                        CraftStep::Deliver
+                    }
+                };
+                Some(Job::Trade {
+                    kind,
+                    quantity,
+                    step,
+                })
+            }
+            Job::Wander { to } => {
+                if self.walk_to(worker, to) {
+                    None
+                } else {
+                    Some(job)
+                }
+            }
+        }
+    }
+
+    fn fetch(
+        &mut self,
+        worker: Entity,
+        materials: &[(ItemKind, u16)],
+        target: Option<Entity>,
+    ) -> Option<CraftStep> {
+        let missing = self.missing_materials(worker, materials);
+        if missing.is_empty() {
+            return Some(CraftStep::Deliver);
+        }
+        let is_needed =
+            |kind: ItemKind| missing.iter().any(|(k, _)| *k == kind);
+        let target = match target
+            .filter(|e| self.items.get(*e).is_some_and(|i| is_needed(i.kind)))
+        {
+            Some(e) => e,
+            None => {
+                let kinds: Vec<ItemKind> =
+                    missing.iter().map(|(k, _)| *k).collect();
+                self.find_stockpiled(&kinds, &self.reserved_items())?
+            }
+        };
+        if self.walk_to(worker, self.location_of(target)?) {
+            let kind = self.items.get(target)?.kind;
+            let (_, quantity) = missing.iter().find(|(k, _)| *k == kind)?;
+            self.pick_up(worker, target, *quantity);
+            Some(CraftStep::Fetch(None))
+        } else {
+            Some(CraftStep::Fetch(Some(target)))
+        }
+    }
+
+    /// Lay the corpse in a grave where it lies.
+    fn finish_burial(&mut self, worker: Entity, corpse: Entity) {
+        let (name, loc) = match self.remove_corpse(corpse) {
+            Some(removed) => removed,
+            None => return,
+        };
+        self.entities
+            .build_entity()
+            .with(components::Grave { name: name.clone() }, &mut self.graves)
+            .with(components::Location::new(loc), &mut self.locations)
+            .with(
+                components::Renderable::new(
+                    VisibleObject::Grave,
+                    GRAVE_Z_INDEX,
+                ),
+                &mut self.renderables,
+            )
+            .build();
+        self.game_log.push(LogEntry::new(
+            format!("{} buried {}", self.name_of(worker), name).as_str(),
+        ));
+    }
+
+    /// Cut up the corpse of an animal, the meat is as fresh as the corpse
+    /// was.
+    fn finish_butchering(&mut self, worker: Entity, corpse: Entity) {
+        let kind = match self.corpses.get(corpse).and_then(|c| c.animal) {
+            Some(kind) => kind,
+            None => return,
+        };
+        let freshness = self.items.get(corpse).and_then(|i| i.freshness);
+        let (name, loc) = match self.remove_corpse(corpse) {
+            Some(removed) => removed,
+            None => return,
+        };
+        let stats = kind.stats();
+        if stats.meat > 0 {
+            let meat = Item::new(ItemKind::RawMeat, stats.meat);
+            self.drop_item(
+                loc,
+                Item {
+                    freshness: freshness.or(meat.freshness),
+                    ..meat
+                },
+            );
+        }
+        if stats.leather > 0 {
+            self.drop_item(loc, Item::new(ItemKind::Leather, stats.leather));
+        }
+        self.game_log.push(LogEntry::new(
+            format!("{} butchered the {}", self.name_of(worker), name)
+                .as_str(),
+        ));
+    }
+
+    /// Burn the corpse with the carried firewood.
+    fn finish_cremation(&mut self, worker: Entity, corpse: Entity) {
+        let (name, _) = match self.remove_corpse(corpse) {
+            Some(removed) => removed,
+            None => return,
+        };
+        if let Some(w) = self.workers.get_mut(worker) {
+            let (kind, quantity) = CREMATION_FUEL;
+            remove_from_carried(&mut w.carrying, kind, quantity);
+        }
+        self.game_log.push(LogEntry::new(
+            format!("{} cremated {}", self.name_of(worker), name).as_str(),
+        ));
+    }
+
+    fn finish_craft(
+        &mut self,
+        worker: Entity,
+        workbench: Entity,
+        bill: usize,
+        recipe: &Recipe,
+    ) {
+        if let Some(w) = self.workers.get_mut(worker) {
+            for (kind, quantity) in recipe.ingredients.iter() {
+                remove_from_carried(&mut w.carrying, *kind, *quantity);
+            }
+        }
+        let skill = self
+            .workbenches
+            .get(workbench)
+            .map_or(SkillKind::Crafting, |w| w.kind.skill());
+        let products: Vec<(ItemKind, u16)> = recipe
+            .products
+            .iter()
+            .map(|(kind, quantity)| {
+                let bonus = self
+                    .workers
+                    .get(worker)
+                    .map_or(0, |w| w.skills.bonus_products(skill, *quantity));
+                (*kind, quantity + bonus)
+            })
+            .collect();
+        if let Some(loc) = self.location_of(workbench) {
+            for (kind, quantity) in products.iter() {
+                self.drop_item(loc, Item::new(*kind, *quantity));
+            }
+        }
+        if let Some(b) = self
+            .workbenches
+            .get_mut(workbench)
+            .and_then(|w| w.bills.get_mut(bill))
+        {
+            b.complete_once();
+        }
+        let name = self
+            .colonists
+            .get(worker)
+            .map_or("Someone", |c| c.name.as_str());
+        let (product, quantity) = products[0];
+        self.game_log.push(LogEntry::new(
+            format!("{} made {} x{}", name, product, quantity).as_str(),
+        ));
+    }
+
+    /// Turn the blueprint into the structure, using up the materials.
+    fn finish_construct(&mut self, worker: Entity, blueprint: Entity) {
+        let structure = match self.blueprints.remove(blueprint) {
+            Some(b) => b.structure,
+            None => return,
+        };
+        if let Some(w) = self.workers.get_mut(worker) {
+            for (kind, quantity) in structure.cost().iter() {
+                remove_from_carried(&mut w.carrying, *kind, *quantity);
+            }
+        }
+        let renderable = match structure {
+            Structure::Barricade
+            | Structure::Sandbags
+            | Structure::SpikeTrap => {
+                self.defenses
+                    .insert(blueprint, components::Defense::new(structure))
+                    .unwrap();
+                components::Renderable::new(
+                    VisibleObject::Defense(structure),
+                    DEFENSE_Z_INDEX,
+                )
+            }
+            Structure::Bed => {
+                self.beds.insert(blueprint, components::Bed).unwrap();
+                components::Renderable::new(VisibleObject::Bed, BED_Z_INDEX)
+            }
+            Structure::Monument => {
+                self.monuments
+                    .insert(blueprint, components::Monument)
+                    .unwrap();
+                components::Renderable::new(
+                    VisibleObject::Monument,
+                    MONUMENT_Z_INDEX,
+                )
+            }
+            Structure::Wall => {
+                self.walls.insert(blueprint, components::Wall).unwrap();
+                components::Renderable::new(VisibleObject::Wall, WALL_Z_INDEX)
+            }
+        };
+        self.renderables.insert(blueprint, renderable).unwrap();
+        let name = self
+            .colonists
+            .get(worker)
+            .map_or("Someone", |c| c.name.as_str());
+        self.game_log.push(LogEntry::new(
+            format!("{} built a {}", name, structure).as_str(),
+        ));
+    }
+
+    /// Gather the crop, leaving the field bare to be sown again.
+    fn finish_harvest(&mut self, worker: Entity, field: Entity) {
+        let crop = match self.fields.get_mut(field) {
+            Some(f) => {
+                f.growth = None;
+                f.crop
+            }
+            None => return,
+        };
+        let harvest_yield = crop.crop().map_or(0, |c| c.harvest_yield);
+        let quantity = harvest_yield
+            + self.workers.get(worker).map_or(0, |w| {
+                w.skills.bonus_products(SkillKind::Farming, harvest_yield)
+            });
+        if let Some(loc) = self.location_of(field) {
+            self.drop_item(loc, Item::new(crop, quantity));
+        }
+        self.renderables
+            .insert(
+                field,
+                components::Renderable::new(
+                    VisibleObject::Field,
+                    FIELD_Z_INDEX,
+                ),
+            )
+            .unwrap();
+        self.game_log.push(LogEntry::new(
+            format!(
+                "{} harvested {} x{}",
+                self.name_of(worker),
+                crop,
+                quantity
+            )
+            .as_str(),
+        ));
+    }
+
+    fn finish_repair(&mut self, worker: Entity, defense: Entity) {
+        let structure = match self.defenses.get_mut(defense) {
+            Some(d) => {
+                d.hp = d.max_hp();
+                d.structure
+            }
+            None => return,
+        };
+        self.game_log.push(LogEntry::new(
+            format!(
+                "{} repaired a {}",
+                self.name_of(worker),
+                structure.to_string().to_lowercase()
+            )
+            .as_str(),
+        ));
+    }
+
+    /// Spend the research points made with `work` milliseconds of skilled
+    /// work, a point per minute.
+    fn finish_research(&mut self, work: u32) {
+        let points = work as f32 / MILLIS_PER_MINUTE as f32;
+        if let Some(project) = self.research.add_points(points) {
+            self.game_log.push(LogEntry::alert(
+                format!("Research completed: {}", project.name).as_str(),
+            ));
+        }
+    }
+
+    fn finish_trade(&mut self, worker: Entity, kind: ItemKind) {
+        let quantity = match self.workers.get_mut(worker) {
+            Some(w) => {
+                let quantity = w
+                    .carrying
+                    .iter()
+                    .filter(|i| i.kind == kind)
+                    .map(|i| i.quantity)
+                    .sum();
+                remove_from_carried(&mut w.carrying, kind, quantity);
+                quantity
+            }
+            None => return,
+        };
+        self.caravan.deliver(kind, quantity);
+        self.game_log.push(LogEntry::new(
+            format!(
+                "{} delivered {} {} to the caravan.",
+                self.name_of(worker),
+                quantity,
+                kind
+            )
+            .as_str(),
+        ));
+    }
+
+    fn finish_eating(&mut self, worker: Entity) {
+        let food = self.workers.get_mut(worker).and_then(|w| {
+            let (kind, food) = w
+                .carrying
+                .iter()
+                .find_map(|i| i.kind.food().map(|food| (i.kind, food)))?;
+            remove_from_carried(&mut w.carrying, kind, 1);
+            Some(food)
+        });
+        let food = match food {
+            Some(food) => food,
+            None => return,
+        };
+        if let Some(needs) = self.needs.get_mut(worker) {
+            needs.food = (needs.food + food.nutrition).min(1.0);
+        }
+        self.add_thought(worker, ThoughtKind::AteMeal(food.quality));
+        let name = self
+            .colonists
+            .get(worker)
+            .map_or("Someone", |c| c.name.as_str());
+        self.game_log.push(LogEntry::new(
+            format!("{} ate {}", name, food.quality).as_str(),
+        ));
+    }
+
+    /// Give the carried feed to the animal.
+    fn finish_feeding(&mut self, worker: Entity, animal: Entity) {
+        let fed = match self.workers.get_mut(worker) {
+            Some(w) => {
+                let fed: u16 = w
+                    .carrying
+                    .iter()
+                    .filter(|i| i.kind == ANIMAL_FEED)
+                    .map(|i| i.quantity)
+                    .sum::<u16>()
+                    .min(FEED_QUANTITY);
+                remove_from_carried(&mut w.carrying, ANIMAL_FEED, fed);
+                fed
+            }
+            None => return,
+        };
+        let kind = match self.animals.get_mut(animal) {
+            Some(a) => {
+                a.food = (a.food + f32::from(fed) * FEED_NUTRITION).min(1.0);
+                a.kind
+            }
+            None => return,
+        };
+        self.game_log.push(LogEntry::new(
+            format!("{} fed the {}", self.name_of(worker), kind).as_str(),
+        ));
+    }
+
+    /// Butcher the animal where it stands.
+    fn finish_sowing(&mut self, worker: Entity, field: Entity) {
+        let crop = match self.fields.get_mut(field) {
+            Some(f) => {
+                f.growth = Some(0.0);
+                f.crop
+            }
+            None => return,
+        };
+        self.renderables
+            .insert(
+                field,
+                components::Renderable::new(
+                    VisibleObject::Crop(crop),
+                    FIELD_Z_INDEX,
+                ),
+            )
+            .unwrap();
+        self.game_log.push(LogEntry::new(
+            format!("{} sowed {}", self.name_of(worker), crop).as_str(),
+        ));
+    }
+
+    fn finish_slaughter(&mut self, worker: Entity, animal: Entity) {
+        let (kind, loc) =
+            match (self.animals.remove(animal), self.locations.remove(animal))
+            {
+                (Some(a), Some(l)) => (a.kind, l.location),
+                _ => return,
+            };
+        self.combatants.remove(animal);
+        self.healths.remove(animal);
+        self.movements.remove(animal);
+        self.renderables.remove(animal);
+        self.entities.delete(animal).unwrap();
+        let stats = kind.stats();
+        for (item, quantity) in [
+            (ItemKind::RawMeat, stats.meat),
+            (ItemKind::Leather, stats.leather),
+        ]
+        .iter()
+        {
+            if *quantity > 0 {
+                self.drop_item(loc, Item::new(*item, *quantity));
+            }
+        }
+        self.game_log.push(LogEntry::new(
+            format!("{} slaughtered the {}", self.name_of(worker), kind)
+                .as_str(),
+        ));
+    }
+
+    /// The animal is tamed with a chance depending on its temperament and
+    /// the handler's skill, otherwise it stays designated for another try.
+    fn finish_taming(&mut self, worker: Entity, animal: Entity) {
+        let level = self
+            .workers
+            .get(worker)
+            .map_or(0, |w| w.skills.level(SkillKind::Animals));
+        let kind = match self.animals.get(animal) {
+            Some(a) => a.kind,
+            None => return,
+        };
+        let chance = kind.stats().temperament.tame_chance(level);
+        let message = if self.random.chance(chance) {
+            if let Some(a) = self.animals.get_mut(animal) {
+                a.is_tame = true;
+                a.designation = None;
+                a.food = 1.0;
+            }
+            format!("{} tamed the {}", self.name_of(worker), kind)
+        } else if kind.stats().temperament == Temperament::Aggressive {
+            if let Some(c) = self.combatants.get_mut(animal) {
+                c.target = Some(worker);
+            }
+            format!(
+                "{} failed to tame the {}, it attacks!",
+                self.name_of(worker),
+                kind
+            )
+        } else {
+            format!("{} failed to tame the {}", self.name_of(worker), kind)
+        };
+        self.game_log.push(LogEntry::new(message.as_str()));
+    }
+
+    /// Use medicine if the doctor carries any, the patient's injuries may
+    /// get infected.
+    fn finish_tending(&mut self, doctor: Entity, patient: Entity) {
+        let treatment = match self.workers.get_mut(doctor) {
+            Some(w)
+                if w.carrying.iter().any(|i| i.kind == ItemKind::Medicine) =>
+            {
+                remove_from_carried(&mut w.carrying, ItemKind::Medicine, 1);
+                Treatment::Medicine
+            }
+            _ => Treatment::Bandaged,
+        };
+        let random = &mut self.random;
+        let infected = self
+            .healths
+            .get_mut(patient)
+            .map_or(0, |h| h.injuries.tend(treatment, random));
+        let how = match treatment {
+            Treatment::Bandaged => "without medicine",
+            Treatment::Medicine => "with medicine",
+        };
+        self.game_log.push(LogEntry::new(
+            format!(
+                "{} tended {}'s wounds {}",
+                self.name_of(doctor),
+                self.name_of(patient),
+                how
+            )
+            .as_str(),
+        ));
+        if infected > 0 {
+            self.game_log.push(LogEntry::new(
+                format!("{}'s wounds got infected", self.name_of(patient))
+                    .as_str(),
+            ));
+        }
+    }
+
+    /// Take one item from the stack and put it on, replacing whatever was
+    /// in its slot.
+    fn equip(&mut self, worker: Entity, item: Entity) {
+        let taken = match self.take_from_stack(item, 1) {
+            Some(taken) => taken,
+            None => return,
+        };
+        let replaced = self.workers.get_mut(worker).and_then(|w| {
+            w.loadout.equip(Equipped {
+                kind: taken.kind,
+                durability: taken.durability.unwrap_or_default(),
+            })
+        });
+        if let (Some(replaced), Some(loc)) =
+            (replaced, self.location_of(worker))
+        {
+            self.drop_item(loc, equipped_item(replaced));
+        }
+        self.game_log.push(LogEntry::new(
+            format!("{} equipped {}", self.name_of(worker), taken.kind)
+                .as_str(),
+        ));
+    }
+
+    fn find_construct_job(&self) -> Option<Job> {
+        let claimed = self.claimed_blueprints();
+        let reserved = self.reserved_items();
+        (&self.entities, &self.blueprints)
+            .join()
+            .find(|(blueprint, b)| {
+                !claimed.contains(blueprint)
+                    && b.structure.cost().iter().all(|(kind, quantity)| {
+                        self.count_stockpiled(*kind, &reserved)
+                            >= u32::from(*quantity)
+                    })
+            })
+            .map(|(blueprint, _)| Job::Construct {
+                blueprint,
+                step: CraftStep::Fetch(None),
+            })
+    }
+
+    fn find_repair_job(&self) -> Option<Job> {
+        let claimed = self.claimed_defenses();
+        (&self.entities, &self.defenses)
+            .join()
+            .find(|(defense, d)| d.is_damaged() && !claimed.contains(defense))
+            .map(|(defense, _)| Job::Repair {
+                defense,
+                remaining: None,
+            })
+    }
+
+    fn find_craft_job(&self, kind: WorkbenchKind) -> Option<Job> {
+        let claimed = self.claimed_workbenches();
+        let reserved = self.reserved_items();
+        for (workbench, w) in (&self.entities, &self.workbenches).join() {
+            if w.kind != kind || claimed.contains(&workbench) {
+                continue;
+            }
+            for (idx, bill) in w.bills.iter().enumerate() {
+                let recipe = match self.recipe_book.get(bill.recipe) {
+                    Some(recipe) => recipe,
+                    None => continue,
+                };
+                if !bill.is_active(self.count_items(recipe.main_product())) {
+                    continue;
+                }
+                if recipe.ingredients.iter().all(|(kind, quantity)| {
+                    self.count_stockpiled(*kind, &reserved)
+                        >= u32::from(*quantity)
+                }) {
+                    return Some(Job::Craft {
+                        workbench,
+                        bill: idx,
+                        recipe: bill.recipe,
+                        step: CraftStep::Fetch(None),
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Carry a downed colonist to a free bed, or else tend someone's
+    /// injuries.
+    fn find_doctor_job(&self, doctor: Entity) -> Option<Job> {
+        let claimed = self.claimed_patients();
+        let patients: Vec<(Entity, bool, bool, Loc)> =
+            (&self.entities, &self.healths, &self.locations)
+                .join()
+                .filter(|(e, _, _)| *e != doctor && !claimed.contains(e))
+                .map(|(e, h, l)| {
+                    (e, h.is_downed, h.injuries.needs_tending(), l.location)
+                })
+                .collect();
+        let bed_tiles: BTreeSet<Loc> = (&self.beds, &self.locations)
+            .join()
+            .map(|(_, l)| l.location)
+            .collect();
+        let rescue = patients
+            .iter()
+            .find(|(_, is_downed, _, loc)| {
+                *is_downed && !bed_tiles.contains(loc)
+            })
+            .and_then(|(patient, ..)| {
+                self.find_free_bed().map(|bed| Job::Rescue {
+                    patient: *patient,
+                    bed,
+                    carrying: false,
+                })
+            });
+        if rescue.is_some() {
+            return rescue;
+        }
+        let has_medicine = self
+            .count_stockpiled(ItemKind::Medicine, &self.reserved_items())
+            > 0;
+        patients
+            .iter()
+            .find(|(_, _, needs_tending, _)| *needs_tending)
+            .map(|(patient, ..)| Job::Tend {
+                patient: *patient,
+                step: if has_medicine {
+                    CraftStep::Fetch(None)
+                } else {
+                    CraftStep::Deliver
+                },
+            })
+    }
+
+    /// Harvest a ripe field, or sow a bare one while crops grow, nobody
+    /// else working on the field.
+    fn find_grow_job(&self) -> Option<Job> {
+        let claimed = self.claimed_fields();
+        let fields: Vec<(Entity, &components::Field)> =
+            (&self.entities, &self.fields)
+                .join()
+                .filter(|(e, _)| !claimed.contains(e))
+                .collect();
+        if let Some((field, _)) = fields.iter().find(|(_, f)| f.is_ripe()) {
+            return Some(Job::Harvest {
+                field: *field,
+                step: CraftStep::Deliver,
+            });
+        }
+        if !is_growing_season(self.time.season()) {
+            return None;
+        }
+        fields
+            .iter()
+            .find(|(_, f)| f.growth.is_none())
+            .map(|(field, _)| Job::Sow {
+                field: *field,
+                step: CraftStep::Deliver,
+            })
+    }
+
+    /// Slaughter, tame or feed an animal nobody else is handling.
+    fn find_handle_job(&self) -> Option<Job> {
+        let claimed = self.claimed_animals();
+        let animals: Vec<(Entity, &components::Animal)> =
+            (&self.entities, &self.animals)
+                .join()
+                .filter(|(e, _)| !claimed.contains(e))
+                .collect();
+        let designated = |designation: Designation| {
+            animals
+                .iter()
+                .find(|(_, a)| a.designation == Some(designation))
+                .map(|(e, _)| *e)
+        };
+        if let Some(animal) = designated(Designation::Slaughter) {
+            return Some(Job::Slaughter {
+                animal,
+                step: CraftStep::Deliver,
+            });
+        }
+        if let Some(animal) = designated(Designation::Tame) {
+            return Some(Job::Tame {
+                animal,
+                step: CraftStep::Deliver,
+            });
+        }
+        let has_feed = self
+            .count_stockpiled(ANIMAL_FEED, &self.reserved_items())
+            >= u32::from(FEED_QUANTITY);
+        animals
+            .iter()
+            .find(|(_, a)| has_feed && a.is_tame && a.is_hungry())
+            .map(|(animal, _)| Job::Feed {
+                animal: *animal,
+                step: CraftStep::Fetch(None),
+            })
+    }
+
+    /// A bed nobody lies in, and nobody is being carried to.
+    fn find_free_bed(&self) -> Option<Entity> {
+        let occupied: BTreeSet<Loc> = (&self.healths, &self.locations)
+            .join()
+            .map(|(_, l)| l.location)
+            .collect();
+        let claimed: BTreeSet<Entity> = self
+            .workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Rescue { bed, .. }) => Some(bed),
+                _ => None,
+            })
+            .collect();
+        (&self.entities, &self.beds, &self.locations)
+            .join()
+            .find(|(e, _, l)| {
+                !claimed.contains(e) && !occupied.contains(&l.location)
+            })
+            .map(|(e, _, _)| e)
+    }
+
+    /// Find a stockpiled item to fill an empty slot with.
+    fn find_equip_job(&self, worker: Entity) -> Option<Job> {
+        let loadout = &self.workers.get(worker)?.loadout;
+        let reserved = self.reserved_items();
+        let stockpile_tiles = self.stockpile_tiles();
+        let available: Vec<(Entity, ItemKind)> =
+            (&self.entities, &self.items, &self.locations)
+                .join()
+                .filter(|(e, item, loc)| {
+                    item.kind.equipment().is_some()
+                        && !reserved.contains(e)
+                        && stockpile_tiles.contains(&loc.location)
+                })
+                .map(|(e, item, _)| (e, item.kind))
+                .collect();
+        let kinds: Vec<ItemKind> = available.iter().map(|(_, k)| *k).collect();
+        Slot::ALL
+            .iter()
+            .filter_map(|slot| loadout.looking_for(*slot, &kinds))
+            .find_map(|kind| available.iter().find(|(_, k)| *k == kind))
+            .map(|(item, _)| Job::Equip { item: *item })
+    }
+
+    /// Find the best food to eat, closer food is preferred when the quality
+    /// is the same.
+    fn find_food(&self, near: Loc) -> Option<Job> {
+        let reserved = self.reserved_items();
+        (&self.entities, &self.items, &self.locations)
+            .join()
+            .filter(|(e, _, _)| !reserved.contains(e))
+            .filter_map(|(e, item, loc)| {
+                item.kind.food().map(|food| {
+                    (e, food.quality, near.distance_to(loc.location))
+                })
+            })
+            .max_by_key(|(_, quality, distance)| {
+                (*quality, Reverse(*distance))
+            })
+            .map(|(food, _, _)| Job::Eat {
+                food,
+                remaining: None,
+            })
+    }
+
+    /// Bury, butcher or cremate a corpse nobody else is disposing of,
+    /// cremation needs firewood in the stockpiles.
+    fn find_dispose_job(&self) -> Option<Job> {
+        let claimed = self.claimed_corpses();
+        let (kind, quantity) = CREMATION_FUEL;
+        let has_fuel = self.count_stockpiled(kind, &self.reserved_items())
+            >= u32::from(quantity);
+        (&self.entities, &self.corpses)
+            .join()
+            .filter(|(e, _)| !claimed.contains(e))
+            .find_map(|(corpse, c)| match c.disposal? {
+                Disposal::Bury => Some(Job::Bury {
+                    corpse,
+                    step: CraftStep::Deliver,
+                }),
+                Disposal::Butcher => Some(Job::Butcher {
+                    corpse,
+                    step: CraftStep::Deliver,
+                }),
+                Disposal::Cremate if has_fuel => Some(Job::Cremate {
+                    corpse,
+                    step: CraftStep::Fetch(None),
+                }),
+                Disposal::Cremate => None,
+            })
+    }
+
+    fn find_haul_job(&self, near: Loc) -> Option<Job> {
+        let stockpile_tiles = self.stockpile_tiles();
+        let reserved = self.reserved_items();
+        let destinations = self.haul_destinations();
+        let (item, kind, _) = (&self.entities, &self.items, &self.locations)
+            .join()
+            .filter(|(e, item, loc)| {
+                !reserved.contains(e)
+                    && item.kind != ItemKind::Corpse
+                    && !stockpile_tiles.contains(&loc.location)
+            })
+            .map(|(e, item, loc)| (e, item.kind, loc.location))
+            .min_by_key(|(_, _, loc)| near.distance_to(*loc))?;
+        let to = stockpile_tiles
+            .into_iter()
+            .filter(|loc| !destinations.contains(loc))
+            .find(|loc| {
+                (&self.items, &self.locations)
+                    .join()
+                    .filter(|(_, l)| l.location == *loc)
+                    .all(|(i, _)| i.kind == kind)
+            })?;
+        Some(Job::Haul {
+            item,
+            to,
+            picked_up: false,
+        })
+    }
+
+    /// Research at a free research bench while there are queued projects.
+    fn find_research_job(&self) -> Option<Job> {
+        self.research.current()?;
+        let claimed = self.claimed_workbenches();
+        (&self.entities, &self.workbenches)
+            .join()
+            .find(|(bench, w)| {
+                w.kind == WorkbenchKind::Research && !claimed.contains(bench)
+            })
+            .map(|(bench, _)| Job::Research {
+                bench,
+                remaining: None,
+            })
+    }
+
+    /// Deliver goods owed to a camped caravan, one kind per worker.
+    fn find_trade_job(&self) -> Option<Job> {
+        self.caravan.camp()?;
+        let claimed: BTreeSet<ItemKind> = self
+            .workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Trade { kind, .. }) => Some(kind),
+                _ => None,
+            })
+            .collect();
+        let reserved = self.reserved_items();
+        self.caravan
+            .owed()
+            .iter()
+            .filter(|(kind, owed)| **owed > 0 && !claimed.contains(kind))
+            .find_map(|(kind, owed)| {
+                let stockpiled = self.count_stockpiled(*kind, &reserved);
+                let quantity =
+                    u16::try_from(stockpiled).unwrap_or(u16::MAX).min(*owed);
+                Some(Job::Trade {
+                    kind: *kind,
+                    quantity,
+                    step: CraftStep::Fetch(None),
+                })
+                .filter(|_| quantity > 0)
+            })
+    }
+
+    fn find_stockpiled(
+        &self,
+        kinds: &[ItemKind],
+        reserved: &BTreeSet<Entity>,
+    ) -> Option<Entity> {
+        let stockpile_tiles = self.stockpile_tiles();
+        (&self.entities, &self.items, &self.locations)
+            .join()
+            .find(|(e, item, loc)| {
+                kinds.contains(&item.kind)
+                    && !reserved.contains(e)
+                    && stockpile_tiles.contains(&loc.location)
+            })
+            .map(|(e, _, _)| e)
+    }
+
+    fn add_thought(&mut self, worker: Entity, thought: ThoughtKind) {
+        if let Some(mood) = self.moods.get_mut(worker) {
+            mood.thoughts.add(thought);
+        }
+    }
+
+    /// Scheduled activity of the worker for the current hour.
+    fn activity_of(&self, worker: Entity) -> Activity {
+        self.workers.get(worker).map_or(Activity::Anything, |w| {
+            w.schedule.get(self.time.game_time_hours())
+        })
+    }
+
+    fn claimed_animals(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Feed { animal, .. })
+                | Some(Job::Slaughter { animal, .. })
+                | Some(Job::Tame { animal, .. }) => Some(animal),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claimed_blueprints(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Construct { blueprint, .. }) => Some(blueprint),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claimed_corpses(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Bury { corpse, .. })
+                | Some(Job::Butcher { corpse, .. })
+                | Some(Job::Cremate { corpse, .. }) => Some(corpse),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claimed_defenses(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Repair { defense, .. }) => Some(defense),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claimed_fields(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Harvest { field, .. })
+                | Some(Job::Sow { field, .. }) => Some(field),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claimed_patients(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Rescue { patient, .. })
+                | Some(Job::Tend { patient, .. }) => Some(patient),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn claimed_workbenches(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Craft { workbench, .. })
+                | Some(Job::Research {
+                    bench: workbench, ..
+                }) => Some(workbench),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn count_items(&self, kind: ItemKind) -> u32 {
+        self.items
+            .join()
+            .filter(|item| item.kind == kind)
+            .map(|item| u32::from(item.quantity))
+            .sum()
+    }
+
+    fn count_stockpiled(
+        &self,
+        kind: ItemKind,
+        reserved: &BTreeSet<Entity>,
+    ) -> u32 {
+        items::count_stockpiled(
+            &self.entities,
+            &self.items,
+            &self.locations,
+            &self.stockpiles,
+            kind,
+            reserved,
+        )
+    }
+
+    /// Fetch the firewood for a cremation, then walk up to the corpse and
+    /// work on it, the work is done when the returned step is `Work(0)`.
+    /// `None` if the corpse is no longer meant to be disposed of that way.
+    fn dispose(
+        &mut self,
+        worker: Entity,
+        corpse: Entity,
+        disposal: Disposal,
+        step: CraftStep,
+    ) -> Option<CraftStep> {
+        let is_designated = self
+            .corpses
+            .get(corpse)
+            .is_some_and(|c| c.disposal == Some(disposal));
+        if !is_designated {
+            return None;
+        }
+        let corpse_loc = self.location_of(corpse)?;
+        let minutes = match disposal {
+            Disposal::Bury => BURIAL_MINUTES,
+            Disposal::Butcher => BUTCHERING_MINUTES,
+            Disposal::Cremate => CREMATION_MINUTES,
+        };
+        if let CraftStep::Fetch(target) = step {
+            return self.fetch(worker, &[CREMATION_FUEL], target);
+        }
+        let is_next_to = self.walk_next_to(worker, corpse_loc);
+        Some(match step {
+            CraftStep::Work(remaining) if is_next_to => CraftStep::Work(
+                remaining.saturating_sub(self.time.game_dt_millis()),
+            ),
+            _ if is_next_to => CraftStep::Work(minutes * MILLIS_PER_MINUTE),
+            _ => CraftStep::Deliver,
+        })
+    }
+
+    fn drop_carried(&mut self, worker: Entity) {
+        let carrying = match self.workers.get_mut(worker) {
+            Some(w) => w.carrying.split_off(0),
+            None => return,
+        };
+        if let Some(loc) = self.location_of(worker) {
+            for item in carrying {
+                self.drop_item(loc, item);
+            }
+        }
+        if let Some(movement) = self.movements.get_mut(worker) {
+            movement.destination = None;
+        }
+    }
+
+    /// Take off the items the worker should not wear any more.
+    fn drop_unwanted_equipment(&mut self, worker: Entity) {
+        let location = match self.location_of(worker) {
+            Some(location) => location,
+            None => return,
+        };
+        let dropped: Vec<Equipped> = match self.workers.get_mut(worker) {
+            Some(w) => w
+                .loadout
+                .unwanted()
+                .into_iter()
+                .filter_map(|slot| w.loadout.unequip(slot))
+                .collect(),
+            None => return,
+        };
+        for item in dropped {
+            self.drop_item(location, equipped_item(item));
+        }
+    }
+
+    fn drop_item(&mut self, loc: Loc, item: Item) {
+        items::drop_item(
+            &self.entities,
+            &mut self.items,
+            &mut self.locations,
+            &mut self.renderables,
+            loc,
+            item,
+        );
+    }
+
+    /// Walk onto the field and work on it, the work is done when the
+    /// returned step is `Work(0)`.  `None` if the field is gone.
+    fn work_field(
+        &mut self,
+        worker: Entity,
+        field: Entity,
+        step: CraftStep,
+        minutes: u32,
+    ) -> Option<CraftStep> {
+        let field_loc = self.location_of(field)?;
+        let is_there = self.walk_to(worker, field_loc);
+        Some(match step {
+            CraftStep::Work(remaining) if is_there => CraftStep::Work(
+                remaining
+                    .saturating_sub(self.practice(worker, SkillKind::Farming)),
+            ),
+            _ if is_there => CraftStep::Work(minutes * MILLIS_PER_MINUTE),
+            _ => CraftStep::Deliver,
+        })
+    }
+
+    /// Walk up to the animal and work on it, the work is done when the
+    /// returned step is `Work(0)`.
+    fn handle_animal(
+        &mut self,
+        worker: Entity,
+        animal_loc: Loc,
+        step: CraftStep,
+        minutes: u32,
+    ) -> CraftStep {
+        let is_next_to = self.walk_next_to(worker, animal_loc);
+        match step {
+            CraftStep::Work(remaining) if is_next_to => CraftStep::Work(
+                remaining
+                    .saturating_sub(self.practice(worker, SkillKind::Animals)),
+            ),
+            _ if is_next_to => CraftStep::Work(minutes * MILLIS_PER_MINUTE),
+            _ => CraftStep::Deliver,
+        }
+    }
+
+    fn haul_destinations(&self) -> BTreeSet<Loc> {
+        self.workers
+            .join()
+            .filter_map(|w| match w.job {
+                Some(Job::Haul { to, .. }) => Some(to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn hurt_at_work(&mut self, worker: Entity) {
+        let part = *self.random.pick(BodyPart::ALL).unwrap();
+        let kind = *self.random.pick(InjuryKind::ALL).unwrap();
+        let severity = self
+            .random
+            .range(ACCIDENT_MIN_SEVERITY, ACCIDENT_MAX_SEVERITY);
+        let armor = self
+            .workers
+            .get(worker)
+            .map_or(0.0, |w| w.loadout.armor(part));
+        if let Some(health) = self.healths.get_mut(worker) {
+            health
+                .injuries
+                .add(part, kind, severity as f32 * (1.0 - armor));
+            self.game_log.push(LogEntry::new(
+                format!(
+                    "{} got a {} on the {} while working",
+                    self.name_of(worker),
+                    kind,
+                    part
+                )
+                .as_str(),
+            ));
+        }
+    }
+
+    /// Whether anything with a [Movement](components::Movement) stands on
+    /// `location`.
+    fn is_anyone_at(&self, location: Loc) -> bool {
+        (&self.movements, &self.locations)
+            .join()
+            .any(|(_, l)| l.location == location)
+    }
+
+    fn is_downed(&self, entity: Entity) -> bool {
+        self.healths.get(entity).is_some_and(|h| h.is_downed)
+    }
+
+    fn location_of(&self, entity: Entity) -> Option<Loc> {
+        self.locations.get(entity).map(|l| l.location)
+    }
+
+    fn mental_break_of(&self, worker: Entity) -> Option<MentalBreakKind> {
+        self.moods
+            .get(worker)
+            .and_then(|m| m.mental_break)
+            .map(|b| b.kind)
+    }
+
+    fn missing_materials(
+        &self,
+        worker: Entity,
+        materials: &[(ItemKind, u16)],
+    ) -> Vec<(ItemKind, u16)> {
+        let carrying: &[Item] =
+            self.workers.get(worker).map_or(&[], |w| &w.carrying);
+        materials
+            .iter()
+            .filter_map(|(kind, quantity)| {
+                let carried: u16 = carrying
+                    .iter()
+                    .filter(|i| i.kind == *kind)
+                    .map(|i| i.quantity)
+                    .sum();
+                if carried < *quantity {
+                    Some((*kind, quantity - carried))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    fn name_of(&self, colonist: Entity) -> &str {
+        self.colonists
+            .get(colonist)
+            .map_or("Someone", |c| c.name.as_str())
+    }
+
+    /// Take up to `max_quantity` items from the stack and carry them.
+    fn pick_up(&mut self, worker: Entity, item: Entity, max_quantity: u16) {
+        let taken = match self.take_from_stack(item, max_quantity) {
+            Some(taken) => taken,
+            None => return,
+        };
+        if let Some(w) = self.workers.get_mut(worker) {
+            match w.carrying.iter_mut().find(|i| i.kind == taken.kind) {
+                Some(carried) => carried.merge(taken),
+                None => w.carrying.push(taken),
+            }
+        }
+    }
+
+    /// Do skilled work for a tick and gain experience, returns the amount of
+    /// work done in milliseconds.
+    fn practice(&mut self, worker: Entity, skill: SkillKind) -> u32 {
+        let dt = self.time.game_dt_millis();
+        let w = match self.workers.get_mut(worker) {
+            Some(w) => w,
+            None => return dt,
+        };
+        let tool_speed = w.loadout.work_speed(skill);
+        let work = (dt as f32 * w.skills.speed(skill) * tool_speed) as u32;
+        // Tools wear out when they are used.
+        let broken_tool = if tool_speed > 1.0 {
+            w.loadout.wear(Slot::Hand, dt)
+        } else {
+            None
+        };
+        let xp = dt as f32 / MILLIS_PER_MINUTE as f32 * XP_PER_WORK_MINUTE;
+        if let Some(level) = w.skills.gain(skill, xp) {
+            let name = self
+                .colonists
+                .get(worker)
+                .map_or("Someone", |c| c.name.as_str());
+            self.game_log.push(LogEntry::new(
+                format!("{}'s {} skill is now {}", name, skill, level)
+                    .as_str(),
+            ));
+        }
+        if let Some(tool) = broken_tool {
+            self.game_log.push(LogEntry::new(
+                format!("{}'s {} broke", self.name_of(worker), tool).as_str(),
+            ));
+        }
+        let accident_chance = ACCIDENT_CHANCE_PER_WORK_HOUR * f64::from(dt)
+            / f64::from(MILLIS_PER_HOUR);
+        if self.random.chance(accident_chance) {
+            self.hurt_at_work(worker);
+        }
+        work
+    }
+
+    fn reserved_items(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| w.job.as_ref()?.reserved_item())
+            .collect()
+    }
+
+    /// Remove the corpse from the map, returning its name and where it lay.
+    fn remove_corpse(&mut self, corpse: Entity) -> Option<(String, Loc)> {
+        let removed =
+            match (self.corpses.remove(corpse), self.locations.remove(corpse))
+            {
+                (Some(c), Some(l)) => Some((c.name, l.location)),
+                _ => None,
+            };
+        self.items.remove(corpse);
+        self.renderables.remove(corpse);
+        self.entities.delete(corpse).unwrap();
+        removed
+    }
+
+    fn stockpile_tiles(&self) -> BTreeSet<Loc> {
+        items::stockpile_tiles(&self.locations, &self.stockpiles)
+    }
+
+    /// Workers having a mental break only do what the break makes them do.
+    fn suits_mental_state(&self, worker: Entity, job: &Job) -> bool {
+        match (self.mental_break_of(worker), job) {
+            (None, _) => true,
+            (Some(MentalBreakKind::FoodBinge), Job::Eat { .. }) => true,
+            (Some(MentalBreakKind::Sulk), Job::Sulk) => true,
+            (Some(MentalBreakKind::Wander), Job::Wander { .. }) => true,
+            (Some(_), _) => false,
+        }
+    }
+
+    /// Take up to `max_quantity` items from the stack, the stack is removed
+    /// if nothing is left.
+    fn take_from_stack(
+        &mut self,
+        item: Entity,
+        max_quantity: u16,
+    ) -> Option<Item> {
+        let stack = self.items.get_mut(item)?;
+        let taken = stack.split(max_quantity);
+        if stack.quantity == 0 {
+            self.items.remove(item);
+            self.locations.remove(item);
+            self.renderables.remove(item);
+            self.entities.delete(item).unwrap();
+        }
+        Some(taken)
+    }
+
+    fn is_drafted(&self, worker: Entity) -> bool {
+        self.workers.get(worker).is_some_and(|w| w.is_drafted)
+    }
+
+    /// Drop the current job for `job`, along with the carried items unless
+    /// the worker was already fighting or drafted.
+    fn replace_job(&mut self, worker: Entity, job: Job) {
+        if !matches!(
+            self.take_job(worker),
+            Some(Job::Drafted) | Some(Job::Fight { .. })
+        ) {
+            self.drop_carried(worker);
+        }
+        if let Some(w) = self.workers.get_mut(worker) {
+            w.job = Some(job);
+        }
+    }
+
+    fn target_of(&self, worker: Entity) -> Option<Entity> {
+        self.combatants.get(worker).and_then(|c| c.target)
+    }
+
+    fn take_job(&mut self, worker: Entity) -> Option<Job> {
+        self.workers.get_mut(worker).and_then(|w| w.job.take())
+    }
+
+    /// Send the worker to `target`, returns `true` if it has already
+    /// arrived.
+    fn walk_to(&mut self, worker: Entity, target: Loc) -> bool {
+        let arrived = self.location_of(worker) == Some(target);
+        if let Some(movement) = self.movements.get_mut(worker) {
+            movement.destination = if arrived { None } else { Some(target) };
+        }
+        arrived
+    }
+
+    /// Send the worker to a tile next to `target`, returns `true` if it is
+    /// already there.  A worker standing on `target` steps aside.
+    fn walk_next_to(&mut self, worker: Entity, target: Loc) -> bool {
+        let location = match self.location_of(worker) {
+            Some(location) => location,
+            None => return false,
+        };
+        let distance = location.distance_to(target);
+        let destination = match distance {
+            0 => [(0, 1), (1, 0), (0, -1), (-1, 0)]
+                .iter()
+                .map(|(dx, dy)| target.move_by(*dx, *dy))
+                .find(|l| !self.rooms.is_blocked(*l)),
+            1 => None,
+            _ => Some(target),
+        };
+        if let Some(movement) = self.movements.get_mut(worker) {
+            movement.destination = destination;
+        }
+        distance == 1
+    }
+}
+
+/// A single item lying on the map, made from an item taken off.
+pub(crate) fn equipped_item(equipped: Equipped) -> Item {
+    Item {
+        durability: Some(equipped.durability),
+        ..Item::new(equipped.kind, 1)
+    }
+}
+
+fn remove_from_carried(
+    carrying: &mut Vec<Item>,
+    kind: ItemKind,
+    quantity: u16,
+) {
+    if let Some(item) = carrying.iter_mut().find(|i| i.kind == kind) {
+        item.split(quantity);
+    }
+    carrying.retain(|i| i.quantity > 0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+    use crate::data::{AnimalKind, DAYS_PER_SEASON};
+
+    /// Game minutes the tests run the system for at most.
+    const MAX_MINUTES: u32 = 24 * 60;
+
+    /// A world with a single worker, standing at the origin.
+    fn with_worker() -> World {
+        let mut world = World::new();
+        components::register_with(&mut world);
+        world.add_resource(RecipeBook::from_ron(asset::RECIPES).unwrap());
+        world.add_resource(Random::new(7));
+        System::setup(&mut JobSystem::new(), &mut world.res);
+        {
+            let mut time = world.write_resource::<Time>();
+            time.set_game_time(0, 12, 0, 0);
+            time.set_game_dt(MILLIS_PER_MINUTE);
+        }
+        world
+            .create_entity()
+            .with(components::Location::new(Loc::new(0, 0)))
+            .with(components::Movement::for_colonist())
+            .with(components::Needs::default())
+            .with(components::Worker::default())
+            .build();
+        world
+    }
+
+    /// A handler standing next to an animal designated for handling.
+    fn handled_animal(
+        kind: AnimalKind,
+        designation: Designation,
+    ) -> (World, Entity) {
+        let mut world = with_worker();
+        let animal = world
+            .create_entity()
+            .with(components::Animal {
+                designation: Some(designation),
+                ..components::Animal::new(kind)
+            })
+            .with(components::Location::new(Loc::new(1, 0)))
+            .build();
+        (world, animal)
+    }
+
+    fn run_until(world: &mut World, mut is_done: impl FnMut(&World) -> bool) {
+        let mut system = JobSystem::new();
+        for _ in 0..MAX_MINUTES {
+            system.run_now(&world.res);
+            world.maintain();
+            if is_done(world) {
+                return;
+            }
+        }
+        panic!("Not done in {} minutes", MAX_MINUTES);
+    }
+
+    #[test]
+    fn taming_is_tried_until_the_animal_is_tame() {
+        let (mut world, animal) =
+            handled_animal(AnimalKind::Goat, Designation::Tame);
+        let mut failures = 0;
+        run_until(&mut world, |world| {
+            failures += world
+                .write_resource::<GameLog>()
+                .take()
+                .iter()
+                .filter(|e| e.contents().contains("failed to tame"))
+                .count();
+            world
+                .read_storage::<components::Animal>()
+                .get(animal)
+                .is_some_and(|a| a.is_tame)
+        });
+        assert!(failures > 0);
+        let animals = world.read_storage::<components::Animal>();
+        assert_eq!(None, animals.get(animal).unwrap().designation);
+    }
+
+    #[test]
+    fn slaughter_yields_meat_and_leather() {
+        let (mut world, animal) =
+            handled_animal(AnimalKind::Sheep, Designation::Slaughter);
+        run_until(&mut world, |world| !world.entities().is_alive(animal));
+        let items = world.read_storage::<Item>();
+        let locations = world.read_storage::<components::Location>();
+        let mut dropped: Vec<(ItemKind, u16, Loc)> = (&items, &locations)
+            .join()
+            .map(|(i, l)| (i.kind, i.quantity, l.location))
+            .collect();
+        dropped.sort_by_key(|(kind, ..)| *kind);
+        let stats = AnimalKind::Sheep.stats();
+        assert_eq!(
+            vec![
+                (ItemKind::Leather, stats.leather, Loc::new(1, 0)),
+                (ItemKind::RawMeat, stats.meat, Loc::new(1, 0)),
+            ],
+            dropped
+        );
+    }
+
+    #[test]
+    fn butchering_an_animal_corpse_yields_meat_and_leather() {
+        let mut world = with_worker();
+        let corpse = world
+            .create_entity()
+            .with(Item::new(ItemKind::Corpse, 1))
+            .with(components::Corpse {
+                disposal: Some(Disposal::Butcher),
+                ..components::Corpse::of_animal(AnimalKind::Goat)
+            })
+            .with(components::Location::new(Loc::new(1, 0)))
+            .build();
+        run_until(&mut world, |world| !world.entities().is_alive(corpse));
+        let items = world.read_storage::<Item>();
+        let mut dropped: Vec<(ItemKind, u16)> =
+            items.join().map(|i| (i.kind, i.quantity)).collect();
+        dropped.sort();
+        let stats = AnimalKind::Goat.stats();
+        assert_eq!(
+            vec![
+                (ItemKind::Leather, stats.leather),
+                (ItemKind::RawMeat, stats.meat),
+            ],
+            dropped
+        );
+    }
+
+    /// A corpse next to the worker, to be disposed of.
+    fn corpse_to_dispose(world: &mut World, disposal: Disposal) -> Entity {
+        world
+            .create_entity()
+            .with(Item::new(ItemKind::Corpse, 1))
+            .with(components::Corpse {
+                disposal: Some(disposal),
+                ..components::Corpse::new("Raider")
+            })
+            .with(components::Location::new(Loc::new(1, 0)))
+            .build()
+    }
+
+    #[test]
+    fn burying_a_corpse_leaves_a_grave() {
+        let mut world = with_worker();
+        let corpse = corpse_to_dispose(&mut world, Disposal::Bury);
+        run_until(&mut world, |world| !world.entities().is_alive(corpse));
+        let graves = world.read_storage::<components::Grave>();
+        let locations = world.read_storage::<components::Location>();
+        let buried: Vec<(&str, Loc)> = (&graves, &locations)
+            .join()
+            .map(|(g, l)| (g.name.as_str(), l.location))
+            .collect();
+        assert_eq!(vec![("Raider", Loc::new(1, 0))], buried);
+    }
+
+    #[test]
+    fn cremating_a_corpse_burns_the_firewood() {
+        let mut world = with_worker();
+        let (fuel, quantity) = CREMATION_FUEL;
+        world
+            .create_entity()
+            .with(Item::new(fuel, quantity))
+            .with(components::Location::new(Loc::new(0, 0)))
+            .with(components::Stockpile)
+            .build();
+        let corpse = corpse_to_dispose(&mut world, Disposal::Cremate);
+        run_until(&mut world, |world| !world.entities().is_alive(corpse));
+        assert!(world.read_storage::<Item>().is_empty());
+        assert!(world.read_storage::<components::Grave>().is_empty());
+        let workers = world.read_storage::<components::Worker>();
+        assert!(workers.join().all(|w| w.carrying.is_empty()));
+    }
+
+    #[test]
+    fn fields_are_sown_and_harvested() {
+        let mut world = with_worker();
+        let field = world
+            .create_entity()
+            .with(components::Field::new(ItemKind::Berries))
+            .with(components::Location::new(Loc::new(0, 0)))
+            .build();
+        let growth = |world: &World| {
+            world
+                .read_storage::<components::Field>()
+                .get(field)
+                .unwrap()
+                .growth
+        };
+        run_until(&mut world, |world| growth(world).is_some());
+        assert_eq!(Some(0.0), growth(&world));
+        world
+            .write_storage::<components::Field>()
+            .get_mut(field)
+            .unwrap()
+            .growth = Some(1.0);
+        run_until(&mut world, |world| growth(world).is_none());
+        let items = world.read_storage::<Item>();
+        let harvested: Vec<(ItemKind, u16)> =
+            items.join().map(|i| (i.kind, i.quantity)).collect();
+        let harvest_yield = ItemKind::Berries.crop().unwrap().harvest_yield;
+        assert_eq!(vec![(ItemKind::Berries, harvest_yield)], harvested);
+        let workers = world.read_storage::<components::Worker>();
+        assert!(workers.join().all(|w| {
+            w.skills.progress_percent(SkillKind::Farming) > 0
+                || w.skills.level(SkillKind::Farming) > 0
+        }));
+    }
+
+    #[test]
+    fn nothing_is_sown_in_winter() {
+        let mut world = with_worker();
+        world.write_resource::<Time>().set_game_time(
+            3 * DAYS_PER_SEASON,
+            12,
+            0,
+            0,
+        );
+        world
+            .create_entity()
+            .with(components::Field::new(ItemKind::Berries))
+            .with(components::Location::new(Loc::new(0, 0)))
+            .build();
+        JobSystem::new().run_now(&world.res);
+        let workers = world.read_storage::<components::Worker>();
+        assert!(workers.join().all(|w| w.job.is_none()));
+    }
+
+    #[test]
+    fn walls_wait_for_the_tile_to_be_clear() {
+        let mut world = with_worker();
+        let blueprint = world
+            .create_entity()
+            .with(components::Blueprint::new(Structure::Wall))
+            .with(components::Location::new(Loc::new(1, 0)))
+            .build();
+        let animal = world
+            .create_entity()
+            .with(components::Animal::new(AnimalKind::Goat))
+            .with(components::Location::new(Loc::new(1, 0)))
+            .with(components::Movement::new(1000))
+            .build();
+        for worker in (&mut world.write_storage::<components::Worker>()).join()
+        {
+            worker.job = Some(Job::Construct {
+                blueprint,
+                step: CraftStep::Work(0),
+            });
+        }
+        let mut system = JobSystem::new();
+        system.run_now(&world.res);
+        assert!(world.read_storage::<components::Wall>().is_empty());
+        world.delete_entity(animal).unwrap();
+        system.run_now(&world.res);
+        assert!(world.read_storage::<components::Wall>().contains(blueprint));
+    }
+}
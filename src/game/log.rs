@@ -25,15 +25,48 @@ use std::sync::Mutex;
 /// systems can write to the logs simultaneously, but there can be one reader
 /// at a time.  Synchronization across the writers are done via a `Mutex`.
 #[derive(Clone, Debug)]
-pub struct LogEntry(String);
+pub struct LogEntry {
+    contents: String,
+    kind: LogKind,
+}
+
+/// Decides how an entry is styled in the UI.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LogKind {
+    Info,
+    Combat,
+    /// Needs the player's attention right away.
+    Alert,
+}
 
 impl LogEntry {
     pub fn new(contents: &str) -> LogEntry {
-        LogEntry(contents.to_owned())
+        LogEntry {
+            contents: contents.to_owned(),
+            kind: LogKind::Info,
+        }
+    }
+
+    pub fn alert(contents: &str) -> LogEntry {
+        LogEntry {
+            contents: contents.to_owned(),
+            kind: LogKind::Alert,
+        }
+    }
+
+    pub fn combat(contents: &str) -> LogEntry {
+        LogEntry {
+            contents: contents.to_owned(),
+            kind: LogKind::Combat,
+        }
     }
 
     pub fn contents(&self) -> &String {
-        &self.0
+        &self.contents
+    }
+
+    pub fn kind(&self) -> LogKind {
+        self.kind
     }
 }
 
@@ -15,13 +15,16 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::{Location, ProbabilityTable, Rectangle, VisibleObject};
+use crate::data::{
+    Background, Biome, Biomes, ItemKind, Location, ProbabilityTable,
+    Rectangle, Schedule, Skills, VisibleObject, WorkPriorities,
+};
 use crate::game::{components, Cursor};
 use bluenoisers::blue_noise_iter;
 use log::debug;
 use specs::prelude::*;
 use std::i32;
-use std::mem::transmute;
+use std::mem::{self, transmute};
 use tcod::noise::{Noise, NoiseType};
 use tcod::random::{Algo, Rng};
 
@@ -33,6 +36,19 @@ const NOISE_SCALE: f32 = 9.18325;
 
 const OBJECT_DISTANCE: u16 = 3;
 
+const STARTING_ITEMS: &[(ItemKind, u16)] = &[
+    (ItemKind::Log, 20),
+    (ItemKind::Stone, 10),
+    (ItemKind::RawMeat, 20),
+    (ItemKind::Berries, 30),
+    (ItemKind::Medicine, 5),
+    (ItemKind::Parka, 2),
+    (ItemKind::Hammer, 1),
+    (ItemKind::Helmet, 1),
+    (ItemKind::Bow, 1),
+    (ItemKind::Silver, 150),
+];
+
 #[derive(Clone, Copy, Debug)]
 enum ObjectChoice {
     Rock,
@@ -47,13 +63,55 @@ pub enum MapStatus {
 
 pub struct MapSystem {
     status: MapStatus,
+    /// Names and backgrounds of the starting colonists.
+    party: Vec<(String, Background)>,
+    /// World seed of the game.
+    seed: u64,
 }
 
 impl MapSystem {
-    pub fn new() -> Self {
+    pub fn new(party: Vec<(String, Background)>, seed: u64) -> Self {
         // Initialize the map.
         Self {
             status: MapStatus::Unitialized,
+            party,
+            seed,
+        }
+    }
+
+    /// Place the starting colonists around the origin, and a stockpile with
+    /// some supplies just below them.
+    fn create_starting_colony(
+        entities: &Entities,
+        lazy_update: &LazyUpdate,
+        party: Vec<(String, Background)>,
+    ) {
+        let first = -(party.len() as i32 / 2);
+        for (idx, (name, background)) in party.into_iter().enumerate() {
+            let loc = Location::new(first + idx as i32, 0);
+            create_colonist(entities, lazy_update, &name, background, loc);
+        }
+        let stockpile = Rectangle::centered_around(Location::new(0, 3), 4, 3);
+        for loc in stockpile {
+            lazy_update
+                .create_entity(entities)
+                .with(components::Location::new(loc))
+                .with(components::Renderable::new(VisibleObject::Stockpile, 1))
+                .with(components::Stockpile)
+                .build();
+        }
+        for (loc, (kind, quantity)) in
+            stockpile.into_iter().zip(STARTING_ITEMS)
+        {
+            lazy_update
+                .create_entity(entities)
+                .with(components::Item::new(*kind, *quantity))
+                .with(components::Location::new(loc))
+                .with(components::Renderable::new(
+                    VisibleObject::Item(*kind),
+                    3,
+                ))
+                .build();
         }
     }
 
@@ -136,13 +194,18 @@ impl MapSystem {
 }
 
 impl<'a> System<'a> for MapSystem {
-    type SystemData = (Write<'a, Cursor>, Entities<'a>, Read<'a, LazyUpdate>);
+    type SystemData = (
+        Write<'a, Biomes>,
+        Write<'a, Cursor>,
+        Entities<'a>,
+        Read<'a, LazyUpdate>,
+    );
 
     fn run(&mut self, sys_data: Self::SystemData) {
-        let (mut cursor, entities, lazy_update) = sys_data;
+        let (mut biomes, mut cursor, entities, lazy_update) = sys_data;
 
         if self.status == MapStatus::Unitialized {
-            let seed: u32 = 987654;
+            let seed = noise_seed(self.seed);
             let boundaries = Rectangle::centered_around(
                 Location::origin(),
                 MAP_WIDTH,
@@ -153,6 +216,9 @@ impl<'a> System<'a> for MapSystem {
                 seed,
                 boundaries,
                 |loc, obj| {
+                    if let Some(biome) = Biome::of_ground(obj) {
+                        biomes.insert(loc, biome);
+                    }
                     lazy_update
                         .create_entity(&entities)
                         .with(components::Location::new(loc))
@@ -178,15 +244,61 @@ impl<'a> System<'a> for MapSystem {
                             VisibleObject::Rock,
                             1,
                         ))
+                        .with(components::Rock)
                         .build();
                 },
             );
+            Self::create_starting_colony(
+                &entities,
+                &lazy_update,
+                mem::take(&mut self.party),
+            );
             cursor.set_boundaries(boundaries).unwrap();
             self.status = MapStatus::Initialized
         }
     }
 }
 
+/// Create a colonist with the given background at `loc`, once the world is
+/// maintained.
+pub fn create_colonist(
+    entities: &Entities,
+    lazy_update: &LazyUpdate,
+    name: &str,
+    background: Background,
+    loc: Location,
+) {
+    let mut priorities = WorkPriorities::default();
+    for work in background.disabled() {
+        priorities.disable(work);
+    }
+    let schedule = if background.sleeps_by_day() {
+        Schedule::by_day()
+    } else {
+        Schedule::default()
+    };
+    let worker = components::Worker {
+        priorities,
+        schedule,
+        skills: Skills::with_levels(&background.skills()),
+        ..components::Worker::default()
+    };
+    let mood = components::Mood::new(background.mood());
+    lazy_update
+        .create_entity(entities)
+        .with(components::Colonist::new(name, background))
+        .with(components::Combatant::default())
+        .with(components::Health::default())
+        .with(components::Location::new(loc))
+        .with(mood)
+        .with(components::Movement::for_colonist())
+        .with(components::Needs::default())
+        .with(components::Renderable::new(VisibleObject::Colonist, 4))
+        .with(components::Social::default())
+        .with(worker)
+        .build();
+}
+
 fn generate_seed(rng: &Rng) -> u32 {
     unsafe {
         // i31::MIN doesn't work so we're using i32::MIN+1.
@@ -194,6 +306,12 @@ fn generate_seed(rng: &Rng) -> u32 {
     }
 }
 
+/// The noise generators take 32 bit seeds, both halves of the world seed
+/// go into one.
+fn noise_seed(seed: u64) -> u32 {
+    (seed ^ (seed >> 32)) as u32
+}
+
 fn location_to_noise_coordinate(location: Location) -> [f32; 2] {
     let x: f32 = (location.x as f32) / NOISE_SCALE;
     let y: f32 = (location.y as f32) / NOISE_SCALE;
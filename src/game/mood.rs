@@ -0,0 +1,231 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{
+    indoor_temperature, outdoor_temperature, rot_thought, Location as Loc,
+    MentalBreak, MentalBreakKind, Random, Rooms, ThoughtKind, Time,
+    COMFORTABLE_TEMPERATURE, MENTAL_BREAK_THRESHOLD, MILLIS_PER_HOUR,
+};
+use crate::game::components::{
+    Colonist, Corpse, Item, Location, Mood, Needs, Worker,
+};
+use crate::game::{GameLog, LogEntry};
+use specs::prelude::*;
+
+/// Chance of a mental break in a game hour, while the mood is low.
+const MENTAL_BREAK_CHANCE_PER_HOUR: f64 = 0.5;
+const MENTAL_BREAK_MIN_HOURS: i32 = 4;
+const MENTAL_BREAK_MAX_HOURS: i32 = 8;
+
+/// Keeps thoughts up to date, and starts & ends mental breaks.  Rotting
+/// corpses upset the colonists who see or smell them.
+pub struct MoodSystem {}
+
+impl MoodSystem {
+    pub fn new() -> MoodSystem {
+        MoodSystem {}
+    }
+}
+
+impl<'a> System<'a> for MoodSystem {
+    type SystemData = (
+        Read<'a, GameLog>,
+        Read<'a, Time>,
+        Write<'a, Random>,
+        Read<'a, Rooms>,
+        ReadStorage<'a, Colonist>,
+        ReadStorage<'a, Corpse>,
+        ReadStorage<'a, Item>,
+        ReadStorage<'a, Location>,
+        WriteStorage<'a, Mood>,
+        ReadStorage<'a, Needs>,
+        ReadStorage<'a, Worker>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (
+            game_log,
+            time,
+            mut random,
+            rooms,
+            colonists,
+            corpses,
+            items,
+            locations,
+            mut moods,
+            needs,
+            workers,
+        ) = sys_data;
+        let game_dt = time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        let break_chance = MENTAL_BREAK_CHANCE_PER_HOUR * f64::from(game_dt)
+            / f64::from(MILLIS_PER_HOUR);
+        let outdoors = outdoor_temperature(*time);
+        let rotting: Vec<Loc> = (&corpses, &items, &locations)
+            .join()
+            .filter(|(_, i, _)| i.freshness.is_some_and(|f| f.is_rotting()))
+            .map(|(_, _, l)| l.location)
+            .collect();
+        for (colonist, location, mood, needs, worker) in
+            (&colonists, &locations, &mut moods, &needs, workers.maybe())
+                .join()
+        {
+            let temperature = if rooms.is_indoors(location.location) {
+                indoor_temperature(outdoors)
+            } else {
+                outdoors
+            };
+            let warmth = worker.map_or(0.0, |w| w.loadout.warmth());
+            if temperature < COMFORTABLE_TEMPERATURE - warmth {
+                mood.thoughts.add(ThoughtKind::Cold);
+            }
+            if needs.is_hungry() {
+                mood.thoughts.add(ThoughtKind::Hungry);
+            }
+            if needs.is_tired() {
+                mood.thoughts.add(ThoughtKind::Tired);
+            }
+            let rot = rotting
+                .iter()
+                .filter_map(|corpse| {
+                    rot_thought(location.location, *corpse, |l| {
+                        rooms.is_blocked(l)
+                    })
+                })
+                .min_by_key(|kind| kind.mood_effect());
+            if let Some(kind) = rot {
+                mood.thoughts.add(kind);
+            }
+            mood.thoughts.tick(game_dt);
+            let value = mood.value();
+            match mood.mental_break.as_mut() {
+                Some(mental_break)
+                    if mental_break.remaining_millis > game_dt =>
+                {
+                    mental_break.remaining_millis -= game_dt;
+                }
+                Some(_) => {
+                    mood.mental_break = None;
+                    mood.thoughts.add(ThoughtKind::Catharsis);
+                    game_log.push(LogEntry::new(
+                        format!("{} has come to their senses", colonist.name)
+                            .as_str(),
+                    ));
+                }
+                None if value < MENTAL_BREAK_THRESHOLD
+                    && random.chance(break_chance) =>
+                {
+                    let kind = *random.pick(MentalBreakKind::ALL).unwrap();
+                    let hours = random
+                        .range(MENTAL_BREAK_MIN_HOURS, MENTAL_BREAK_MAX_HOURS);
+                    mood.mental_break = Some(MentalBreak {
+                        kind,
+                        remaining_millis: hours as u32 * MILLIS_PER_HOUR,
+                    });
+                    game_log.push(LogEntry::new(
+                        format!(
+                            "{} is having a mental break: {}",
+                            colonist.name, kind
+                        )
+                        .as_str(),
+                    ));
+                }
+                None => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset;
+    use crate::data::{
+        Backgrounds, MealQuality, RecipeBook, MILLIS_PER_MINUTE,
+    };
+    use crate::game::components::{self, Movement};
+    use crate::game::{Job, JobSystem};
+
+    const MINUTES_PER_DAY: u32 = 24 * 60;
+
+    #[test]
+    fn a_bad_day_breaks_a_colonist_until_they_recover() {
+        let mut world = World::new();
+        components::register_with(&mut world);
+        world.add_resource(RecipeBook::from_ron(asset::RECIPES).unwrap());
+        let mut random = Random::new(3);
+        let background = Backgrounds::from_ron(asset::BACKGROUNDS)
+            .unwrap()
+            .generate(&mut random);
+        world.add_resource(random);
+        let mut moods = MoodSystem::new();
+        let mut jobs = JobSystem::new();
+        System::setup(&mut moods, &mut world.res);
+        System::setup(&mut jobs, &mut world.res);
+        world
+            .write_resource::<Time>()
+            .set_game_dt(MILLIS_PER_MINUTE);
+        let mut mood = Mood::default();
+        mood.thoughts.add(ThoughtKind::SleptOutside);
+        mood.thoughts.add(ThoughtKind::AteMeal(MealQuality::Raw));
+        let colonist = world
+            .create_entity()
+            .with(Colonist::new("Ada", background))
+            .with(Location::new(Loc::origin()))
+            .with(mood)
+            .with(Movement::for_colonist())
+            .with(Needs {
+                food: 0.0,
+                rest: 0.0,
+            })
+            .with(Worker {
+                job: Some(Job::Sleep),
+                ..Worker::default()
+            })
+            .build();
+        let mut run = |world: &mut World| {
+            moods.run_now(&world.res);
+            jobs.run_now(&world.res);
+            world.maintain();
+            world
+                .read_storage::<Mood>()
+                .get(colonist)
+                .unwrap()
+                .mental_break
+        };
+        let started = (0..MINUTES_PER_DAY).any(|_| run(&mut world).is_some());
+        assert!(started);
+        let job = world
+            .read_storage::<Worker>()
+            .get(colonist)
+            .unwrap()
+            .job
+            .clone();
+        assert!(matches!(job, Some(Job::Sulk) | Some(Job::Wander { .. })));
+        let ended = (0..MINUTES_PER_DAY).any(|_| run(&mut world).is_none());
+        assert!(ended);
+        let moods = world.read_storage::<Mood>();
+        let mood = moods.get(colonist).unwrap();
+        assert!(mood
+            .thoughts
+            .iter()
+            .any(|t| t.kind == ThoughtKind::Catharsis));
+        assert!(mood.value() >= MENTAL_BREAK_THRESHOLD);
+    }
+}
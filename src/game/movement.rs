@@ -0,0 +1,104 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{find_path, Location as Loc, Rectangle, Rooms, Time};
+use crate::game::components::{Location, Movement};
+use specs::prelude::*;
+
+/// How far around the walls and the destination paths are searched.
+const SEARCH_MARGIN: i32 = 16;
+
+/// Moves entities towards their destinations as game time passes, walking
+/// around walls.
+pub struct MovementSystem {}
+
+impl MovementSystem {
+    pub fn new() -> MovementSystem {
+        MovementSystem {}
+    }
+}
+
+impl<'a> System<'a> for MovementSystem {
+    type SystemData = (
+        Read<'a, Rooms>,
+        Read<'a, Time>,
+        WriteStorage<'a, Location>,
+        WriteStorage<'a, Movement>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (rooms, time, mut locations, mut movements) = sys_data;
+        for (loc, movement) in (&mut locations, &mut movements).join() {
+            let destination = match movement.destination {
+                Some(destination) => destination,
+                None => {
+                    movement.path.clear();
+                    continue;
+                }
+            };
+            let is_path_valid = movement.path.first() == Some(&destination)
+                && movement
+                    .path
+                    .last()
+                    .is_some_and(|next| !rooms.is_blocked(*next));
+            if !is_path_valid {
+                let bounds = search_bounds(&rooms, loc.location, destination);
+                movement.path =
+                    match find_path(loc.location, destination, bounds, |l| {
+                        rooms.is_blocked(l)
+                    }) {
+                        Some(mut path) => {
+                            path.reverse();
+                            path
+                        }
+                        // Unreachable, wait until the way is open.
+                        None => continue,
+                    };
+            }
+            movement.progress_millis += time.game_dt_millis();
+            while movement.progress_millis >= movement.millis_per_tile {
+                match movement.path.pop() {
+                    Some(next) => {
+                        movement.progress_millis -= movement.millis_per_tile;
+                        loc.location = next;
+                    }
+                    None => break,
+                }
+            }
+            if loc.location == destination {
+                movement.destination = None;
+                movement.path.clear();
+                movement.progress_millis = 0;
+            }
+        }
+    }
+}
+
+fn search_bounds(rooms: &Rooms, from: Loc, to: Loc) -> Rectangle {
+    let r = Rectangle::new(from, to);
+    let r = match rooms.walled_area() {
+        Some(w) => Rectangle::new(
+            Loc::new(r.min_x.min(w.min_x), r.min_y.min(w.min_y)),
+            Loc::new(r.max_x.max(w.max_x), r.max_y.max(w.max_y)),
+        ),
+        None => r,
+    };
+    Rectangle::new(
+        Loc::new(r.min_x - SEARCH_MARGIN, r.min_y - SEARCH_MARGIN),
+        Loc::new(r.max_x + SEARCH_MARGIN, r.max_y + SEARCH_MARGIN),
+    )
+}
@@ -0,0 +1,56 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Time, MILLIS_PER_DAY};
+use crate::game::components::{Colonist, Needs};
+use specs::prelude::*;
+
+/// Fraction of the food need that is used up in a game day.
+const FOOD_PER_DAY: f32 = 1.0;
+/// Fraction of the rest need that is used up in a game day, sleeping
+/// restores it.
+const REST_PER_DAY: f32 = 1.0;
+
+/// Colonists get hungry and tired as the game time passes, some faster than
+/// others because of their traits.
+pub struct NeedsSystem {}
+
+impl NeedsSystem {
+    pub fn new() -> NeedsSystem {
+        NeedsSystem {}
+    }
+}
+
+impl<'a> System<'a> for NeedsSystem {
+    type SystemData = (
+        Read<'a, Time>,
+        ReadStorage<'a, Colonist>,
+        WriteStorage<'a, Needs>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (time, colonists, mut needs) = sys_data;
+        let days = time.game_dt_millis() as f32 / MILLIS_PER_DAY as f32;
+        for (n, colonist) in (&mut needs, colonists.maybe()).join() {
+            let (food_rate, rest_rate) = colonist.map_or((1.0, 1.0), |c| {
+                (c.background.food_rate(), c.background.rest_rate())
+            });
+            n.food = (n.food - days * FOOD_PER_DAY * food_rate).max(0.0);
+            n.rest = (n.rest - days * REST_PER_DAY * rest_rate).max(0.0);
+        }
+    }
+}
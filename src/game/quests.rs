@@ -0,0 +1,420 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    days_until_next_quest, Factions, ItemKind, Location as Loc, Objective,
+    Quest, QuestStatus, Random, Season, Time, DAYS_PER_SEASON,
+    FIRST_QUEST_DAY, MAX_ACTIVE_QUESTS, MILLIS_PER_DAY,
+};
+use crate::game::components::{
+    Colonist, Health, Item, Location, Raider, Renderable, Stockpile, Trader,
+    Worker,
+};
+use crate::game::{items, Caravan, GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::collections::BTreeSet;
+
+/// Goodwill a faction loses when a quest it offered fails.
+const FAILURE_GOODWILL: i32 = -5;
+
+/// The quests the colony took on.
+#[derive(Clone, Debug, Default)]
+pub struct Quests {
+    active: Vec<Quest>,
+}
+
+impl Quests {
+    pub fn active(&self) -> &[Quest] {
+        &self.active
+    }
+
+    fn has(&self, objective: Objective) -> bool {
+        self.active.iter().any(|q| q.objective == objective)
+    }
+}
+
+/// Factions offer quests: deliveries every few days, surviving the winter
+/// when it comes and defending their caravan from raiders.  Completed
+/// quests pay their silver into the stockpiles, failed quests cost
+/// goodwill.
+pub struct QuestSystem {
+    next_quest_day: u16,
+    /// Year the last winter quest was offered in.
+    winter_year: Option<u16>,
+    /// Whether the camping caravan already asked to be defended.
+    is_caravan_defended: bool,
+}
+
+impl QuestSystem {
+    pub fn new() -> QuestSystem {
+        QuestSystem {
+            next_quest_day: FIRST_QUEST_DAY,
+            winter_year: None,
+            is_caravan_defended: false,
+        }
+    }
+}
+
+impl<'a> System<'a> for QuestSystem {
+    type SystemData = QuestSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        let game_dt = sys_data.time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        let time = *sys_data.time;
+        if sys_data.quests.active.len() < MAX_ACTIVE_QUESTS {
+            if time.game_time_days() >= self.next_quest_day {
+                self.next_quest_day = time.game_time_days()
+                    + days_until_next_quest(&mut sys_data.random);
+                if let Some(faction) =
+                    sys_data.factions.pick_traders(&mut sys_data.random)
+                {
+                    let quest = Quest::delivery(faction, &mut sys_data.random);
+                    sys_data.offer(quest);
+                }
+            }
+            if time.season() == Season::Winter
+                && self.winter_year != Some(time.year())
+            {
+                self.winter_year = Some(time.year());
+                if let Some(faction) =
+                    sys_data.factions.pick_traders(&mut sys_data.random)
+                {
+                    let days = DAYS_PER_SEASON
+                        - time.game_time_days() % DAYS_PER_SEASON;
+                    sys_data.offer(Quest::survive_winter(faction, days));
+                }
+            }
+        }
+        if sys_data.caravan.camp().is_none() {
+            self.is_caravan_defended = false;
+        } else if !self.is_caravan_defended {
+            let raiders = sys_data.hostile_raiders();
+            if raiders > 0 {
+                self.is_caravan_defended = true;
+                let faction = sys_data.caravan.faction();
+                sys_data.offer(Quest::defend_caravan(faction, raiders));
+            }
+        }
+        sys_data.update_quests(game_dt);
+    }
+}
+
+#[derive(SystemData)]
+pub struct QuestSystemData<'a> {
+    entities: Entities<'a>,
+    caravan: Read<'a, Caravan>,
+    factions: Write<'a, Factions>,
+    game_log: Read<'a, GameLog>,
+    quests: Write<'a, Quests>,
+    random: Write<'a, Random>,
+    time: Read<'a, Time>,
+    colonists: ReadStorage<'a, Colonist>,
+    healths: ReadStorage<'a, Health>,
+    items: WriteStorage<'a, Item>,
+    locations: WriteStorage<'a, Location>,
+    raiders: ReadStorage<'a, Raider>,
+    renderables: WriteStorage<'a, Renderable>,
+    stockpiles: ReadStorage<'a, Stockpile>,
+    traders: ReadStorage<'a, Trader>,
+    workers: ReadStorage<'a, Worker>,
+}
+
+impl<'a> QuestSystemData<'a> {
+    fn offer(&mut self, quest: Quest) {
+        if self.quests.has(quest.objective) {
+            return;
+        }
+        let name = self.faction_name(quest.faction);
+        self.game_log.push(LogEntry::alert(
+            format!(
+                "The {} offer a quest: {} for {}",
+                name, quest.objective, quest.reward
+            )
+            .as_str(),
+        ));
+        self.quests.active.push(quest);
+    }
+
+    /// Track the progress of the active quests, pay for the completed ones
+    /// and drop the failed ones.
+    fn update_quests(&mut self, game_dt: u32) {
+        for mut quest in std::mem::take(&mut self.quests.active) {
+            quest.advance(game_dt);
+            let is_failed = self.track(&mut quest);
+            match quest.status() {
+                _ if is_failed => self.fail(&quest),
+                QuestStatus::Active => self.quests.active.push(quest),
+                QuestStatus::Completed => self.complete(&quest),
+                QuestStatus::Failed => self.fail(&quest),
+            }
+        }
+    }
+
+    /// Update the progress of `quest`.  Returns `true` if the quest failed
+    /// before its deadline.
+    fn track(&mut self, quest: &mut Quest) -> bool {
+        match quest.objective {
+            Objective::Deliver { kind, quantity } => {
+                let stockpiled = self.count_stockpiled(kind);
+                if stockpiled >= u32::from(quantity) {
+                    self.collect(kind, quantity);
+                }
+                quest.set_progress(stockpiled);
+                false
+            }
+            Objective::SurviveWinter => {
+                let days_left = quest.millis_left().div_ceil(MILLIS_PER_DAY);
+                quest.set_progress(quest.goal().saturating_sub(days_left));
+                self.colonists.is_empty()
+            }
+            Objective::DefendCaravan => {
+                let progress = if self.caravan.camp().is_none() {
+                    quest.goal()
+                } else {
+                    let remaining = self.hostile_raiders() as u32;
+                    quest.goal().saturating_sub(remaining)
+                };
+                quest.set_progress(progress);
+                (&self.traders, &self.healths)
+                    .join()
+                    .any(|(_, health)| health.is_downed)
+            }
+        }
+    }
+
+    /// Remove `quantity` goods from the stockpiles, the faction collected
+    /// them.  Stacks colonists are about to take are left alone.
+    fn collect(&mut self, kind: ItemKind, quantity: u16) {
+        let stockpile_tiles = self.stockpile_tiles();
+        let reserved = self.reserved_items();
+        let stacks: Vec<Entity> =
+            (&self.entities, &self.items, &self.locations)
+                .join()
+                .filter(|(e, item, l)| {
+                    item.kind == kind
+                        && !reserved.contains(e)
+                        && stockpile_tiles.contains(&l.location)
+                })
+                .map(|(e, _, _)| e)
+                .collect();
+        let mut left = quantity;
+        for stack in stacks {
+            let item = match self.items.get_mut(stack) {
+                Some(item) if left > 0 => item,
+                _ => break,
+            };
+            left -= item.split(left).quantity;
+            if item.quantity == 0 {
+                self.items.remove(stack);
+                self.locations.remove(stack);
+                self.renderables.remove(stack);
+                self.entities.delete(stack).unwrap();
+            }
+        }
+    }
+
+    fn count_stockpiled(&self, kind: ItemKind) -> u32 {
+        items::count_stockpiled(
+            &self.entities,
+            &self.items,
+            &self.locations,
+            &self.stockpiles,
+            kind,
+            &self.reserved_items(),
+        )
+    }
+
+    fn faction_name(&self, faction: usize) -> String {
+        self.factions
+            .get(faction)
+            .map_or_else(String::new, |f| f.name.clone())
+    }
+
+    /// Number of raiders on their feet that are not retreating.
+    fn hostile_raiders(&self) -> usize {
+        (&self.raiders, &self.healths)
+            .join()
+            .filter(|(r, h)| !h.is_downed && !r.is_retreating && !r.is_rescued)
+            .count()
+    }
+
+    fn reserved_items(&self) -> BTreeSet<Entity> {
+        self.workers
+            .join()
+            .filter_map(|w| w.job.as_ref()?.reserved_item())
+            .collect()
+    }
+
+    /// Where the reward is left: a stockpile tile with silver on it, or an
+    /// empty one, or else where a colonist stands.
+    fn reward_location(&self) -> Loc {
+        let stockpile_tiles = self.stockpile_tiles();
+        stockpile_tiles
+            .into_iter()
+            .filter_map(|loc| {
+                let kinds: Vec<ItemKind> = (&self.items, &self.locations)
+                    .join()
+                    .filter(|(_, l)| l.location == loc)
+                    .map(|(i, _)| i.kind)
+                    .collect();
+                kinds
+                    .iter()
+                    .all(|kind| *kind == ItemKind::Silver)
+                    .then_some((kinds.is_empty(), loc))
+            })
+            .min()
+            .map(|(_, loc)| loc)
+            .or_else(|| {
+                (&self.colonists, &self.locations)
+                    .join()
+                    .map(|(_, l)| l.location)
+                    .next()
+            })
+            .unwrap_or_else(Loc::origin)
+    }
+
+    fn stockpile_tiles(&self) -> BTreeSet<Loc> {
+        items::stockpile_tiles(&self.locations, &self.stockpiles)
+    }
+
+    fn complete(&mut self, quest: &Quest) {
+        let day = self.time.game_time_days();
+        let reward = quest.reward;
+        if let Some(faction) = self.factions.get_mut(quest.faction) {
+            faction.change_goodwill(reward.goodwill, "Quest completed", day);
+        }
+        if reward.silver > 0 {
+            let loc = self.reward_location();
+            items::drop_item(
+                &self.entities,
+                &mut self.items,
+                &mut self.locations,
+                &mut self.renderables,
+                loc,
+                Item::new(ItemKind::Silver, reward.silver),
+            );
+        }
+        self.game_log.push(LogEntry::alert(
+            format!(
+                "Quest completed: {}.  The {} paid {}",
+                quest.objective,
+                self.faction_name(quest.faction),
+                reward
+            )
+            .as_str(),
+        ));
+    }
+
+    fn fail(&mut self, quest: &Quest) {
+        let day = self.time.game_time_days();
+        if let Some(faction) = self.factions.get_mut(quest.faction) {
+            faction.change_goodwill(FAILURE_GOODWILL, "Quest failed", day);
+        }
+        self.game_log.push(LogEntry::alert(
+            format!("Quest failed: {}", quest.objective).as_str(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MILLIS_PER_MINUTE;
+    use crate::game::components;
+    use crate::game::Job;
+
+    /// A world with an active delivery quest and the goods in a stockpile.
+    /// Returns the goods and the silver the quest pays.
+    fn with_delivery() -> (World, Entity, u16) {
+        let mut world = World::new();
+        components::register_with(&mut world);
+        System::setup(&mut QuestSystem::new(), &mut world.res);
+        world
+            .write_resource::<Time>()
+            .set_game_dt(MILLIS_PER_MINUTE);
+        let quest = Quest::delivery(0, &mut Random::new(1));
+        let silver = quest.reward.silver;
+        let goods = match quest.objective {
+            Objective::Deliver { kind, quantity } => {
+                stockpiled(&mut world, Loc::new(0, 0), kind, quantity)
+            }
+            _ => unreachable!(),
+        };
+        world.write_resource::<Quests>().active.push(quest);
+        (world, goods, silver)
+    }
+
+    fn stockpiled(
+        world: &mut World,
+        loc: Loc,
+        kind: ItemKind,
+        quantity: u16,
+    ) -> Entity {
+        world
+            .create_entity()
+            .with(Item::new(kind, quantity))
+            .with(Location::new(loc))
+            .with(Stockpile)
+            .build()
+    }
+
+    fn items(world: &World) -> Vec<(ItemKind, u16, Loc)> {
+        let items = world.read_storage::<Item>();
+        let locations = world.read_storage::<Location>();
+        (&items, &locations)
+            .join()
+            .map(|(i, l)| (i.kind, i.quantity, l.location))
+            .collect()
+    }
+
+    #[test]
+    fn rewards_are_stockpiled_with_the_silver() {
+        let (mut world, _, silver) = with_delivery();
+        stockpiled(&mut world, Loc::new(1, 0), ItemKind::Silver, 5);
+        QuestSystem::new().run_now(&world.res);
+        world.maintain();
+        assert!(world.read_resource::<Quests>().active().is_empty());
+        assert_eq!(
+            vec![(ItemKind::Silver, silver + 5, Loc::new(1, 0))],
+            items(&world)
+        );
+    }
+
+    #[test]
+    fn reserved_goods_are_not_collected() {
+        let (mut world, goods, _) = with_delivery();
+        world
+            .create_entity()
+            .with(components::Worker {
+                job: Some(Job::Haul {
+                    item: goods,
+                    to: Loc::new(2, 0),
+                    picked_up: false,
+                }),
+                ..components::Worker::default()
+            })
+            .build();
+        let before = items(&world);
+        QuestSystem::new().run_now(&world.res);
+        world.maintain();
+        assert_eq!(1, world.read_resource::<Quests>().active().len());
+        assert_eq!(before, items(&world));
+    }
+}
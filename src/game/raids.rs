@@ -0,0 +1,537 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    arrival_point, is_beaten, raid_size, raider_weapons, Direction, Factions,
+    ItemKind, Location as Loc, Pause, RaiderPlan, Random, Rectangle, Rooms,
+    Storyteller, Time, VisibleObject, MILLIS_PER_MINUTE,
+};
+use crate::game::components::{
+    Bed, Colonist, Combatant, Corpse, Defense, Health, Item, Location,
+    Movement, Raider, Renderable, Stockpile, Trader, Wall, Workbench, Worker,
+};
+use crate::game::{items, Chronicle, Cursor, GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::collections::BTreeSet;
+
+/// Game time a downed raider survives outside of a bed.
+const BLEED_OUT_MINUTES: u32 = 360;
+/// Goodwill a faction loses for each of its raiders downed.
+const FIGHT_GOODWILL: i32 = -3;
+const ITEM_Z_INDEX: u16 = 3;
+/// How far raiders spread out around their arrival point.
+const RAIDER_SPREAD: i32 = 2;
+const RAIDER_Z_INDEX: u16 = 4;
+/// Goodwill a faction gains for each of its raiders rescued.
+const RESCUE_GOODWILL: i32 = 15;
+/// Raiders attack colonists this close.
+const SIGHT_DISTANCE: u32 = 10;
+/// Hit points a defense loses each time a raider is done wrecking it.
+const WRECKING_DAMAGE: u16 = 40;
+const WRECKING_MINUTES: u32 = 60;
+
+#[derive(Debug)]
+struct Raid {
+    size: usize,
+    /// Raiders downed so far.
+    down: usize,
+    is_retreating: bool,
+}
+
+/// Sends raids of a hostile faction from a map edge when the [Storyteller]
+/// tells one, sized by the strength of the colony.  Raiders fight the colonists they see, wreck
+/// structures and steal from the stockpiles, and retreat once the raid is
+/// beaten.  Downed raiders die unless they are rescued, which the faction
+/// is grateful for.
+pub struct RaidSystem {
+    raid: Option<Raid>,
+}
+
+impl RaidSystem {
+    pub fn new() -> RaidSystem {
+        RaidSystem { raid: None }
+    }
+}
+
+impl<'a> System<'a> for RaidSystem {
+    type SystemData = RaidSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        let game_dt = sys_data.time.game_dt_millis();
+        let map = match sys_data.cursor.boundaries() {
+            Some(map) if game_dt > 0 => map,
+            _ => return,
+        };
+        let down = sys_data.tend_downed(game_dt);
+        match self.raid.as_mut() {
+            Some(raid) => {
+                raid.down += down;
+                if !raid.is_retreating && is_beaten(raid.size, raid.down) {
+                    raid.is_retreating = true;
+                    sys_data.retreat();
+                }
+                if sys_data.raiders.join().all(|r| r.is_rescued) {
+                    sys_data
+                        .game_log
+                        .push(LogEntry::alert("The raid is over"));
+                    self.raid = None;
+                }
+            }
+            None if sys_data.storyteller.take_raid() => {
+                self.raid = sys_data.launch(map).map(|size| Raid {
+                    size,
+                    down: 0,
+                    is_retreating: false,
+                });
+            }
+            None => (),
+        }
+        sys_data.storyteller.set_raid_active(self.raid.is_some());
+        sys_data.command(map, game_dt);
+    }
+}
+
+#[derive(SystemData)]
+pub struct RaidSystemData<'a> {
+    entities: Entities<'a>,
+    chronicle: Write<'a, Chronicle>,
+    cursor: Write<'a, Cursor>,
+    factions: Write<'a, Factions>,
+    game_log: Read<'a, GameLog>,
+    pause: Write<'a, Pause>,
+    random: Write<'a, Random>,
+    rooms: Read<'a, Rooms>,
+    storyteller: Write<'a, Storyteller>,
+    time: Read<'a, Time>,
+    beds: WriteStorage<'a, Bed>,
+    colonists: ReadStorage<'a, Colonist>,
+    combatants: WriteStorage<'a, Combatant>,
+    corpses: WriteStorage<'a, Corpse>,
+    defenses: WriteStorage<'a, Defense>,
+    healths: WriteStorage<'a, Health>,
+    items: WriteStorage<'a, Item>,
+    locations: WriteStorage<'a, Location>,
+    movements: WriteStorage<'a, Movement>,
+    raiders: WriteStorage<'a, Raider>,
+    renderables: WriteStorage<'a, Renderable>,
+    stockpiles: ReadStorage<'a, Stockpile>,
+    traders: ReadStorage<'a, Trader>,
+    walls: WriteStorage<'a, Wall>,
+    workbenches: WriteStorage<'a, Workbench>,
+    workers: ReadStorage<'a, Worker>,
+}
+
+impl<'a> RaidSystemData<'a> {
+    /// Spawn a raid of a hostile faction on a random map edge, pause the
+    /// game and point the cursor at the raiders.  Returns the number of
+    /// raiders, or `None` if no faction is hostile.
+    fn launch(&mut self, map: Rectangle) -> Option<usize> {
+        let faction = self.factions.pick_raiders(&mut self.random)?;
+        let colonists = (&self.colonists, &self.healths)
+            .join()
+            .filter(|(_, h)| !h.is_downed)
+            .count();
+        let armed = (&self.colonists, &self.workers)
+            .join()
+            .filter(|(_, w)| w.loadout.weapon().is_some())
+            .count();
+        let size = raid_size(colonists, armed);
+        let edge = *self.random.pick(Direction::ALL).unwrap();
+        let arrival = arrival_point(map, edge, &mut self.random);
+        let weapons = raider_weapons();
+        for _ in 0..size {
+            let loc = arrival.move_by(
+                self.random.range(-RAIDER_SPREAD, RAIDER_SPREAD),
+                self.random.range(-RAIDER_SPREAD, RAIDER_SPREAD),
+            );
+            let loc = if map.contains(loc) && !self.rooms.is_blocked(loc) {
+                loc
+            } else {
+                arrival
+            };
+            let plan = *self.random.pick(RaiderPlan::ALL).unwrap();
+            let weapon = weapons.choose(self.random.unit());
+            self.entities
+                .build_entity()
+                .with(
+                    Raider::new(faction, plan, weapon, loc),
+                    &mut self.raiders,
+                )
+                .with(Combatant::default(), &mut self.combatants)
+                .with(Health::default(), &mut self.healths)
+                .with(Location::new(loc), &mut self.locations)
+                .with(Movement::for_colonist(), &mut self.movements)
+                .with(
+                    Renderable::new(VisibleObject::Raider, RAIDER_Z_INDEX),
+                    &mut self.renderables,
+                )
+                .build();
+        }
+        self.pause.is_paused = true;
+        self.cursor.jump_to(arrival);
+        let name = self.factions.get(faction).map_or("", |f| &f.name);
+        self.game_log.push(LogEntry::alert(
+            format!(
+                "{} raiders of the {} arrived from the {} at {}, {}!",
+                size, name, edge, arrival.x, arrival.y
+            )
+            .as_str(),
+        ));
+        self.chronicle.record(
+            self.time.game_time_days(),
+            format!("{} raiders of the {} attacked.", size, name).as_str(),
+        );
+        Some(size)
+    }
+
+    /// Raiders downed in a bed are rescued, the others die once they bleed
+    /// out.  Returns the number of raiders downed since the last time.
+    fn tend_downed(&mut self, game_dt: u32) -> usize {
+        let bed_tiles: BTreeSet<Loc> = (&self.beds, &self.locations)
+            .join()
+            .map(|(_, l)| l.location)
+            .collect();
+        let day = self.time.game_time_days();
+        let mut down = 0;
+        let mut dead = vec![];
+        for (entity, raider, _, location, combatant, movement) in (
+            &self.entities,
+            &mut self.raiders,
+            &self.healths,
+            &self.locations,
+            &mut self.combatants,
+            &mut self.movements,
+        )
+            .join()
+            .filter(|(_, _, health, ..)| health.is_downed)
+        {
+            combatant.target = None;
+            movement.destination = None;
+            let faction = self.factions.get_mut(raider.faction);
+            if raider.downed_millis.is_none() {
+                down += 1;
+                if let Some(faction) = faction {
+                    faction.change_goodwill(FIGHT_GOODWILL, "Fight", day);
+                }
+            }
+            let downed_millis = raider.downed_millis.unwrap_or(0);
+            if bed_tiles.contains(&location.location) {
+                raider.downed_millis = Some(downed_millis);
+                if raider.is_rescued {
+                    continue;
+                }
+                raider.is_rescued = true;
+                if let Some(faction) = self.factions.get_mut(raider.faction) {
+                    faction.change_goodwill(
+                        RESCUE_GOODWILL,
+                        "Rescued a member",
+                        day,
+                    );
+                    self.game_log.push(LogEntry::new(
+                        format!(
+                            "A rescued raider of the {} will go home once \
+                             recovered.",
+                            faction.name
+                        )
+                        .as_str(),
+                    ));
+                }
+                continue;
+            }
+            raider.downed_millis = Some(downed_millis + game_dt);
+            if downed_millis + game_dt >= BLEED_OUT_MINUTES * MILLIS_PER_MINUTE
+            {
+                dead.push(entity);
+            }
+        }
+        for entity in dead.iter().copied() {
+            let (raider, loc) = match (
+                self.raiders.remove(entity),
+                self.locations.remove(entity),
+            ) {
+                (Some(raider), Some(l)) => (raider, l.location),
+                _ => continue,
+            };
+            self.remove(entity);
+            let name = self
+                .factions
+                .get(raider.faction)
+                .map_or("Raider".to_owned(), |f| {
+                    format!("Raider of the {}", f.name)
+                });
+            self.entities
+                .build_entity()
+                .with(Item::new(ItemKind::Corpse, 1), &mut self.items)
+                .with(Corpse::new(&name), &mut self.corpses)
+                .with(Location::new(loc), &mut self.locations)
+                .with(
+                    Renderable::new(
+                        VisibleObject::Item(ItemKind::Corpse),
+                        ITEM_Z_INDEX,
+                    ),
+                    &mut self.renderables,
+                )
+                .build();
+            let weapon = raider.weapon.map(|kind| Item::new(kind, 1));
+            for item in weapon.into_iter().chain(raider.loot) {
+                self.drop_item(loc, item);
+            }
+            self.game_log.push(LogEntry::combat("A raider died"));
+        }
+        down
+    }
+
+    fn retreat(&mut self) {
+        for raider in (&mut self.raiders).join() {
+            raider.is_retreating = true;
+        }
+        self.game_log
+            .push(LogEntry::alert("The raiders are retreating"));
+    }
+
+    /// Raiders fight colonists and traders in sight, otherwise they carry
+    /// out their plan.  Retreating raiders, rescued raiders and raiders with loot run
+    /// to the map edge they came from.  Downed raiders do nothing.
+    fn command(&mut self, map: Rectangle, game_dt: u32) {
+        let colonists: Vec<(Entity, Loc)> =
+            (&self.entities, &self.healths, &self.locations)
+                .join()
+                .filter(|(entity, health, _)| {
+                    !health.is_downed
+                        && (self.colonists.contains(*entity)
+                            || self.traders.contains(*entity))
+                })
+                .map(|(entity, _, location)| (entity, location.location))
+                .collect();
+        let raiders: Vec<(Entity, Loc)> = (
+            &self.entities,
+            &self.raiders,
+            &self.healths,
+            &self.locations,
+        )
+            .join()
+            .filter(|(_, _, health, _)| !health.is_downed)
+            .map(|(entity, _, _, location)| (entity, location.location))
+            .collect();
+        for (entity, loc) in raiders {
+            let (is_leaving, plan, edge) = match self.raiders.get(entity) {
+                Some(r) => (
+                    r.is_retreating || r.is_rescued || r.loot.is_some(),
+                    r.plan,
+                    r.edge,
+                ),
+                None => continue,
+            };
+            if is_leaving {
+                self.leave(entity, loc, edge, map);
+                continue;
+            }
+            let colonist = colonists
+                .iter()
+                .filter(|(_, c)| c.distance_to(loc) <= SIGHT_DISTANCE)
+                .min_by_key(|(_, c)| c.distance_to(loc))
+                .map(|(c, _)| *c);
+            if let Some(c) = self.combatants.get_mut(entity) {
+                if colonist.is_some() {
+                    c.target = colonist;
+                }
+                if c.target.is_some() {
+                    continue;
+                }
+            }
+            let done = match plan {
+                RaiderPlan::Wreck => self.wreck(entity, loc, game_dt),
+                RaiderPlan::Steal => self.steal(entity, loc),
+            };
+            if !done {
+                continue;
+            }
+            // Nothing left to do, try the other plan before leaving.
+            if let Some(raider) = self.raiders.get_mut(entity) {
+                match raider.plan {
+                    RaiderPlan::Wreck => raider.plan = RaiderPlan::Steal,
+                    RaiderPlan::Steal => raider.is_retreating = true,
+                }
+            }
+        }
+    }
+
+    /// Run to the map edge and leave, taking the loot along.
+    fn leave(&mut self, entity: Entity, loc: Loc, edge: Loc, map: Rectangle) {
+        if let Some(c) = self.combatants.get_mut(entity) {
+            c.target = None;
+        }
+        // Raiders spread out on arrival, so they may have come in a few
+        // tiles away from the edge.
+        let is_on_edge = loc == edge
+            || loc.x == map.min_x
+            || loc.x == map.max_x
+            || loc.y == map.min_y
+            || loc.y == map.max_y;
+        if !is_on_edge {
+            if let Some(movement) = self.movements.get_mut(entity) {
+                movement.destination = Some(edge);
+            }
+            return;
+        }
+        let raider = self.raiders.remove(entity);
+        self.locations.remove(entity);
+        self.remove(entity);
+        if raider.as_ref().is_some_and(|r| r.is_rescued) {
+            self.game_log
+                .push(LogEntry::new("A rescued raider went home"));
+        }
+        if let Some(loot) = raider.and_then(|r| r.loot) {
+            self.game_log.push(LogEntry::alert(
+                format!(
+                    "A raider got away with {} {}",
+                    loot.quantity, loot.kind
+                )
+                .as_str(),
+            ));
+        }
+    }
+
+    /// Walk to the nearest stack in the stockpiles and take it.  Returns
+    /// `true` if there is nothing to steal.
+    fn steal(&mut self, entity: Entity, loc: Loc) -> bool {
+        let stockpile_tiles =
+            items::stockpile_tiles(&self.locations, &self.stockpiles);
+        let target = (&self.entities, &self.items, &self.locations)
+            .join()
+            .filter(|(_, _, l)| stockpile_tiles.contains(&l.location))
+            .min_by_key(|(_, _, l)| l.location.distance_to(loc))
+            .map(|(e, _, l)| (e, l.location));
+        let (item, item_loc) = match target {
+            Some(target) => target,
+            None => return true,
+        };
+        if item_loc != loc {
+            if let Some(movement) = self.movements.get_mut(entity) {
+                movement.destination = Some(item_loc);
+            }
+            return false;
+        }
+        let loot = match self.items.remove(item) {
+            Some(loot) => loot,
+            None => return true,
+        };
+        self.locations.remove(item);
+        self.renderables.remove(item);
+        self.entities.delete(item).unwrap();
+        self.game_log.push(LogEntry::alert(
+            format!("A raider stole {} {}", loot.quantity, loot.kind).as_str(),
+        ));
+        if let Some(raider) = self.raiders.get_mut(entity) {
+            raider.loot = Some(loot);
+        }
+        false
+    }
+
+    /// Walk next to the nearest wall, workbench, bed or defense and wreck
+    /// it.  Defenses only lose some of their hit points, and traps are
+    /// hidden from raiders.  Returns `true` if there is nothing to wreck.
+    fn wreck(&mut self, entity: Entity, loc: Loc, game_dt: u32) -> bool {
+        let target = (&self.entities, &self.locations)
+            .join()
+            .filter(|(e, _)| {
+                self.walls.contains(*e)
+                    || self.workbenches.contains(*e)
+                    || self.beds.contains(*e)
+                    || self
+                        .defenses
+                        .get(*e)
+                        .is_some_and(|d| !d.structure.is_trap())
+            })
+            .min_by_key(|(_, l)| l.location.distance_to(loc))
+            .map(|(e, l)| (e, l.location));
+        let (structure, structure_loc) = match target {
+            Some(target) => target,
+            None => return true,
+        };
+        if structure_loc.distance_to(loc) > 1 {
+            let rooms = &self.rooms;
+            let next_to = Direction::ALL
+                .iter()
+                .map(|d| structure_loc.move_towards(*d))
+                .filter(|l| !rooms.is_blocked(*l))
+                .min_by_key(|l| l.distance_to(loc));
+            if let Some(movement) = self.movements.get_mut(entity) {
+                movement.destination = next_to;
+            }
+            return false;
+        }
+        let raider = match self.raiders.get_mut(entity) {
+            Some(raider) => raider,
+            None => return true,
+        };
+        raider.work_millis += game_dt;
+        if raider.work_millis < WRECKING_MINUTES * MILLIS_PER_MINUTE {
+            return false;
+        }
+        raider.work_millis = 0;
+        if let Some(defense) = self.defenses.get_mut(structure) {
+            defense.damage(WRECKING_DAMAGE);
+            self.game_log.push(LogEntry::alert(
+                format!(
+                    "A raider damaged a {}",
+                    defense.structure.to_string().to_lowercase()
+                )
+                .as_str(),
+            ));
+            return false;
+        }
+        let name = if self.walls.remove(structure).is_some() {
+            "wall".to_owned()
+        } else if self.beds.remove(structure).is_some() {
+            "bed".to_owned()
+        } else {
+            self.workbenches
+                .remove(structure)
+                .map_or("structure".to_owned(), |w| {
+                    w.kind.to_string().to_lowercase()
+                })
+        };
+        self.locations.remove(structure);
+        self.renderables.remove(structure);
+        self.entities.delete(structure).unwrap();
+        self.game_log.push(LogEntry::alert(
+            format!("A raider wrecked a {}", name).as_str(),
+        ));
+        false
+    }
+
+    /// Delete a raider whose [Raider] and [Location] are already removed.
+    fn remove(&mut self, entity: Entity) {
+        self.combatants.remove(entity);
+        self.healths.remove(entity);
+        self.movements.remove(entity);
+        self.renderables.remove(entity);
+        self.entities.delete(entity).unwrap();
+    }
+
+    fn drop_item(&mut self, loc: Loc, item: Item) {
+        items::drop_item(
+            &self.entities,
+            &mut self.items,
+            &mut self.locations,
+            &mut self.renderables,
+            loc,
+            item,
+        );
+    }
+}
@@ -0,0 +1,42 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+/// A colonist along with their friends and rivals, as shown in the
+/// [RelationshipTable].
+#[derive(Clone, Debug)]
+pub struct RelationshipRow {
+    pub name: String,
+    /// Names of the friends and the opinions of them, best friend first.
+    pub friends: Vec<(String, i32)>,
+    /// Names of the rivals and the opinions of them, worst rival first.
+    pub rivals: Vec<(String, i32)>,
+}
+
+/// Full-screen list of each colonist's friends and rivals.
+#[derive(Clone, Debug, Default)]
+pub struct RelationshipTable {
+    rows: Vec<RelationshipRow>,
+}
+
+impl RelationshipTable {
+    pub fn rows(&self) -> &[RelationshipRow] {
+        &self.rows
+    }
+
+    pub fn set_rows(&mut self, rows: Vec<RelationshipRow>) {
+        self.rows = rows;
+    }
+}
@@ -15,10 +15,18 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::Time;
-use crate::game::{components, Cursor, GameLog, SceneData};
+use crate::data::{Location, RoomOverlay, Rooms, Time, VisibleObject};
+use crate::game::{
+    components, Cursor, EquipmentTable, FactionTable, GameLog, Projectiles,
+    Quests, RelationshipTable, ResearchTable, SceneData, ScheduleEditor,
+    Screen, ScreenTables, Selection, TradeTable, WorkTable,
+};
 use shred_derive::*;
 use specs::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Projectiles fly over everything else.
+const PROJECTILE_Z_INDEX: u16 = 10;
 
 pub struct RenderingSystem {}
 
@@ -42,6 +50,65 @@ impl<'a> System<'a> for RenderingSystem {
                 rend.z_index,
             );
         }
+        for loc in sys_data.projectiles.locations() {
+            scene_data.add_object_to_location(
+                loc,
+                VisibleObject::Projectile,
+                PROJECTILE_Z_INDEX,
+            );
+        }
+        scene_data.set_room_overlay(if sys_data.room_overlay.is_visible {
+            sys_data
+                .rooms
+                .room_tiles()
+                .map(|(loc, room)| (loc, room.quality()))
+                .collect()
+        } else {
+            BTreeMap::new()
+        });
+        let locations = &sys_data.locations;
+        let mut selection: BTreeSet<Location> = sys_data
+            .selection
+            .selected()
+            .iter()
+            .filter_map(|e| locations.get(*e))
+            .map(|loc| loc.location)
+            .collect();
+        if let Some(area) = sys_data.selection.area(sys_data.cursor.location())
+        {
+            selection.extend(area);
+        }
+        scene_data.set_selection(selection);
+        scene_data.set_quest_lines(
+            sys_data
+                .quests
+                .active()
+                .iter()
+                .flat_map(|quest| {
+                    vec![
+                        quest.objective.to_string(),
+                        format!(
+                            " {}/{}, {} left",
+                            quest.progress(),
+                            quest.goal(),
+                            quest.time_left()
+                        ),
+                    ]
+                })
+                .collect(),
+        );
+        scene_data.set_screen(
+            *sys_data.screen,
+            ScreenTables {
+                equipment: &sys_data.equipment_table,
+                factions: &sys_data.faction_table,
+                relationships: &sys_data.relationship_table,
+                research: &sys_data.research_table,
+                schedules: &sys_data.schedule_editor,
+                trade: &sys_data.trade_table,
+                work_priorities: &sys_data.work_table,
+            },
+        );
         scene_data.update(
             sys_data.cursor.location(),
             sys_data.game_log.take(),
@@ -62,9 +129,22 @@ impl<'a> System<'a> for RenderingSystem {
 #[derive(SystemData)]
 pub struct RenderingSystemData<'a> {
     cursor: Read<'a, Cursor>,
+    equipment_table: Read<'a, EquipmentTable>,
+    faction_table: Read<'a, FactionTable>,
     game_log: Write<'a, GameLog>,
+    projectiles: Read<'a, Projectiles>,
+    quests: Read<'a, Quests>,
+    relationship_table: Read<'a, RelationshipTable>,
+    research_table: Read<'a, ResearchTable>,
+    room_overlay: Read<'a, RoomOverlay>,
+    rooms: Read<'a, Rooms>,
     scene_data: Write<'a, SceneData>,
+    schedule_editor: Read<'a, ScheduleEditor>,
+    screen: Read<'a, Screen>,
+    selection: Read<'a, Selection>,
     time: Read<'a, Time>,
+    trade_table: Read<'a, TradeTable>,
+    work_table: Read<'a, WorkTable>,
     locations: ReadStorage<'a, components::Location>,
     renderables: ReadStorage<'a, components::Renderable>,
 }
@@ -0,0 +1,60 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::game::{RowSelection, SelectRows};
+
+/// A project of the technology tree, as shown in the [ResearchTable].
+#[derive(Clone, Debug, Default)]
+pub struct ResearchRow {
+    /// Index of the project in the tech tree.
+    pub project: usize,
+    pub name: String,
+    /// Number of prerequisites to complete one after another first.
+    pub depth: usize,
+    pub cost: u32,
+    pub progress: f32,
+    pub is_available: bool,
+    pub is_completed: bool,
+    /// Position in the research queue, starting from 0.
+    pub queued: Option<usize>,
+    pub prerequisites: Vec<String>,
+    pub unlocks: Vec<String>,
+}
+
+/// Full-screen table of the tech tree, a row for each research project.
+#[derive(Clone, Debug, Default)]
+pub struct ResearchTable {
+    rows: RowSelection<ResearchRow>,
+}
+
+impl ResearchTable {
+    /// Tech tree index of the selected project.
+    pub fn selected_project(&self) -> Option<usize> {
+        self.rows.selected().map(|row| row.project)
+    }
+}
+
+impl SelectRows for ResearchTable {
+    type Row = ResearchRow;
+
+    fn row_selection(&self) -> &RowSelection<ResearchRow> {
+        &self.rows
+    }
+
+    fn row_selection_mut(&mut self) -> &mut RowSelection<ResearchRow> {
+        &mut self.rows
+    }
+}
@@ -0,0 +1,54 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Location as Loc, Rooms};
+use crate::game::components::{Bed, Location, Wall, Workbench};
+use specs::prelude::*;
+use std::collections::BTreeSet;
+
+/// Keeps the [Rooms] up to date as walls are built and furniture is placed.
+pub struct RoomSystem {}
+
+impl RoomSystem {
+    pub fn new() -> RoomSystem {
+        RoomSystem {}
+    }
+}
+
+impl<'a> System<'a> for RoomSystem {
+    type SystemData = (
+        Write<'a, Rooms>,
+        ReadStorage<'a, Bed>,
+        ReadStorage<'a, Location>,
+        ReadStorage<'a, Wall>,
+        ReadStorage<'a, Workbench>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (mut rooms, beds, locations, walls, workbenches) = sys_data;
+        let wall_tiles: BTreeSet<Loc> = (&locations, &walls)
+            .join()
+            .map(|(l, _)| l.location)
+            .collect();
+        let furniture: Vec<Loc> = (&locations, &workbenches)
+            .join()
+            .map(|(l, _)| l.location)
+            .chain((&locations, &beds).join().map(|(l, _)| l.location))
+            .collect();
+        rooms.update(wall_tiles, &furniture);
+    }
+}
@@ -0,0 +1,134 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+/// Rows of a full-screen table and the selected one, the selection always
+/// stays within the table.
+#[derive(Clone, Debug)]
+pub struct RowSelection<T> {
+    rows: Vec<T>,
+    selected: usize,
+}
+
+impl<T> RowSelection<T> {
+    pub fn rows(&self) -> &[T] {
+        &self.rows
+    }
+
+    pub fn rows_mut(&mut self) -> &mut [T] {
+        &mut self.rows
+    }
+
+    pub fn select_above(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        }
+    }
+
+    pub fn select_below(&mut self) {
+        if self.selected + 1 < self.rows.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn selected(&self) -> Option<&T> {
+        self.rows.get(self.selected)
+    }
+
+    pub fn selected_mut(&mut self) -> Option<&mut T> {
+        self.rows.get_mut(self.selected)
+    }
+
+    pub fn selected_row(&self) -> usize {
+        self.selected
+    }
+
+    /// Replace the rows, keeping the selection within the table.
+    pub fn set_rows(&mut self, rows: Vec<T>) {
+        self.rows = rows;
+        self.selected = self.selected.min(self.rows.len().saturating_sub(1));
+    }
+}
+
+impl<T> Default for RowSelection<T> {
+    fn default() -> Self {
+        Self {
+            rows: vec![],
+            selected: 0,
+        }
+    }
+}
+
+/// A table whose rows are selected one at a time with a [RowSelection].
+pub trait SelectRows {
+    type Row;
+
+    fn row_selection(&self) -> &RowSelection<Self::Row>;
+
+    fn row_selection_mut(&mut self) -> &mut RowSelection<Self::Row>;
+
+    fn rows(&self) -> &[Self::Row] {
+        self.row_selection().rows()
+    }
+
+    fn select_above(&mut self) {
+        self.row_selection_mut().select_above();
+    }
+
+    fn select_below(&mut self) {
+        self.row_selection_mut().select_below();
+    }
+
+    fn selected_row(&self) -> usize {
+        self.row_selection().selected_row()
+    }
+
+    fn set_rows(&mut self, rows: Vec<Self::Row>) {
+        self.row_selection_mut().set_rows(rows);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_stays_within_the_rows() {
+        let mut selection = RowSelection::default();
+        assert_eq!(None, selection.selected());
+        selection.set_rows(vec!['a', 'b', 'c']);
+        selection.select_above();
+        assert_eq!(Some(&'a'), selection.selected());
+        for _ in 0..5 {
+            selection.select_below();
+        }
+        assert_eq!(Some(&'c'), selection.selected());
+        assert_eq!(2, selection.selected_row());
+    }
+
+    #[test]
+    fn removing_rows_moves_the_selection_up() {
+        let mut selection = RowSelection::default();
+        selection.set_rows(vec!['a', 'b', 'c']);
+        selection.select_below();
+        selection.select_below();
+        selection.set_rows(vec!['a']);
+        assert_eq!(0, selection.selected_row());
+        assert_eq!(Some(&'a'), selection.selected());
+        selection.set_rows(vec![]);
+        assert_eq!(None, selection.selected());
+    }
+}
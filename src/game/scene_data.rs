@@ -15,11 +15,26 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::{Location, Rectangle, Time, VisibleObject};
-use crate::game::LogEntry;
-use std::collections::{BTreeMap, VecDeque};
+use crate::data::{Location, Rectangle, RoomQuality, Time, VisibleObject};
+use crate::game::{
+    EquipmentTable, FactionTable, LogEntry, RelationshipTable, ResearchTable,
+    ScheduleEditor, Screen, TradeTable, WorkTable,
+};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
 use std::sync::Mutex;
 
+/// The data of each full-screen view, only the open one is copied to
+/// [SceneData].
+pub struct ScreenTables<'a> {
+    pub equipment: &'a EquipmentTable,
+    pub factions: &'a FactionTable,
+    pub relationships: &'a RelationshipTable,
+    pub research: &'a ResearchTable,
+    pub schedules: &'a ScheduleEditor,
+    pub trade: &'a TradeTable,
+    pub work_priorities: &'a WorkTable,
+}
+
 /// Data structure used to pass UI data from rendering system to the UI.
 ///
 /// The game world is not supposed to know UI details.  Details like window
@@ -28,10 +43,22 @@ use std::sync::Mutex;
 #[derive(Debug, Default)]
 pub struct SceneData {
     cursor_location: Location,
+    equipment_table: EquipmentTable,
+    faction_table: FactionTable,
     game_time_str: String,
     game_log: Mutex<VecDeque<LogEntry>>,
+    inspector_lines: Vec<String>,
     objects: BTreeMap<Location, Vec<(u16, VisibleObject)>>,
+    quest_lines: Vec<String>,
+    relationship_table: RelationshipTable,
+    research_table: ResearchTable,
+    room_overlay: BTreeMap<Location, RoomQuality>,
+    schedule_editor: ScheduleEditor,
+    screen: Screen,
+    selection: BTreeSet<Location>,
     time: Time,
+    trade_table: TradeTable,
+    work_table: WorkTable,
 }
 
 impl SceneData {
@@ -103,10 +130,19 @@ impl SceneData {
         }
     }
 
+    pub fn get_equipment_table(&self) -> &EquipmentTable {
+        &self.equipment_table
+    }
+
     pub fn get_game_time_str(&self) -> &str {
         &self.game_time_str
     }
 
+    /// Descriptions of the entities under the cursor.
+    pub fn get_inspector_lines(&self) -> &[String] {
+        &self.inspector_lines
+    }
+
     pub fn get_objects_for_location(
         &self,
         location: &Location,
@@ -119,6 +155,53 @@ impl SceneData {
             .collect()
     }
 
+    /// Active quests, as shown in the side panel.
+    pub fn get_quest_lines(&self) -> &[String] {
+        &self.quest_lines
+    }
+
+    /// Quality of the room at `location`, if rooms are highlighted.
+    pub fn get_room_overlay(
+        &self,
+        location: &Location,
+    ) -> Option<RoomQuality> {
+        self.room_overlay.get(location).copied()
+    }
+
+    pub fn get_faction_table(&self) -> &FactionTable {
+        &self.faction_table
+    }
+
+    pub fn get_relationship_table(&self) -> &RelationshipTable {
+        &self.relationship_table
+    }
+
+    pub fn get_research_table(&self) -> &ResearchTable {
+        &self.research_table
+    }
+
+    pub fn get_schedule_editor(&self) -> &ScheduleEditor {
+        &self.schedule_editor
+    }
+
+    pub fn get_screen(&self) -> Screen {
+        self.screen
+    }
+
+    pub fn get_trade_table(&self) -> &TradeTable {
+        &self.trade_table
+    }
+
+    pub fn get_work_table(&self) -> &WorkTable {
+        &self.work_table
+    }
+
+    /// Whether `location` is in the selection rectangle or has a selected
+    /// colonist.
+    pub fn is_selected(&self, location: &Location) -> bool {
+        self.selection.contains(location)
+    }
+
     pub fn t_millis(&self) -> u64 {
         self.time.t_millis()
     }
@@ -141,6 +224,48 @@ impl SceneData {
         self.game_time_str = game_time_str;
     }
 
+    pub fn set_inspector_lines(&mut self, inspector_lines: Vec<String>) {
+        self.inspector_lines = inspector_lines;
+    }
+
+    pub fn set_quest_lines(&mut self, quest_lines: Vec<String>) {
+        self.quest_lines = quest_lines;
+    }
+
+    pub fn set_selection(&mut self, selection: BTreeSet<Location>) {
+        self.selection = selection;
+    }
+
+    pub fn set_room_overlay(
+        &mut self,
+        room_overlay: BTreeMap<Location, RoomQuality>,
+    ) {
+        self.room_overlay = room_overlay;
+    }
+
+    /// Set the open screen along with the data it shows.
+    pub fn set_screen(&mut self, screen: Screen, tables: ScreenTables) {
+        self.screen = screen;
+        match screen {
+            Screen::Equipment => {
+                self.equipment_table = tables.equipment.clone()
+            }
+            Screen::Factions => self.faction_table = tables.factions.clone(),
+            Screen::Map => (),
+            Screen::Relationships => {
+                self.relationship_table = tables.relationships.clone()
+            }
+            Screen::Research => self.research_table = tables.research.clone(),
+            Screen::Schedules => {
+                self.schedule_editor = tables.schedules.clone()
+            }
+            Screen::Trade => self.trade_table = tables.trade.clone(),
+            Screen::WorkPriorities => {
+                self.work_table = tables.work_priorities.clone()
+            }
+        }
+    }
+
     fn set_objects_for_location(
         &mut self,
         location: Location,
@@ -0,0 +1,215 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Activity, Direction, Schedule, HOURS_PER_DAY};
+use crate::game::RowSelection;
+use crate::menu::Menu;
+use specs::Entity;
+use std::collections::BTreeSet;
+use std::slice::Iter;
+
+/// A colonist and their schedule, as shown in the [ScheduleEditor].
+#[derive(Clone, Debug)]
+pub struct ScheduleRow {
+    pub entity: Entity,
+    pub name: String,
+    pub schedule: Schedule,
+}
+
+/// Full-screen editor of colonist schedules.
+///
+/// Colonists are rows and hours of the day are columns.  The activity to
+/// paint with is selected through [Menu].  While painting, every cell the
+/// selection moves over gets the selected activity.  If some colonists are
+/// marked, all of them are painted at once.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleEditor {
+    brush: usize,
+    is_painting: bool,
+    marked: BTreeSet<Entity>,
+    rows: RowSelection<ScheduleRow>,
+    selected_hour: u8,
+}
+
+impl ScheduleEditor {
+    pub fn is_marked(&self, entity: Entity) -> bool {
+        self.marked.contains(&entity)
+    }
+
+    pub fn is_painting(&self) -> bool {
+        self.is_painting
+    }
+
+    pub fn move_selection(&mut self, direction: Direction) {
+        let last_hour = HOURS_PER_DAY as u8 - 1;
+        match direction {
+            Direction::North => self.rows.select_above(),
+            Direction::East => {
+                self.selected_hour = (self.selected_hour + 1).min(last_hour)
+            }
+            Direction::South => self.rows.select_below(),
+            Direction::West => {
+                self.selected_hour = self.selected_hour.saturating_sub(1)
+            }
+        }
+    }
+
+    pub fn rows(&self) -> &[ScheduleRow] {
+        self.rows.rows()
+    }
+
+    pub fn selected_hour(&self) -> u8 {
+        self.selected_hour
+    }
+
+    pub fn selected_row(&self) -> usize {
+        self.rows.selected_row()
+    }
+
+    /// Replace the rows, keeping the selection within the editor.  Marks
+    /// of colonists that are gone are dropped.
+    pub fn set_rows(&mut self, rows: Vec<ScheduleRow>) {
+        self.marked
+            .retain(|entity| rows.iter().any(|row| row.entity == *entity));
+        self.rows.set_rows(rows);
+    }
+
+    /// Colonists whose schedules are painted.
+    pub fn targets(&self) -> Vec<Entity> {
+        if self.marked.is_empty() {
+            self.rows
+                .selected()
+                .map(|row| row.entity)
+                .into_iter()
+                .collect()
+        } else {
+            self.marked.iter().cloned().collect()
+        }
+    }
+
+    /// Mark or unmark the colonist in the selected row.
+    pub fn toggle_mark(&mut self) {
+        if let Some(row) = self.rows.selected() {
+            if !self.marked.remove(&row.entity) {
+                self.marked.insert(row.entity);
+            }
+        }
+    }
+
+    pub fn toggle_painting(&mut self) {
+        self.is_painting = !self.is_painting;
+    }
+}
+
+pub struct ScheduleEditorIterator<'a> {
+    i: Iter<'a, Activity>,
+}
+
+impl<'a> Iterator for ScheduleEditorIterator<'a> {
+    type Item = &'a Activity;
+
+    fn next(&mut self) -> Option<&'a Activity> {
+        self.i.next()
+    }
+}
+
+impl<'a> Menu<'a> for ScheduleEditor {
+    type Item = Activity;
+    type IterMenu = ScheduleEditorIterator<'a>;
+
+    fn iter(&self) -> Self::IterMenu {
+        ScheduleEditorIterator {
+            i: Activity::ALL.iter(),
+        }
+    }
+
+    fn select_next(&mut self) {
+        self.brush = (self.brush + 1) % Activity::ALL.len();
+    }
+
+    fn select_previous(&mut self) {
+        self.brush =
+            (self.brush + Activity::ALL.len() - 1) % Activity::ALL.len();
+    }
+
+    fn is_selected(&self, item: &Activity) -> bool {
+        self.selected() == *item
+    }
+
+    fn selected(&self) -> Activity {
+        Activity::ALL[self.brush]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::prelude::*;
+
+    fn editor_with_rows(n: usize) -> ScheduleEditor {
+        let mut world = World::new();
+        let rows = (0..n)
+            .map(|idx| ScheduleRow {
+                entity: world.create_entity().build(),
+                name: format!("Colonist {}", idx),
+                schedule: Schedule::default(),
+            })
+            .collect();
+        let mut editor = ScheduleEditor::default();
+        editor.set_rows(rows);
+        editor
+    }
+
+    #[test]
+    fn selection_stays_within_the_day_and_the_rows() {
+        let mut editor = editor_with_rows(2);
+        editor.move_selection(Direction::West);
+        editor.move_selection(Direction::North);
+        assert_eq!((0, 0), (editor.selected_hour(), editor.selected_row()));
+        for _ in 0..30 {
+            editor.move_selection(Direction::East);
+            editor.move_selection(Direction::South);
+        }
+        assert_eq!((23, 1), (editor.selected_hour(), editor.selected_row()));
+    }
+
+    #[test]
+    fn brush_cycles_through_activities() {
+        let mut editor = editor_with_rows(1);
+        assert_eq!(Activity::ALL[0], editor.selected());
+        editor.select_previous();
+        assert_eq!(*Activity::ALL.last().unwrap(), editor.selected());
+        editor.select_next();
+        editor.select_next();
+        assert_eq!(Activity::ALL[1], editor.selected());
+    }
+
+    #[test]
+    fn marked_colonists_are_painted_together() {
+        let mut editor = editor_with_rows(3);
+        let first = editor.rows()[0].entity;
+        let last = editor.rows()[2].entity;
+        assert_eq!(vec![first], editor.targets());
+        editor.toggle_mark();
+        editor.move_selection(Direction::South);
+        editor.move_selection(Direction::South);
+        editor.toggle_mark();
+        assert_eq!(vec![first, last], editor.targets());
+        editor.toggle_mark();
+        assert_eq!(vec![first], editor.targets());
+    }
+}
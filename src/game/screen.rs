@@ -0,0 +1,37 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+/// Full-screen views that are shown instead of the map.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Screen {
+    Equipment,
+    Factions,
+    #[default]
+    Map,
+    Relationships,
+    Research,
+    Schedules,
+    Trade,
+    WorkPriorities,
+}
+
+impl Screen {
+    /// Open `screen`, or go back to the map if it is already open.
+    pub fn toggle(&mut self, screen: Screen) {
+        *self = if *self == screen { Screen::Map } else { screen };
+    }
+}
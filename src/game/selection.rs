@@ -0,0 +1,82 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{Location, Rectangle};
+use specs::Entity;
+
+/// Colonists selected with the cursor, to be drafted and given orders
+/// together.
+///
+/// Selecting starts by anchoring a corner of the selection rectangle at the
+/// cursor, the opposite corner follows the cursor until the selection is
+/// finished.
+#[derive(Clone, Debug, Default)]
+pub struct Selection {
+    anchor: Option<Location>,
+    selected: Vec<Entity>,
+}
+
+impl Selection {
+    /// Rectangle being selected, `None` unless a selection is started.
+    pub fn area(&self, cursor: Location) -> Option<Rectangle> {
+        self.anchor.map(|anchor| Rectangle::new(anchor, cursor))
+    }
+
+    /// Replace the selection with `entities` and stop selecting.
+    pub fn finish(&mut self, entities: Vec<Entity>) {
+        self.anchor = None;
+        self.selected = entities;
+    }
+
+    pub fn selected(&self) -> &[Entity] {
+        &self.selected
+    }
+
+    pub fn start(&mut self, cursor: Location) {
+        self.anchor = Some(cursor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::prelude::*;
+
+    #[test]
+    fn area_spans_from_the_anchor_to_the_cursor() {
+        let mut selection = Selection::default();
+        assert_eq!(None, selection.area(Location::origin()));
+        selection.start(Location::new(2, 3));
+        assert_eq!(
+            Some(Rectangle::new(Location::new(-1, 0), Location::new(2, 3))),
+            selection.area(Location::new(-1, 0))
+        );
+    }
+
+    #[test]
+    fn finishing_replaces_the_selection() {
+        let mut world = World::new();
+        let entity = world.create_entity().build();
+        let mut selection = Selection::default();
+        selection.start(Location::origin());
+        selection.finish(vec![entity]);
+        assert_eq!(None, selection.area(Location::origin()));
+        assert_eq!(&[entity], selection.selected());
+        selection.start(Location::origin());
+        selection.finish(vec![]);
+        assert!(selection.selected().is_empty());
+    }
+}
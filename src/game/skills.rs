@@ -0,0 +1,57 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::Time;
+use crate::game::components::{Colonist, Worker};
+use crate::game::{GameLog, LogEntry};
+use specs::prelude::*;
+
+/// Skills that are not practiced decay as game time passes, and lose
+/// levels once their experience runs out.
+pub struct SkillSystem {}
+
+impl SkillSystem {
+    pub fn new() -> SkillSystem {
+        SkillSystem {}
+    }
+}
+
+impl<'a> System<'a> for SkillSystem {
+    type SystemData = (
+        Read<'a, GameLog>,
+        Read<'a, Time>,
+        ReadStorage<'a, Colonist>,
+        WriteStorage<'a, Worker>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (game_log, time, colonists, mut workers) = sys_data;
+        let game_dt = time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        for (worker, colonist) in (&mut workers, colonists.maybe()).join() {
+            let name = colonist.map_or("Someone", |c| c.name.as_str());
+            for (skill, level) in worker.skills.decay(game_dt) {
+                game_log.push(LogEntry::new(
+                    format!("{}'s {} skill dropped to {}", name, skill, level)
+                        .as_str(),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    InteractionKind, Location as Loc, Random, ThoughtKind, Time,
+    MILLIS_PER_HOUR,
+};
+use crate::game::components::{Colonist, Location, Mood, Social, Worker};
+use crate::game::{GameLog, Job, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+
+/// Colonists this close to each other can interact.
+const INTERACTION_DISTANCE: u32 = 2;
+/// Chance of two colonists close to each other interacting in a game hour.
+const INTERACTION_CHANCE_PER_HOUR: f64 = 0.5;
+
+/// Colonists close to each other interact and change their opinions of each
+/// other.  Friends and rivals affect the mood.
+pub struct SocialSystem {}
+
+impl SocialSystem {
+    pub fn new() -> SocialSystem {
+        SocialSystem {}
+    }
+}
+
+impl<'a> System<'a> for SocialSystem {
+    type SystemData = SocialSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        let game_dt = sys_data.time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        let chance = INTERACTION_CHANCE_PER_HOUR * f64::from(game_dt)
+            / f64::from(MILLIS_PER_HOUR);
+        let awake: Vec<(Entity, Loc)> = (
+            &sys_data.entities,
+            &sys_data.locations,
+            &sys_data.socials,
+            &sys_data.workers,
+        )
+            .join()
+            .filter(|(_, _, _, worker)| {
+                !matches!(worker.job, Some(Job::Sleep))
+            })
+            .map(|(entity, location, _, _)| (entity, location.location))
+            .collect();
+        for (idx, &(a, a_loc)) in awake.iter().enumerate() {
+            for &(b, b_loc) in awake[idx + 1..].iter() {
+                if a_loc.distance_to(b_loc) <= INTERACTION_DISTANCE
+                    && sys_data.random.chance(chance)
+                {
+                    sys_data.interact(a, b);
+                }
+            }
+        }
+        for (social, mood) in (&sys_data.socials, &mut sys_data.moods).join() {
+            if !social.opinions.friends().is_empty() {
+                mood.thoughts.add(ThoughtKind::HasFriend);
+            }
+            if !social.opinions.rivals().is_empty() {
+                mood.thoughts.add(ThoughtKind::HasRival);
+            }
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct SocialSystemData<'a> {
+    entities: Entities<'a>,
+    game_log: Read<'a, GameLog>,
+    random: Write<'a, Random>,
+    time: Read<'a, Time>,
+    colonists: ReadStorage<'a, Colonist>,
+    locations: ReadStorage<'a, Location>,
+    moods: WriteStorage<'a, Mood>,
+    socials: WriteStorage<'a, Social>,
+    workers: ReadStorage<'a, Worker>,
+}
+
+impl<'a> SocialSystemData<'a> {
+    /// `a` starts an interaction with `b`, both change their opinions.
+    fn interact(&mut self, a: Entity, b: Entity) {
+        let opinion = self.socials.get(a).map_or(0, |s| s.opinions.get(b));
+        let kind = InteractionKind::table(opinion).choose(self.random.unit());
+        let delta = kind.opinion_change();
+        if let Some(social) = self.socials.get_mut(a) {
+            social.opinions.change(b, delta);
+        }
+        if let Some(social) = self.socials.get_mut(b) {
+            social.opinions.change(a, delta);
+        }
+        if let (Some(a), Some(b)) =
+            (self.colonists.get(a), self.colonists.get(b))
+        {
+            self.game_log.push(LogEntry::new(
+                format!("{} {} {}", a.name, kind, b.name).as_str(),
+            ));
+        }
+    }
+}
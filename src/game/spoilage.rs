@@ -0,0 +1,166 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{
+    indoor_temperature, outdoor_temperature, spoilage_rate, ItemKind, Rooms,
+    Time,
+};
+use crate::game::components::{Item, Location};
+use crate::game::{GameLog, LogEntry};
+use specs::prelude::*;
+
+/// Decays the freshness of food and corpses lying on the map, and discards
+/// rotten food.  Items the weather harms lose durability outdoors, and are
+/// discarded once they fall apart.  Nothing decays while the game is
+/// paused.
+pub struct SpoilageSystem {}
+
+impl SpoilageSystem {
+    pub fn new() -> SpoilageSystem {
+        SpoilageSystem {}
+    }
+}
+
+impl<'a> System<'a> for SpoilageSystem {
+    type SystemData = (
+        Entities<'a>,
+        Read<'a, GameLog>,
+        Read<'a, Rooms>,
+        Read<'a, Time>,
+        WriteStorage<'a, Item>,
+        ReadStorage<'a, Location>,
+    );
+
+    fn run(&mut self, sys_data: Self::SystemData) {
+        let (entities, game_log, rooms, time, mut items, locations) = sys_data;
+        let game_dt = time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        let outdoor = outdoor_temperature(*time);
+        let outdoor_rate = spoilage_rate(outdoor, true);
+        let indoor_rate = spoilage_rate(indoor_temperature(outdoor), false);
+        let mut rotten: Vec<Entity> = vec![];
+        let mut ruined: Vec<Entity> = vec![];
+        for (entity, item, loc) in (&entities, &mut items, &locations).join() {
+            let is_indoors = rooms.is_indoors(loc.location);
+            let weathering = item
+                .kind
+                .weathering_days()
+                .filter(|_| !is_indoors)
+                .zip(item.durability.as_mut());
+            if let Some((weathering_days, durability)) = weathering {
+                durability.wear(game_dt, weathering_days);
+                if durability.is_broken() {
+                    ruined.push(entity);
+                    continue;
+                }
+            }
+            let shelf_life_days = match item.kind.shelf_life_days() {
+                Some(shelf_life_days) => shelf_life_days,
+                None => continue,
+            };
+            let rate = if is_indoors {
+                indoor_rate
+            } else {
+                outdoor_rate
+            };
+            if let Some(freshness) = item.freshness.as_mut() {
+                freshness.decay(game_dt, shelf_life_days, rate);
+                // Rotten corpses stay until they are buried or cremated.
+                if freshness.is_rotten() && item.kind != ItemKind::Corpse {
+                    game_log.push(LogEntry::new(
+                        format!(
+                            "{} x{} rotted away",
+                            item.kind, item.quantity
+                        )
+                        .as_str(),
+                    ));
+                    rotten.push(entity);
+                }
+            }
+        }
+        for entity in ruined {
+            if let Some(item) = items.remove(entity) {
+                game_log.push(LogEntry::new(
+                    format!(
+                        "{} x{} fell apart in the weather",
+                        item.kind, item.quantity
+                    )
+                    .as_str(),
+                ));
+            }
+            entities.delete(entity).unwrap();
+        }
+        for entity in rotten {
+            items.remove(entity);
+            entities.delete(entity).unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{Location as Loc, MILLIS_PER_DAY};
+
+    /// Run the system once over a log and a corpse lying at the origin,
+    /// returning the log's durability and the corpse's freshness.
+    fn spoil(game_dt: u32, is_indoors: bool) -> (u8, u8) {
+        let mut world = World::new();
+        let mut system = SpoilageSystem::new();
+        System::setup(&mut system, &mut world.res);
+        world.write_resource::<Time>().set_game_dt(game_dt);
+        if is_indoors {
+            world.write_resource::<Rooms>().toggle_roof(Loc::new(0, 0));
+        }
+        let log = world
+            .create_entity()
+            .with(Item::new(ItemKind::Log, 1))
+            .with(Location::new(Loc::new(0, 0)))
+            .build();
+        let corpse = world
+            .create_entity()
+            .with(Item::new(ItemKind::Corpse, 1))
+            .with(Location::new(Loc::new(0, 0)))
+            .build();
+        system.run_now(&world.res);
+        let items = world.read_storage::<Item>();
+        (
+            items.get(log).unwrap().durability.unwrap().percent(),
+            items.get(corpse).unwrap().freshness.unwrap().percent(),
+        )
+    }
+
+    #[test]
+    fn items_outdoors_weather_and_corpses_rot() {
+        let (durability, freshness) = spoil(MILLIS_PER_DAY, false);
+        assert!(durability < 100);
+        assert!(freshness < 100);
+    }
+
+    #[test]
+    fn items_indoors_do_not_weather() {
+        let (durability, _) = spoil(MILLIS_PER_DAY, true);
+        assert_eq!(100, durability);
+    }
+
+    #[test]
+    fn nothing_decays_while_paused() {
+        assert_eq!((100, 100), spoil(0, false));
+    }
+}
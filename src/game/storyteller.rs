@@ -0,0 +1,221 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    arrival_point, herd_size, resource_drops, wanderer, Backgrounds, Biome,
+    Biomes, BodyPart, Direction, InjuryKind, Location as Loc, Names, Random,
+    Rectangle, Rooms, StoryEvent, Storyteller, Time, VisibleObject,
+};
+use crate::game::components::{
+    Animal, Colonist, Combatant, Health, Item, Location, Movement, Renderable,
+};
+use crate::game::{create_colonist, Chronicle, Cursor, GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+
+const ANIMAL_Z_INDEX: u16 = 4;
+/// How far supplies land from the middle of the colony.
+const DROP_DISTANCE: i32 = 6;
+/// How far a herd spreads out around its arrival point.
+const HERD_SPREAD: i32 = 3;
+const ITEM_Z_INDEX: u16 = 3;
+const MAX_SICKNESS: i32 = 20;
+const MIN_SICKNESS: i32 = 10;
+
+/// Asks the [Storyteller] for an event every game day and makes it happen.
+/// Raids are launched by the raid system.
+pub struct StorytellerSystem;
+
+impl StorytellerSystem {
+    pub fn new() -> StorytellerSystem {
+        StorytellerSystem
+    }
+}
+
+impl<'a> System<'a> for StorytellerSystem {
+    type SystemData = StorytellerSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        let map = match sys_data.cursor.boundaries() {
+            Some(map) if sys_data.time.game_dt_millis() > 0 => map,
+            _ => return,
+        };
+        let day = sys_data.time.game_time_days();
+        let wealth = sys_data.chronicle.wealth();
+        let event =
+            match sys_data.storyteller.tell(day, wealth, &mut sys_data.random)
+            {
+                Some(event) => event,
+                None => return,
+            };
+        match event {
+            StoryEvent::Herd => sys_data.herd(map),
+            StoryEvent::Disease => sys_data.disease(),
+            StoryEvent::Wanderer => sys_data.wanderer(map),
+            StoryEvent::ResourceDrop => sys_data.resource_drop(),
+            StoryEvent::Raid => (),
+        }
+    }
+}
+
+#[derive(SystemData)]
+pub struct StorytellerSystemData<'a> {
+    entities: Entities<'a>,
+    backgrounds: Read<'a, Backgrounds>,
+    biomes: Read<'a, Biomes>,
+    chronicle: Write<'a, Chronicle>,
+    cursor: Read<'a, Cursor>,
+    game_log: Read<'a, GameLog>,
+    lazy_update: Read<'a, LazyUpdate>,
+    names: Read<'a, Names>,
+    random: Write<'a, Random>,
+    rooms: Read<'a, Rooms>,
+    storyteller: Write<'a, Storyteller>,
+    time: Read<'a, Time>,
+    colonists: ReadStorage<'a, Colonist>,
+    healths: WriteStorage<'a, Health>,
+}
+
+impl<'a> StorytellerSystemData<'a> {
+    /// A free tile around `loc`, `loc` itself if there is none.
+    fn free_tile_around(
+        &mut self,
+        map: Rectangle,
+        loc: Loc,
+        spread: i32,
+    ) -> Loc {
+        let tile = loc.move_by(
+            self.random.range(-spread, spread),
+            self.random.range(-spread, spread),
+        );
+        if map.contains(tile) && !self.rooms.is_blocked(tile) {
+            tile
+        } else {
+            loc
+        }
+    }
+
+    /// Wild animals of a single kind wander in from a map edge.
+    fn herd(&mut self, map: Rectangle) {
+        let biome = *self.random.pick(Biome::ALL).unwrap();
+        if self.biomes.random_tile(biome, &mut self.random).is_none() {
+            return;
+        }
+        let kind = biome.animals().choose(self.random.unit());
+        let edge = *self.random.pick(Direction::ALL).unwrap();
+        let arrival = arrival_point(map, edge, &mut self.random);
+        let size = herd_size(&mut self.random);
+        for _ in 0..size {
+            let loc = self.free_tile_around(map, arrival, HERD_SPREAD);
+            self.lazy_update
+                .create_entity(&self.entities)
+                .with(Animal::new(kind))
+                .with(Combatant::default())
+                .with(Health::default())
+                .with(Location::new(loc))
+                .with(Movement::new(kind.stats().millis_per_tile))
+                .with(Renderable::new(
+                    VisibleObject::Animal(kind),
+                    ANIMAL_Z_INDEX,
+                ))
+                .build();
+        }
+        self.game_log.push(LogEntry::new(
+            format!(
+                "A herd of {} animals ({}) wandered in from the {}.",
+                size, kind, edge
+            )
+            .as_str(),
+        ));
+    }
+
+    /// A colonist who is not downed falls sick.
+    fn disease(&mut self) {
+        let healthy: Vec<Entity> =
+            (&self.entities, &self.colonists, &self.healths)
+                .join()
+                .filter(|(_, _, health)| !health.is_downed)
+                .map(|(entity, ..)| entity)
+                .collect();
+        let entity = match self.random.pick(&healthy) {
+            Some(entity) => *entity,
+            None => return,
+        };
+        let severity = self.random.range(MIN_SICKNESS, MAX_SICKNESS) as f32;
+        if let Some(health) = self.healths.get_mut(entity) {
+            health.injuries.add(
+                BodyPart::Torso,
+                InjuryKind::Sickness,
+                severity,
+            );
+        }
+        if let Some(colonist) = self.colonists.get(entity) {
+            let message = format!("{} fell sick.", colonist.name);
+            self.game_log.push(LogEntry::alert(message.as_str()));
+            self.chronicle
+                .record(self.time.game_time_days(), message.as_str());
+        }
+    }
+
+    /// Someone arrives from a map edge and joins the colony.
+    fn wanderer(&mut self, map: Rectangle) {
+        let (name, background) =
+            wanderer(&self.backgrounds, &self.names, &mut self.random);
+        let edge = *self.random.pick(Direction::ALL).unwrap();
+        let arrival = arrival_point(map, edge, &mut self.random);
+        create_colonist(
+            &self.entities,
+            &self.lazy_update,
+            &name,
+            background,
+            arrival,
+        );
+        let message = format!(
+            "{} wandered in from the {} and joined the colony.",
+            name, edge
+        );
+        self.game_log.push(LogEntry::alert(message.as_str()));
+        self.chronicle
+            .record(self.time.game_time_days(), message.as_str());
+    }
+
+    /// Supplies are found near the middle of the colony.
+    fn resource_drop(&mut self) {
+        let (kind, quantity) = resource_drops().choose(self.random.unit());
+        let loc = Loc::origin().move_by(
+            self.random.range(-DROP_DISTANCE, DROP_DISTANCE),
+            self.random.range(-DROP_DISTANCE, DROP_DISTANCE),
+        );
+        let loc = if self.rooms.is_blocked(loc) {
+            Loc::origin()
+        } else {
+            loc
+        };
+        self.lazy_update
+            .create_entity(&self.entities)
+            .with(Item::new(kind, quantity))
+            .with(Location::new(loc))
+            .with(Renderable::new(VisibleObject::Item(kind), ITEM_Z_INDEX))
+            .build();
+        self.game_log.push(LogEntry::new(
+            format!(
+                "Supplies were found at {}, {}: {} {}.",
+                loc.x, loc.y, quantity, kind
+            )
+            .as_str(),
+        ));
+    }
+}
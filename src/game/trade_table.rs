@@ -0,0 +1,239 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{ItemKind, TraderKind};
+use crate::game::{RowSelection, SelectRows};
+use std::collections::BTreeMap;
+
+/// Goods of a kind the colony or the caravan has, as shown in the
+/// [TradeTable].
+#[derive(Clone, Debug)]
+pub struct TradeRow {
+    pub kind: ItemKind,
+    /// Items in the stockpiles, less the ones owed to the caravan.
+    pub colony: u16,
+    pub caravan: u16,
+    /// Silver the colony pays for one item.
+    pub buy_price: u32,
+    /// Silver the colony gets for one item.
+    pub sell_price: u32,
+    /// Items to buy if positive, items to sell if negative.
+    pub offer: i32,
+}
+
+/// Full-screen table of the goods of the colony and the camping caravan.
+///
+/// Goods are rows, selected through [SelectRows].  The offer of the
+/// selected row is raised to buy and lowered to sell, silver makes up the
+/// difference.
+#[derive(Clone, Debug, Default)]
+pub struct TradeTable {
+    rows: RowSelection<TradeRow>,
+    trader: Option<TraderKind>,
+    colony_silver: u32,
+    caravan_silver: u32,
+}
+
+impl TradeTable {
+    /// Silver the colony pays for the offers, negative if it gets paid.
+    pub fn balance(&self) -> i64 {
+        self.rows
+            .rows()
+            .iter()
+            .map(|row| {
+                if row.offer > 0 {
+                    i64::from(row.offer) * i64::from(row.buy_price)
+                } else {
+                    i64::from(row.offer) * i64::from(row.sell_price)
+                }
+            })
+            .sum()
+    }
+
+    /// Buy one more of the selected goods, or sell one less.
+    pub fn buy_more(&mut self) {
+        if let Some(row) = self.rows.selected_mut() {
+            if row.offer < i32::from(row.caravan) {
+                row.offer += 1;
+            }
+        }
+    }
+
+    /// Whether both sides have the silver to pay for the offers.
+    pub fn can_afford(&self) -> bool {
+        let balance = self.balance();
+        balance <= i64::from(self.colony_silver)
+            && -balance <= i64::from(self.caravan_silver)
+    }
+
+    pub fn caravan_silver(&self) -> u32 {
+        self.caravan_silver
+    }
+
+    pub fn clear_offers(&mut self) {
+        for row in self.rows.rows_mut().iter_mut() {
+            row.offer = 0;
+        }
+    }
+
+    pub fn colony_silver(&self) -> u32 {
+        self.colony_silver
+    }
+
+    /// Sell one more of the selected goods, or buy one less.
+    pub fn sell_more(&mut self) {
+        if let Some(row) = self.rows.selected_mut() {
+            if -row.offer < i32::from(row.colony) {
+                row.offer -= 1;
+            }
+        }
+    }
+
+    /// Replace the goods, keeping the offers that can still be made and
+    /// the selection within the table.
+    pub fn set_goods(
+        &mut self,
+        trader: TraderKind,
+        mut rows: Vec<TradeRow>,
+        colony_silver: u32,
+        caravan_silver: u32,
+    ) {
+        let offers: BTreeMap<ItemKind, i32> = self
+            .rows
+            .rows()
+            .iter()
+            .filter(|_| self.trader == Some(trader))
+            .map(|row| (row.kind, row.offer))
+            .collect();
+        for row in rows.iter_mut() {
+            row.offer = offers
+                .get(&row.kind)
+                .copied()
+                .unwrap_or_default()
+                .clamp(-i32::from(row.colony), i32::from(row.caravan));
+        }
+        self.rows.set_rows(rows);
+        self.trader = Some(trader);
+        self.colony_silver = colony_silver;
+        self.caravan_silver = caravan_silver;
+    }
+
+    pub fn trader(&self) -> Option<TraderKind> {
+        self.trader
+    }
+}
+
+impl SelectRows for TradeTable {
+    type Row = TradeRow;
+
+    fn row_selection(&self) -> &RowSelection<TradeRow> {
+        &self.rows
+    }
+
+    fn row_selection_mut(&mut self) -> &mut RowSelection<TradeRow> {
+        &mut self.rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(kind: ItemKind, colony: u16, caravan: u16) -> TradeRow {
+        TradeRow {
+            kind,
+            colony,
+            caravan,
+            buy_price: 10,
+            sell_price: 6,
+            offer: 0,
+        }
+    }
+
+    #[test]
+    fn offers_are_limited_by_the_goods_at_hand() {
+        let mut table = TradeTable::default();
+        table.set_goods(
+            TraderKind::BulkGoods,
+            vec![row(ItemKind::Log, 1, 2), row(ItemKind::Wool, 0, 0)],
+            100,
+            100,
+        );
+        for _ in 0..3 {
+            table.buy_more();
+        }
+        assert_eq!(2, table.rows()[0].offer);
+        for _ in 0..5 {
+            table.sell_more();
+        }
+        assert_eq!(-1, table.rows()[0].offer);
+        table.select_below();
+        table.buy_more();
+        table.sell_more();
+        assert_eq!(0, table.rows()[1].offer);
+    }
+
+    #[test]
+    fn silver_pays_the_balance() {
+        let mut table = TradeTable::default();
+        table.set_goods(
+            TraderKind::BulkGoods,
+            vec![row(ItemKind::Log, 5, 5), row(ItemKind::Wool, 5, 5)],
+            25,
+            10,
+        );
+        table.buy_more();
+        table.buy_more();
+        table.select_below();
+        table.sell_more();
+        assert_eq!(14, table.balance());
+        assert!(table.can_afford());
+        table.select_above();
+        table.buy_more();
+        assert_eq!(24, table.balance());
+        table.buy_more();
+        assert!(!table.can_afford());
+        table.clear_offers();
+        assert_eq!(0, table.balance());
+    }
+
+    #[test]
+    fn offers_are_kept_while_the_goods_last() {
+        let mut table = TradeTable::default();
+        table.set_goods(
+            TraderKind::BulkGoods,
+            vec![row(ItemKind::Log, 5, 5)],
+            0,
+            0,
+        );
+        table.buy_more();
+        table.buy_more();
+        table.set_goods(
+            TraderKind::BulkGoods,
+            vec![row(ItemKind::Log, 5, 1)],
+            0,
+            0,
+        );
+        assert_eq!(1, table.rows()[0].offer);
+        table.set_goods(
+            TraderKind::Exotic,
+            vec![row(ItemKind::Log, 5, 1)],
+            0,
+            0,
+        );
+        assert_eq!(0, table.rows()[0].offer);
+    }
+}
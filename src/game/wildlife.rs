@@ -0,0 +1,566 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+use crate::data::{
+    AnimalKind, AnimalState, Biome, Biomes, ItemKind, Location as Loc, Random,
+    Rooms, Season, Temperament, Time, VisibleObject, MILLIS_PER_DAY,
+    MILLIS_PER_MINUTE,
+};
+use crate::game::components::{
+    Animal, Colonist, Combatant, Corpse, Health, Item, Location, Movement,
+    Pen, Renderable,
+};
+use crate::game::{items, GameLog, LogEntry};
+use shred_derive::*;
+use specs::prelude::*;
+use std::collections::BTreeMap;
+
+/// Aggressive wild animals attack colonists coming this close.
+const AGGRESSION_DISTANCE: u32 = 2;
+const ANIMAL_Z_INDEX: u16 = 4;
+/// Chance of an animal with a mate nearby having young in a game day of its
+/// breeding season.
+const BREEDING_CHANCE_PER_DAY: f64 = 0.2;
+/// How far fleeing animals run from the colonist that scared them.
+const FLEE_DISTANCE: i32 = 8;
+/// Hunger of tame animals in a game day.
+const FOOD_PER_DAY: f32 = 0.5;
+/// Food tame animals find in a game day of grazing on grass.
+const GRAZING_FOOD_PER_DAY: f32 = 2.0;
+const ITEM_Z_INDEX: u16 = 3;
+const MAX_GRAZING_MINUTES: i32 = 180;
+const MIN_GRAZING_MINUTES: i32 = 30;
+/// Fraction of the carrying capacity populated when the map is generated.
+const INITIAL_POPULATION: f64 = 0.5;
+/// Animals of the same kind this close to each other can breed.
+const MATE_DISTANCE: u32 = 10;
+/// Pen tiles it takes to keep a single tame animal.
+const PEN_TILES_PER_ANIMAL: usize = 4;
+const WANDER_ATTEMPTS: usize = 4;
+/// How far animals wander between grazing spots.
+const WANDER_DISTANCE: i32 = 6;
+
+/// Wild animals graze, wander around their biome, run away from colonists
+/// or attack them, and have young in their breeding season, as long as the
+/// biome can feed them.  Tame animals stay in the pens, get hungry and
+/// produce.  Downed animals die.
+pub struct WildlifeSystem {
+    is_populated: bool,
+}
+
+impl WildlifeSystem {
+    pub fn new() -> WildlifeSystem {
+        WildlifeSystem {
+            is_populated: false,
+        }
+    }
+}
+
+impl<'a> System<'a> for WildlifeSystem {
+    type SystemData = WildlifeSystemData<'a>;
+
+    fn run(&mut self, mut sys_data: Self::SystemData) {
+        if !self.is_populated {
+            if sys_data.biomes.is_empty() {
+                // Wait for the map to be generated.
+                return;
+            }
+            sys_data.populate();
+            self.is_populated = true;
+        }
+        let game_dt = sys_data.time.game_dt_millis();
+        if game_dt == 0 {
+            return;
+        }
+        sys_data.remove_dead();
+        let colonists: Vec<(Entity, Loc)> = (
+            &sys_data.entities,
+            &sys_data.colonists,
+            &sys_data.healths,
+            &sys_data.locations,
+        )
+            .join()
+            .filter(|(_, _, health, _)| !health.is_downed)
+            .map(|(entity, _, _, location)| (entity, location.location))
+            .collect();
+        let pen_tiles: Vec<Loc> = (&sys_data.pens, &sys_data.locations)
+            .join()
+            .map(|(_, location)| location.location)
+            .filter(|l| !sys_data.rooms.is_blocked(*l))
+            .collect();
+        let animals: Vec<(Entity, Loc)> =
+            (&sys_data.entities, &sys_data.animals, &sys_data.locations)
+                .join()
+                .map(|(entity, _, location)| (entity, location.location))
+                .collect();
+        for &(entity, loc) in animals.iter() {
+            let nearest_colonist = colonists
+                .iter()
+                .copied()
+                .min_by_key(|(_, c)| c.distance_to(loc));
+            sys_data.provoke(entity, loc, nearest_colonist);
+            let nearest_colonist = nearest_colonist.map(|(_, c)| c);
+            sys_data.behave(
+                entity,
+                loc,
+                nearest_colonist,
+                &pen_tiles,
+                game_dt,
+            );
+            sys_data.keep(entity, loc, game_dt);
+        }
+        sys_data.breed(&animals, pen_tiles.len(), game_dt);
+    }
+}
+
+#[derive(SystemData)]
+pub struct WildlifeSystemData<'a> {
+    entities: Entities<'a>,
+    biomes: Read<'a, Biomes>,
+    game_log: Read<'a, GameLog>,
+    random: Write<'a, Random>,
+    rooms: Read<'a, Rooms>,
+    time: Read<'a, Time>,
+    animals: WriteStorage<'a, Animal>,
+    colonists: ReadStorage<'a, Colonist>,
+    combatants: WriteStorage<'a, Combatant>,
+    corpses: WriteStorage<'a, Corpse>,
+    healths: WriteStorage<'a, Health>,
+    items: WriteStorage<'a, Item>,
+    locations: WriteStorage<'a, Location>,
+    movements: WriteStorage<'a, Movement>,
+    pens: ReadStorage<'a, Pen>,
+    renderables: WriteStorage<'a, Renderable>,
+}
+
+impl<'a> WildlifeSystemData<'a> {
+    /// Spawn the starting wildlife of each biome.
+    fn populate(&mut self) {
+        for biome in Biome::ALL {
+            let capacity = self.biomes.carrying_capacity(*biome) as f64;
+            let animals = biome.animals();
+            for _ in 0..(capacity * INITIAL_POPULATION).round() as usize {
+                let kind = animals.choose(self.random.unit());
+                let tile = self.biomes.random_tile(*biome, &mut self.random);
+                if let Some(loc) = tile.filter(|l| !self.rooms.is_blocked(*l))
+                {
+                    self.spawn(Animal::new(kind), loc);
+                }
+            }
+        }
+    }
+
+    fn spawn(&mut self, animal: Animal, loc: Loc) {
+        let kind = animal.kind;
+        self.entities
+            .build_entity()
+            .with(animal, &mut self.animals)
+            .with(Combatant::default(), &mut self.combatants)
+            .with(Health::default(), &mut self.healths)
+            .with(Location::new(loc), &mut self.locations)
+            .with(
+                Movement::new(kind.stats().millis_per_tile),
+                &mut self.movements,
+            )
+            .with(
+                Renderable::new(VisibleObject::Animal(kind), ANIMAL_Z_INDEX),
+                &mut self.renderables,
+            )
+            .build();
+    }
+
+    /// Aggressive wild animals attack colonists coming too close.
+    fn provoke(
+        &mut self,
+        entity: Entity,
+        loc: Loc,
+        nearest_colonist: Option<(Entity, Loc)>,
+    ) {
+        let is_aggressive = self.animals.get(entity).is_some_and(|a| {
+            !a.is_tame && a.kind.stats().temperament == Temperament::Aggressive
+        });
+        let colonist = match nearest_colonist {
+            Some((colonist, c))
+                if is_aggressive
+                    && c.distance_to(loc) <= AGGRESSION_DISTANCE =>
+            {
+                colonist
+            }
+            _ => return,
+        };
+        if let Some(combatant) = self.combatants.get_mut(entity) {
+            if combatant.target.is_none() {
+                combatant.target = Some(colonist);
+            }
+        }
+    }
+
+    /// Downed animals die, leaving their corpse behind.
+    fn remove_dead(&mut self) {
+        let dead: Vec<Entity> = (&self.entities, &self.animals, &self.healths)
+            .join()
+            .filter(|(_, _, health)| health.is_downed)
+            .map(|(entity, _, _)| entity)
+            .collect();
+        for entity in dead {
+            let (kind, loc) = match (
+                self.animals.remove(entity),
+                self.locations.remove(entity),
+            ) {
+                (Some(a), Some(l)) => (a.kind, l.location),
+                _ => continue,
+            };
+            self.combatants.remove(entity);
+            self.healths.remove(entity);
+            self.movements.remove(entity);
+            self.renderables.remove(entity);
+            self.entities.delete(entity).unwrap();
+            self.entities
+                .build_entity()
+                .with(Item::new(ItemKind::Corpse, 1), &mut self.items)
+                .with(Corpse::of_animal(kind), &mut self.corpses)
+                .with(Location::new(loc), &mut self.locations)
+                .with(
+                    Renderable::new(
+                        VisibleObject::Item(ItemKind::Corpse),
+                        ITEM_Z_INDEX,
+                    ),
+                    &mut self.renderables,
+                )
+                .build();
+            self.game_log
+                .push(LogEntry::combat(format!("The {} died", kind).as_str()));
+        }
+    }
+
+    /// Run away from a colonist coming too close, otherwise graze for a
+    /// while and then wander to another spot.  Tame animals and animals
+    /// being handled do not run away, tame animals wander within the pens.
+    /// Animals that are fighting are moved by the
+    /// [CombatSystem](crate::game::CombatSystem).
+    fn behave(
+        &mut self,
+        entity: Entity,
+        loc: Loc,
+        nearest_colonist: Option<Loc>,
+        pen_tiles: &[Loc],
+        game_dt: u32,
+    ) {
+        let (animal, movement) = match (
+            self.animals.get_mut(entity),
+            self.movements.get_mut(entity),
+        ) {
+            (Some(animal), Some(movement)) => (animal, movement),
+            _ => return,
+        };
+        if self
+            .combatants
+            .get(entity)
+            .is_some_and(|c| c.target.is_some())
+        {
+            return;
+        }
+        let stats = animal.kind.stats();
+        let threat = nearest_colonist.filter(|c| {
+            !animal.is_tame
+                && animal.designation.is_none()
+                && c.distance_to(loc) < stats.temperament.flee_distance()
+        });
+        if let Some(threat) = threat {
+            if animal.state != AnimalState::Fleeing
+                || movement.destination.is_none()
+            {
+                animal.state = AnimalState::Fleeing;
+                movement.destination =
+                    flee_destination(&self.biomes, &self.rooms, loc, threat);
+            }
+            return;
+        }
+        match animal.state {
+            AnimalState::Grazing => {
+                animal.state_millis =
+                    animal.state_millis.saturating_sub(game_dt);
+                if animal.state_millis == 0 {
+                    let (biomes, rooms) = (&self.biomes, &self.rooms);
+                    let random = &mut self.random;
+                    let destination =
+                        if animal.is_tame && !pen_tiles.is_empty() {
+                            random.pick(pen_tiles).copied()
+                        } else {
+                            (0..WANDER_ATTEMPTS)
+                                .map(|_| {
+                                    loc.move_by(
+                                        random.range(
+                                            -WANDER_DISTANCE,
+                                            WANDER_DISTANCE,
+                                        ),
+                                        random.range(
+                                            -WANDER_DISTANCE,
+                                            WANDER_DISTANCE,
+                                        ),
+                                    )
+                                })
+                                .find(|l| {
+                                    biomes.get(*l) == Some(stats.biome)
+                                        && !rooms.is_blocked(*l)
+                                })
+                        };
+                    match destination {
+                        Some(destination) => {
+                            animal.state = AnimalState::Wandering;
+                            movement.destination = Some(destination);
+                        }
+                        None => {
+                            animal.state_millis = grazing_millis(random);
+                        }
+                    }
+                }
+            }
+            AnimalState::Wandering | AnimalState::Fleeing => {
+                if movement.destination.is_none() {
+                    animal.state = AnimalState::Grazing;
+                    animal.state_millis = grazing_millis(&mut self.random);
+                }
+            }
+        }
+    }
+
+    /// Tame animals get hungry, find food grazing on grass unless it is
+    /// winter, and produce while they are fed.  Starving animals go wild.
+    fn keep(&mut self, entity: Entity, loc: Loc, game_dt: u32) {
+        let days = game_dt as f32 / MILLIS_PER_DAY as f32;
+        let can_graze = self.biomes.get(loc) == Some(Biome::Meadow)
+            && self.time.season() != Season::Winter;
+        let animal = match self.animals.get_mut(entity) {
+            Some(animal) if animal.is_tame => animal,
+            _ => return,
+        };
+        let grazing = if can_graze && animal.state == AnimalState::Grazing {
+            days * GRAZING_FOOD_PER_DAY
+        } else {
+            0.0
+        };
+        animal.food = (animal.food - days * FOOD_PER_DAY + grazing).min(1.0);
+        if animal.food <= 0.0 {
+            animal.food = 0.0;
+            animal.is_tame = false;
+            animal.designation = None;
+            let kind = animal.kind;
+            self.game_log.push(LogEntry::new(
+                format!("The hungry {} went wild", kind).as_str(),
+            ));
+            return;
+        }
+        let product = match animal.kind.stats().product {
+            Some(product) if !animal.is_hungry() => product,
+            _ => return,
+        };
+        animal.produce_millis += game_dt;
+        let interval = u32::from(product.interval_days) * MILLIS_PER_DAY;
+        if animal.produce_millis >= interval {
+            animal.produce_millis -= interval;
+            self.drop_item(loc, Item::new(product.kind, product.quantity));
+        }
+    }
+
+    /// Animals in their breeding season with a mate nearby have young, unless
+    /// their biome is already at its carrying capacity.  Tame animals are
+    /// limited by the size of the pens instead.
+    fn breed(
+        &mut self,
+        animals: &[(Entity, Loc)],
+        pen_area: usize,
+        game_dt: u32,
+    ) {
+        let season = self.time.season();
+        let year = self.time.year();
+        let chance = BREEDING_CHANCE_PER_DAY * f64::from(game_dt)
+            / f64::from(MILLIS_PER_DAY);
+        let herds: Vec<(AnimalKind, bool, Loc)> = animals
+            .iter()
+            .filter_map(|(entity, loc)| {
+                self.animals.get(*entity).map(|a| (a.kind, a.is_tame, *loc))
+            })
+            .collect();
+        // Tame animals are counted as `None`.
+        let mut population: BTreeMap<Option<Biome>, usize> = BTreeMap::new();
+        for (kind, is_tame, _) in herds.iter() {
+            let biome = Some(kind.stats().biome).filter(|_| !is_tame);
+            *population.entry(biome).or_default() += 1;
+        }
+        let mut births: Vec<(AnimalKind, bool, Loc, u8)> = vec![];
+        for (entity, loc) in animals.iter() {
+            let animal = match self.animals.get_mut(*entity) {
+                Some(animal) => animal,
+                None => continue,
+            };
+            let stats = animal.kind.stats();
+            let has_mate = herds.iter().any(|(kind, is_tame, l)| {
+                *kind == animal.kind
+                    && *is_tame == animal.is_tame
+                    && l != loc
+                    && l.distance_to(*loc) <= MATE_DISTANCE
+            });
+            let (biome, capacity) = if animal.is_tame {
+                (None, pen_area / PEN_TILES_PER_ANIMAL)
+            } else {
+                let biome = stats.biome;
+                (Some(biome), self.biomes.carrying_capacity(biome))
+            };
+            let count = population.entry(biome).or_default();
+            if stats.breeding_season != season
+                || animal.bred_in_year == Some(year)
+                || !has_mate
+                || *count >= capacity
+                || !self.random.chance(chance)
+            {
+                continue;
+            }
+            let litter =
+                (self.random.range(1, i32::from(stats.max_litter_size))
+                    as usize)
+                    .min(capacity - *count);
+            *count += litter;
+            animal.bred_in_year = Some(year);
+            births.push((animal.kind, animal.is_tame, *loc, litter as u8));
+        }
+        for (kind, is_tame, loc, litter) in births {
+            for _ in 0..litter {
+                // Young animals do not breed in the year they are born.
+                self.spawn(
+                    Animal {
+                        bred_in_year: Some(year),
+                        is_tame,
+                        ..Animal::new(kind)
+                    },
+                    loc,
+                );
+            }
+            self.game_log.push(LogEntry::new(
+                format!("A {} gave birth to {} young", kind, litter).as_str(),
+            ));
+        }
+    }
+
+    fn drop_item(&mut self, loc: Loc, item: Item) {
+        items::drop_item(
+            &self.entities,
+            &mut self.items,
+            &mut self.locations,
+            &mut self.renderables,
+            loc,
+            item,
+        );
+    }
+}
+
+/// A tile away from `threat`, as far as the map and the walls allow.
+fn flee_destination(
+    biomes: &Biomes,
+    rooms: &Rooms,
+    loc: Loc,
+    threat: Loc,
+) -> Option<Loc> {
+    let dx = (loc.x - threat.x).signum();
+    let dy = (loc.y - threat.y).signum();
+    let (dx, dy) = if dx == 0 && dy == 0 { (1, 0) } else { (dx, dy) };
+    (1..=FLEE_DISTANCE)
+        .rev()
+        .map(|d| loc.move_by(dx * d, dy * d))
+        .find(|l| biomes.get(*l).is_some() && !rooms.is_blocked(*l))
+}
+
+fn grazing_millis(random: &mut Random) -> u32 {
+    random.range(MIN_GRAZING_MINUTES, MAX_GRAZING_MINUTES) as u32
+        * MILLIS_PER_MINUTE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::MILLIS_PER_HOUR;
+
+    fn milk(world: &World) -> u16 {
+        world
+            .read_storage::<Item>()
+            .join()
+            .filter(|i| i.kind == ItemKind::Milk)
+            .map(|i| i.quantity)
+            .sum()
+    }
+
+    #[test]
+    fn tame_animals_produce_over_time() {
+        let mut world = World::new();
+        crate::game::components::register_with(&mut world);
+        let mut system = WildlifeSystem::new();
+        System::setup(&mut system, &mut world.res);
+        let loc = Loc::new(0, 0);
+        world
+            .write_resource::<Biomes>()
+            .insert(loc, Biome::Scrubland);
+        world.write_resource::<Time>().set_game_dt(MILLIS_PER_HOUR);
+        world
+            .create_entity()
+            .with(Animal {
+                is_tame: true,
+                ..Animal::new(AnimalKind::Goat)
+            })
+            .with(Location::new(loc))
+            .build();
+        let product = AnimalKind::Goat.stats().product.unwrap();
+        let hours = u32::from(product.interval_days) * MILLIS_PER_DAY
+            / MILLIS_PER_HOUR;
+        for _ in 1..hours {
+            system.run_now(&world.res);
+            world.maintain();
+        }
+        assert_eq!(0, milk(&world));
+        system.run_now(&world.res);
+        world.maintain();
+        assert_eq!(product.quantity, milk(&world));
+    }
+
+    #[test]
+    fn dead_animals_leave_a_corpse() {
+        let mut world = World::new();
+        crate::game::components::register_with(&mut world);
+        let mut system = WildlifeSystem::new();
+        System::setup(&mut system, &mut world.res);
+        let loc = Loc::new(0, 0);
+        world.write_resource::<Biomes>().insert(loc, Biome::Meadow);
+        world
+            .write_resource::<Time>()
+            .set_game_dt(MILLIS_PER_MINUTE);
+        let animal = world
+            .create_entity()
+            .with(Animal::new(AnimalKind::Deer))
+            .with(Health {
+                is_downed: true,
+                ..Health::default()
+            })
+            .with(Location::new(loc))
+            .build();
+        system.run_now(&world.res);
+        world.maintain();
+        assert!(!world.entities().is_alive(animal));
+        let corpses = world.read_storage::<Corpse>();
+        let locations = world.read_storage::<Location>();
+        let (corpse, location) = (&corpses, &locations).join().next().unwrap();
+        assert_eq!(Some(AnimalKind::Deer), corpse.animal);
+        assert_eq!(loc, location.location);
+    }
+}
@@ -0,0 +1,138 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{WorkPriorities, WorkType};
+use crate::game::{RowSelection, SelectRows};
+use crate::menu::Menu;
+use specs::Entity;
+use std::slice::Iter;
+
+/// A colonist and their work priorities, as shown in the [WorkTable].
+#[derive(Clone, Debug)]
+pub struct WorkTableRow {
+    pub entity: Entity,
+    pub name: String,
+    pub priorities: WorkPriorities,
+}
+
+/// Full-screen table of colonists and their work priorities.
+///
+/// Colonists are rows and work types are columns.  Columns are selected
+/// through [Menu], rows through [SelectRows].
+#[derive(Clone, Debug, Default)]
+pub struct WorkTable {
+    rows: RowSelection<WorkTableRow>,
+    selected_column: usize,
+}
+
+impl WorkTable {
+    /// Entity of the colonist in the selected row.
+    pub fn selected_entity(&self) -> Option<Entity> {
+        self.rows.selected().map(|row| row.entity)
+    }
+}
+
+impl SelectRows for WorkTable {
+    type Row = WorkTableRow;
+
+    fn row_selection(&self) -> &RowSelection<WorkTableRow> {
+        &self.rows
+    }
+
+    fn row_selection_mut(&mut self) -> &mut RowSelection<WorkTableRow> {
+        &mut self.rows
+    }
+}
+
+pub struct WorkTableIterator<'a> {
+    i: Iter<'a, WorkType>,
+}
+
+impl<'a> Iterator for WorkTableIterator<'a> {
+    type Item = &'a WorkType;
+
+    fn next(&mut self) -> Option<&'a WorkType> {
+        self.i.next()
+    }
+}
+
+impl<'a> Menu<'a> for WorkTable {
+    type Item = WorkType;
+    type IterMenu = WorkTableIterator<'a>;
+
+    fn iter(&self) -> Self::IterMenu {
+        WorkTableIterator {
+            i: WorkType::ALL.iter(),
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.selected_column + 1 < WorkType::ALL.len() {
+            self.selected_column += 1;
+        }
+    }
+
+    fn select_previous(&mut self) {
+        if self.selected_column > 0 {
+            self.selected_column -= 1;
+        }
+    }
+
+    fn is_selected(&self, item: &WorkType) -> bool {
+        self.selected() == *item
+    }
+
+    fn selected(&self) -> WorkType {
+        WorkType::ALL[self.selected_column]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use specs::prelude::*;
+
+    fn table_with_rows(n: usize) -> WorkTable {
+        let mut world = World::new();
+        let rows = (0..n)
+            .map(|idx| WorkTableRow {
+                entity: world.create_entity().build(),
+                name: format!("Colonist {}", idx),
+                priorities: WorkPriorities::default(),
+            })
+            .collect();
+        let mut table = WorkTable::default();
+        table.set_rows(rows);
+        table
+    }
+
+    #[test]
+    fn selection_stays_within_the_table() {
+        let mut table = table_with_rows(2);
+        table.select_previous();
+        table.select_above();
+        assert_eq!(WorkType::ALL[0], table.selected());
+        assert_eq!(0, table.selected_row());
+        for _ in 0..10 {
+            table.select_next();
+            table.select_below();
+        }
+        assert_eq!(*WorkType::ALL.last().unwrap(), table.selected());
+        assert_eq!(1, table.selected_row());
+        assert_eq!(Some(table.rows()[1].entity), table.selected_entity());
+    }
+}
@@ -17,21 +17,28 @@
 
 //! Game state
 
-use crate::data::Direction;
-use crate::game::GameEvent;
+use crate::data::{Designation, Direction, Structure, WorkbenchKind};
+use crate::game::{GameEvent, Screen};
 use crate::input::{Event, KeyCode};
 use crate::stage::game::Game;
+use crate::stage::game_over::GameOver;
 use crate::stage::main_menu::MainMenu;
+use crate::stage::party::{Party, PartyAction};
 use log::info;
 
 pub mod game;
+pub mod game_over;
 pub mod main_menu;
+pub mod party;
 
 /// Stages represent game state in a self contained manner.
 #[derive(Debug)]
 pub enum Stage {
     Game(Game),
+    GameOver(GameOver),
     MainMenu(MainMenu),
+    /// Boxed, it is much larger than the other stages.
+    Party(Box<Party>),
 }
 
 impl Stage {
@@ -50,6 +57,10 @@ impl Stage {
                 Stage::tick_main_menu(menu, dt_millis, events)
             }
             Stage::Game(game) => Stage::tick_game(game, dt_millis, events),
+            Stage::GameOver(game_over) => {
+                Stage::tick_game_over(game_over, dt_millis, events)
+            }
+            Stage::Party(party) => Stage::tick_party(party, dt_millis, events),
         }
     }
 
@@ -57,6 +68,8 @@ impl Stage {
         match self {
             Stage::MainMenu(menu) => !menu.should_exit,
             Stage::Game(_game) => true,
+            Stage::GameOver(_game_over) => true,
+            Stage::Party(_party) => true,
         }
     }
 
@@ -85,11 +98,130 @@ impl Stage {
                 Event::KeyPress(KeyCode::Spacebar, ..) => {
                     game.publish_event(GameEvent::Spacebar);
                 }
+                Event::KeyPress(KeyCode::Enter, ..) => {
+                    game.publish_event(GameEvent::Select);
+                }
+                Event::KeyPress(KeyCode::F1, ..) => {
+                    game.publish_event(GameEvent::ToggleScreen(
+                        Screen::Factions,
+                    ));
+                }
+                Event::KeyPress(KeyCode::F2, ..) => {
+                    game.publish_event(GameEvent::PlaceBlueprint(
+                        Structure::Monument,
+                    ));
+                }
+                Event::KeyPress(KeyCode::F3, ..) => {
+                    game.publish_event(GameEvent::CycleDisposal);
+                }
+                Event::KeyPress(KeyCode::F4, ..) => {
+                    game.publish_event(GameEvent::ToggleField);
+                }
+                Event::KeyPress(KeyCode::Tab, ..) => {
+                    game.publish_event(GameEvent::ToggleScreen(
+                        Screen::Research,
+                    ));
+                }
+                Event::KeyPress(KeyCode::Char, Some(c), ..) => match c {
+                    'a' => game.publish_event(GameEvent::CycleActivity),
+                    'b' => game.publish_event(GameEvent::AddBill),
+                    'c' => game.publish_event(GameEvent::PlaceBlueprint(
+                        Structure::Wall,
+                    )),
+                    'd' => game.publish_event(GameEvent::PlaceBlueprint(
+                        Structure::Bed,
+                    )),
+                    'e' => game.publish_event(GameEvent::ToggleScreen(
+                        Screen::Schedules,
+                    )),
+                    'f' => game.publish_event(GameEvent::ToggleRoof),
+                    'g' => game.publish_event(GameEvent::SelectArea),
+                    'h' => game.publish_event(GameEvent::DesignateAnimal(
+                        Designation::Tame,
+                    )),
+                    'i' => game.publish_event(GameEvent::ToggleScreen(
+                        Screen::Equipment,
+                    )),
+                    'j' => game.publish_event(GameEvent::Order),
+                    'k' => game.publish_event(GameEvent::DesignateAnimal(
+                        Designation::Slaughter,
+                    )),
+                    'l' => game.publish_event(GameEvent::ToggleScreen(
+                        Screen::Relationships,
+                    )),
+                    'm' => game.publish_event(GameEvent::CycleBillMode),
+                    'n' => game.publish_event(GameEvent::TogglePen),
+                    'o' => game.publish_event(GameEvent::PlaceWorkbench(
+                        WorkbenchKind::Stove,
+                    )),
+                    'p' => game.publish_event(GameEvent::ToggleScreen(
+                        Screen::WorkPriorities,
+                    )),
+                    'q' => game.publish_event(GameEvent::PlaceWorkbench(
+                        WorkbenchKind::Research,
+                    )),
+                    'r' => game.publish_event(GameEvent::CycleBillRecipe),
+                    's' => game.publish_event(GameEvent::ToggleStockpile),
+                    't' => game.publish_event(GameEvent::ToggleMark),
+                    'u' => game.publish_event(GameEvent::CycleDefense),
+                    'v' => game.publish_event(GameEvent::ToggleRoomOverlay),
+                    'w' => game.publish_event(GameEvent::PlaceWorkbench(
+                        WorkbenchKind::Crafting,
+                    )),
+                    'x' => game.publish_event(GameEvent::RemoveBill),
+                    'y' => game
+                        .publish_event(GameEvent::ToggleScreen(Screen::Trade)),
+                    'z' => game.publish_event(GameEvent::ToggleDraft),
+                    _ => (),
+                },
                 _ => (),
             }
         }
         game.update_world(dt_millis);
-        StageTransition::Continue
+        match game.summary() {
+            Some(summary) => {
+                info!("Game over.");
+                StageTransition::SwitchTo(Stage::GameOver(GameOver::new(
+                    summary,
+                )))
+            }
+            None => StageTransition::Continue,
+        }
+    }
+
+    fn tick_game_over<E>(
+        game_over: &mut GameOver,
+        _dt_millis: u32,
+        events: E,
+    ) -> StageTransition
+    where
+        E: Iterator<Item = Event>,
+    {
+        if game_over.handle_events(events) {
+            StageTransition::SwitchTo(Stage::MainMenu(MainMenu::new()))
+        } else {
+            StageTransition::Continue
+        }
+    }
+
+    fn tick_party<E>(
+        party: &mut Party,
+        _dt_millis: u32,
+        events: E,
+    ) -> StageTransition
+    where
+        E: Iterator<Item = Event>,
+    {
+        match party.handle_events(events) {
+            Some(PartyAction::Start) => {
+                info!("Starting new game.");
+                StageTransition::SwitchTo(Stage::Game(Game::new(party)))
+            }
+            Some(PartyAction::Back) => {
+                StageTransition::SwitchTo(Stage::MainMenu(MainMenu::new()))
+            }
+            None => StageTransition::Continue,
+        }
     }
 
     fn tick_main_menu<E>(
@@ -102,10 +234,15 @@ impl Stage {
     {
         match menu.handle_events(events) {
             Some(main_menu::Choice::NewGame) => {
-                info!("Starting new game.");
-                StageTransition::SwitchTo(Stage::Game(Game::new()))
+                StageTransition::SwitchTo(Stage::Party(Box::new(Party::new(
+                    menu.storyteller,
+                    menu.goal,
+                ))))
             }
             Some(main_menu::Choice::Credits) => unimplemented!(),
+            Some(main_menu::Choice::Storyteller | main_menu::Choice::Goal) => {
+                StageTransition::Continue
+            }
             Some(main_menu::Choice::Exit) => {
                 menu.should_exit = true;
                 StageTransition::Continue
@@ -19,11 +19,23 @@
 //!
 //! [Game] is the entry point.
 
-use crate::data::{Pause, Time};
+use crate::asset;
+use crate::data::{
+    Backgrounds, Biome, Biomes, Factions, Names, Pause, Random, RecipeBook,
+    Research, RoomOverlay, Rooms, Storyteller, TechTree, Time,
+};
 use crate::game::{
-    components, Cursor, GameEvent, GameLog, GameTimeSystem, InputSystem,
-    LogEntry, MapSystem, RenderingSystem, SceneData,
+    components, Caravan, CaravanSystem, Chronicle, ChronicleSystem,
+    CombatSystem, Cursor, DefenseSystem, EquipmentSystem, EquipmentTable,
+    FactionTable, FarmingSystem, GameEvent, GameLog, GameTimeSystem,
+    HealthSystem, InputSystem, InspectorSystem, JobSystem, LogEntry,
+    MapSystem, MoodSystem, MovementSystem, NeedsSystem, Projectiles,
+    QuestSystem, Quests, RaidSystem, RelationshipTable, RenderingSystem,
+    ResearchTable, RoomSystem, SceneData, ScheduleEditor, Screen, Selection,
+    SkillSystem, SocialSystem, SpoilageSystem, StorytellerSystem, Summary,
+    TradeTable, WildlifeSystem, WorkTable,
 };
+use crate::stage::party::Party;
 use crate::stage::StageData;
 use specs::prelude::*;
 use std::fmt::{Debug, Error, Formatter};
@@ -36,21 +48,140 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn new() -> Game {
+    /// A new game in the world of the seed of the `party`, played by its
+    /// colonists for its goal with events told by its storyteller.
+    pub fn new(party: &Party) -> Game {
         let (event_sink, event_source) = mpsc::channel::<GameEvent>();
 
         let mut world = World::new();
+        let mut random = Random::new(party.seed);
+        let names = Names::from_ron(asset::NAMES).expect("Cannot read names");
+        let mut biomes = Biomes::default();
+        for biome in Biome::ALL {
+            let name = names.region(party.culture(), *biome, &mut random);
+            biomes.set_name(*biome, name);
+        }
         // TODO: Register resources like components::register
+        world.add_resource(
+            Backgrounds::from_ron(asset::BACKGROUNDS)
+                .expect("Cannot read backgrounds"),
+        );
+        world.add_resource(biomes);
+        world.add_resource(Caravan::default());
+        world.add_resource(Chronicle::new(party.goal, &party.colony));
         world.add_resource(Cursor::default());
+        world.add_resource(EquipmentTable::default());
+        world.add_resource(FactionTable::default());
+        world.add_resource(Factions::generate(&names, &mut random));
         world.add_resource(GameLog::default());
+        world.add_resource(names);
         world.add_resource(SceneData::default());
         world.add_resource(Pause::default());
+        world.add_resource(Projectiles::default());
+        world.add_resource(Quests::default());
+        world.add_resource(random);
+        world.add_resource(
+            RecipeBook::from_ron(asset::RECIPES).expect("Cannot read recipes"),
+        );
+        world.add_resource(RelationshipTable::default());
+        world.add_resource(Research::new(
+            TechTree::from_ron(asset::RESEARCH)
+                .expect("Cannot read research projects"),
+        ));
+        world.add_resource(ResearchTable::default());
+        world.add_resource(RoomOverlay::default());
+        world.add_resource(Rooms::default());
+        world.add_resource(ScheduleEditor::default());
+        world.add_resource(Screen::default());
+        world.add_resource(Selection::default());
+        world.add_resource(Storyteller::new(party.storyteller));
         world.add_resource(Time::default());
+        world.add_resource(TradeTable::default());
+        world.add_resource(WorkTable::default());
         components::register_with(&mut world);
         let mut dispatcher = DispatcherBuilder::new()
             .with(GameTimeSystem::new(), "game_time_system", &[])
-            .with(MapSystem::new(), "map_system", &[])
+            .with(
+                MapSystem::new(party.colonists.clone(), party.seed),
+                "map_system",
+                &[],
+            )
             .with(InputSystem::new(event_source), "input_system", &[])
+            .with(NeedsSystem::new(), "needs_system", &["game_time_system"])
+            .with(RoomSystem::new(), "room_system", &["input_system"])
+            .with(SkillSystem::new(), "skill_system", &["game_time_system"])
+            .with(
+                FarmingSystem::new(),
+                "farming_system",
+                &["game_time_system"],
+            )
+            .with(
+                EquipmentSystem::new(),
+                "equipment_system",
+                &["game_time_system"],
+            )
+            .with(HealthSystem::new(), "health_system", &["game_time_system"])
+            .with(SocialSystem::new(), "social_system", &["game_time_system"])
+            .with(
+                WildlifeSystem::new(),
+                "wildlife_system",
+                &["game_time_system"],
+            )
+            .with(
+                MoodSystem::new(),
+                "mood_system",
+                &["needs_system", "social_system"],
+            )
+            .with(
+                SpoilageSystem::new(),
+                "spoilage_system",
+                &["game_time_system", "room_system"],
+            )
+            .with(
+                JobSystem::new(),
+                "job_system",
+                &[
+                    "equipment_system",
+                    "farming_system",
+                    "health_system",
+                    "mood_system",
+                    "skill_system",
+                    "spoilage_system",
+                ],
+            )
+            .with(
+                ChronicleSystem::new(),
+                "chronicle_system",
+                &["health_system", "job_system", "map_system"],
+            )
+            .with(
+                StorytellerSystem::new(),
+                "storyteller_system",
+                &["chronicle_system"],
+            )
+            .with(RaidSystem::new(), "raid_system", &["storyteller_system"])
+            .with(
+                CaravanSystem::new(),
+                "caravan_system",
+                &["job_system", "map_system"],
+            )
+            .with(
+                QuestSystem::new(),
+                "quest_system",
+                &["caravan_system", "raid_system"],
+            )
+            .with(
+                CombatSystem::new(),
+                "combat_system",
+                &["job_system", "raid_system", "wildlife_system"],
+            )
+            .with(MovementSystem::new(), "movement_system", &["combat_system"])
+            .with(DefenseSystem::new(), "defense_system", &["movement_system"])
+            .with(
+                InspectorSystem::new(),
+                "inspector_system",
+                &["defense_system", "input_system"],
+            )
             .with_thread_local(RenderingSystem::new())
             .build();
         dispatcher.setup(&mut world.res);
@@ -77,6 +208,11 @@ impl Game {
         f(&self.world.read_resource())
     }
 
+    /// Summary of the game once it is over, `None` until then.
+    pub fn summary(&self) -> Option<Summary> {
+        self.world.read_resource::<Chronicle>().summary()
+    }
+
     pub fn update_world(&mut self, dt_millis: u32) {
         self.world.write_resource::<Time>().advance_dt(dt_millis);
         self.dispatcher.dispatch(&self.world.res);
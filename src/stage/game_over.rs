@@ -0,0 +1,51 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Stage where the summary of an ended game is.
+//!
+//! [GameOver] is the entry point.
+
+use crate::game::Summary;
+use crate::input::{Event, KeyCode};
+use crate::stage::StageData;
+
+#[derive(Debug)]
+pub struct GameOver {
+    pub summary: Summary,
+}
+
+impl GameOver {
+    pub fn new(summary: Summary) -> GameOver {
+        GameOver { summary }
+    }
+
+    /// Whether the player is done reading the summary.
+    pub fn handle_events<E>(&mut self, events: E) -> bool
+    where
+        E: Iterator<Item = Event>,
+    {
+        let mut is_done = false;
+        for e in events {
+            if let Event::KeyPress(KeyCode::Enter, ..) = e {
+                is_done = true;
+            }
+        }
+        is_done
+    }
+}
+
+impl StageData for GameOver {}
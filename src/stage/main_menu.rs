@@ -19,6 +19,7 @@
 //!
 //! [MainMenu] is the entry point.
 
+use crate::data::{Goal, Personality};
 use crate::input::{Event, KeyCode};
 use crate::menu::Menu;
 use crate::stage::StageData;
@@ -28,17 +29,28 @@ use std::slice::Iter;
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Choice {
     NewGame,
+    /// Setting of the new game, changed with left and right.
+    Storyteller,
+    /// Setting of the new game, changed with left and right.
+    Goal,
     Credits,
     Exit,
 }
 
 impl Choice {
-    pub const ALL: &'static [Choice] =
-        &[Choice::NewGame, Choice::Credits, Choice::Exit];
+    pub const ALL: &'static [Choice] = &[
+        Choice::NewGame,
+        Choice::Storyteller,
+        Choice::Goal,
+        Choice::Credits,
+        Choice::Exit,
+    ];
 
     pub fn next(self) -> Option<Choice> {
         match self {
-            Choice::NewGame => Some(Choice::Credits),
+            Choice::NewGame => Some(Choice::Storyteller),
+            Choice::Storyteller => Some(Choice::Goal),
+            Choice::Goal => Some(Choice::Credits),
             Choice::Credits => Some(Choice::Exit),
             Choice::Exit => None,
         }
@@ -47,7 +59,9 @@ impl Choice {
     pub fn previous(self) -> Option<Choice> {
         match self {
             Choice::NewGame => None,
-            Choice::Credits => Some(Choice::NewGame),
+            Choice::Storyteller => Some(Choice::NewGame),
+            Choice::Goal => Some(Choice::Storyteller),
+            Choice::Credits => Some(Choice::Goal),
             Choice::Exit => Some(Choice::Credits),
         }
     }
@@ -65,6 +79,10 @@ impl Display for Choice {
 pub struct MainMenu {
     pub selected: Choice,
     pub should_exit: bool,
+    /// Storyteller of the new game.
+    pub storyteller: Personality,
+    /// Goal of the new game.
+    pub goal: Goal,
 }
 
 impl MainMenu {
@@ -72,6 +90,35 @@ impl MainMenu {
         MainMenu {
             selected: Choice::NewGame,
             should_exit: false,
+            storyteller: Personality::default(),
+            goal: Goal::default(),
+        }
+    }
+
+    /// Text of a menu item, with the current value of the settings.
+    pub fn label(&self, choice: Choice) -> String {
+        match choice {
+            Choice::NewGame => "New game".to_owned(),
+            Choice::Storyteller => {
+                format!("Storyteller: {}", self.storyteller)
+            }
+            Choice::Goal => format!("Goal: {}", self.goal),
+            _ => choice.to_string(),
+        }
+    }
+
+    /// Change the selected setting to the next or the previous value.
+    fn change_setting(&mut self, forward: bool) {
+        match self.selected {
+            Choice::Storyteller if forward => {
+                self.storyteller = self.storyteller.next()
+            }
+            Choice::Storyteller => {
+                self.storyteller = self.storyteller.previous()
+            }
+            Choice::Goal if forward => self.goal = self.goal.next(),
+            Choice::Goal => self.goal = self.goal.previous(),
+            _ => (),
         }
     }
 
@@ -84,9 +131,18 @@ impl MainMenu {
             match e {
                 Event::KeyPress(KeyCode::Up, ..) => self.select_previous(),
                 Event::KeyPress(KeyCode::Down, ..) => self.select_next(),
-                Event::KeyPress(KeyCode::Enter, ..) => {
-                    selected = Some(self.selected)
+                Event::KeyPress(KeyCode::Left, ..) => {
+                    self.change_setting(false)
+                }
+                Event::KeyPress(KeyCode::Right, ..) => {
+                    self.change_setting(true)
                 }
+                Event::KeyPress(KeyCode::Enter, ..) => match self.selected {
+                    Choice::Storyteller | Choice::Goal => {
+                        self.change_setting(true)
+                    }
+                    choice => selected = Some(choice),
+                },
                 _ => (),
             }
         }
@@ -0,0 +1,140 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Stage where the starting colonists are picked.
+//!
+//! [Party] is the entry point.
+
+use crate::asset;
+use crate::data::{
+    unique_name, Background, Backgrounds, Culture, Goal, Names, Personality,
+    Random,
+};
+use crate::input::{Event, KeyCode};
+use crate::stage::StageData;
+
+const STARTING_COLONISTS: usize = 3;
+
+/// What the player wants to do with the party.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PartyAction {
+    Start,
+    Back,
+}
+
+#[derive(Debug)]
+pub struct Party {
+    /// Storyteller of the new game.
+    pub storyteller: Personality,
+    /// Goal of the new game.
+    pub goal: Goal,
+    /// Seed of the world, the same seed gives the same map and names.
+    pub seed: u64,
+    pub colony: String,
+    pub colonists: Vec<(String, Background)>,
+    pub selected: usize,
+    /// Index of the culture the colony and the colonists are named after.
+    culture: usize,
+    backgrounds: Backgrounds,
+    names: Names,
+    random: Random,
+}
+
+impl Party {
+    pub fn new(storyteller: Personality, goal: Goal) -> Party {
+        let seed = Random::clock_seed();
+        let backgrounds = Backgrounds::from_ron(asset::BACKGROUNDS)
+            .expect("Cannot read backgrounds");
+        let names = Names::from_ron(asset::NAMES).expect("Cannot read names");
+        let mut random = Random::new(seed);
+        let culture = names.pick_culture(&mut random);
+        let mut party = Party {
+            storyteller,
+            goal,
+            seed,
+            colony: String::new(),
+            colonists: vec![],
+            selected: 0,
+            culture,
+            backgrounds,
+            names,
+            random,
+        };
+        let culture = party.names.culture(culture).expect("No cultures");
+        party.colony = culture.place(&mut party.random);
+        for _ in 0..STARTING_COLONISTS {
+            let colonist = party.generate();
+            party.colonists.push(colonist);
+        }
+        party
+    }
+
+    /// Culture the colony and the colonists are named after.
+    pub fn culture(&self) -> &Culture {
+        self.names.culture(self.culture).expect("No cultures")
+    }
+
+    /// A colonist with a name of the culture of the colony, that is not
+    /// the name of another colonist of the party.
+    fn generate(&mut self) -> (String, Background) {
+        let culture = self.names.culture(self.culture).expect("No cultures");
+        let colonists = &self.colonists;
+        let random = &mut self.random;
+        let name = unique_name(
+            || culture.person(random),
+            |name| colonists.iter().any(|(other, _)| other == name),
+        );
+        (name, self.backgrounds.generate(&mut self.random))
+    }
+
+    /// Replace the selected colonist with a newly generated one.
+    fn reroll(&mut self) {
+        if self.selected < self.colonists.len() {
+            self.colonists[self.selected] = self.generate();
+        }
+    }
+
+    pub fn handle_events<E>(&mut self, events: E) -> Option<PartyAction>
+    where
+        E: Iterator<Item = Event>,
+    {
+        let mut action: Option<PartyAction> = None;
+        for e in events {
+            match e {
+                Event::KeyPress(KeyCode::Up, ..) => {
+                    self.selected = self.selected.saturating_sub(1);
+                }
+                Event::KeyPress(KeyCode::Down, ..)
+                    if self.selected + 1 < self.colonists.len() =>
+                {
+                    self.selected += 1;
+                }
+                Event::KeyPress(KeyCode::Char, Some('r'), ..) => self.reroll(),
+                Event::KeyPress(KeyCode::Enter, ..) => {
+                    action = Some(PartyAction::Start)
+                }
+                Event::KeyPress(KeyCode::Escape, ..) => {
+                    action = Some(PartyAction::Back)
+                }
+                _ => (),
+            }
+        }
+        action
+    }
+}
+
+impl StageData for Party {}
@@ -31,8 +31,10 @@ use tcod::system::{
 };
 
 mod constants;
+mod game_over_renderer;
 mod game_renderer;
 mod main_menu_renderer;
+mod party_renderer;
 mod render;
 mod renderer;
 mod tile;
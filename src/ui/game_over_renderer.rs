@@ -0,0 +1,103 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::stage::game_over::GameOver;
+use crate::ui::render::Render;
+use std::fmt;
+use tcod::colors;
+use tcod::console::{BackgroundFlag, Console, Offscreen, TextAlignment};
+
+/// Lines between the top of the screen and the title.
+const TOP_MARGIN: i32 = 2;
+
+pub struct GameOverRenderer {
+    root: Offscreen,
+}
+
+impl GameOverRenderer {
+    pub fn new(window_width: u32, window_height: u32) -> GameOverRenderer {
+        let root = Offscreen::new(window_width as i32, window_height as i32);
+        GameOverRenderer { root }
+    }
+
+    fn print_centered(&mut self, y: i32, text: &str) {
+        let x = self.root.width() / 2;
+        self.root.print_ex(
+            x,
+            y,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            text,
+        );
+    }
+}
+
+impl fmt::Debug for GameOverRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "GameOverRenderer")
+    }
+}
+
+impl Render for GameOverRenderer {
+    type StageType = GameOver;
+
+    fn borrow_root(&self) -> &Offscreen {
+        &self.root
+    }
+
+    fn update(&mut self, game_over: &GameOver) {
+        let summary = &game_over.summary;
+        let height = self.root.height();
+        self.root.clear();
+        self.root.set_default_foreground(colors::LIGHT_YELLOW);
+        self.print_centered(TOP_MARGIN, &summary.colony);
+        self.print_centered(TOP_MARGIN + 1, &summary.outcome.to_string());
+        self.root.set_default_foreground(colors::WHITE);
+        let colonists = if summary.colonists.is_empty() {
+            "none".to_owned()
+        } else {
+            summary.colonists.join(", ")
+        };
+        let mut lines = vec![
+            format!("Days survived: {}", summary.days),
+            format!("Colonists: {}", colonists),
+        ];
+        if !summary.dead.is_empty() {
+            lines.push(format!("Died: {}", summary.dead.join(", ")));
+        }
+        lines.push(format!("Peak wealth: {:.0} silver", summary.peak_wealth));
+        let mut y = TOP_MARGIN + 3;
+        for line in lines.iter() {
+            self.print_centered(y, line);
+            y += 1;
+        }
+        y += 1;
+        self.print_centered(y, "Notable events");
+        y += 1;
+        // The latest events that fit above the footer.
+        let room = (height - 3 - y).max(0) as usize;
+        let skipped = summary.events.len().saturating_sub(room);
+        for event in summary.events.iter().skip(skipped) {
+            self.print_centered(y, event);
+            y += 1;
+        }
+        self.print_centered(
+            height - 2,
+            "Press <enter> to return to the main menu.",
+        );
+    }
+}
@@ -15,7 +15,14 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::{Location, Rectangle, VisibleObject};
+use crate::data::{
+    Location, Rectangle, Relation, VisibleObject, HOURS_PER_DAY,
+};
+use crate::game::{
+    EquipmentColumn, EquipmentTable, FactionTable, LogKind, RelationshipTable,
+    ResearchTable, ScheduleEditor, Screen, SelectRows, TradeTable, WorkTable,
+};
+use crate::menu::Menu;
 use crate::stage::game::Game;
 use crate::ui::constants::{
     BOTTOM_PANEL_HEIGHT, MAP_MIN_SIZE, SIDE_PANEL_WIDTH,
@@ -25,12 +32,30 @@ use crate::ui::tile::{self, Tile};
 use std::convert::TryFrom;
 use std::fmt;
 use tcod::colors;
-use tcod::console::{blit, Console, Offscreen, TextAlignment};
+use tcod::console::{blit, BackgroundFlag, Console, Offscreen, TextAlignment};
+
+const EQUIPMENT_NAME_WIDTH: i32 = 12;
+const EQUIPMENT_COLUMN_WIDTH: i32 = 16;
+const FACTION_NAME_WIDTH: i32 = 26;
+const FACTION_COLUMN_WIDTH: i32 = 10;
+const RELATIONSHIP_NAME_WIDTH: i32 = 12;
+const RELATIONSHIP_COLUMN_WIDTH: i32 = 30;
+const RESEARCH_NAME_WIDTH: i32 = 24;
+const RESEARCH_COLUMN_WIDTH: i32 = 12;
+const SCHEDULE_NAME_WIDTH: i32 = 11;
+const SCHEDULE_COLUMN_WIDTH: i32 = 2;
+const TRADE_NAME_WIDTH: i32 = 14;
+const TRADE_COLUMN_WIDTH: i32 = 9;
+const WORK_TABLE_NAME_WIDTH: i32 = 12;
+const WORK_TABLE_COLUMN_WIDTH: i32 = 7;
 
 pub struct GameRenderer {
     bottom_panel: Offscreen,
     map: Offscreen,
     root: Offscreen,
+    /// Full-screen tables are drawn here, covering the map and the side
+    /// panel.
+    screen: Offscreen,
     side_panel: Offscreen,
 }
 
@@ -43,10 +68,12 @@ impl GameRenderer {
         let bottom_panel =
             Offscreen::new(width as i32, (height - map_h) as i32);
         let side_panel = Offscreen::new((width - map_w) as i32, map_h as i32);
+        let screen = Offscreen::new(width as i32, map_h as i32);
         GameRenderer {
             bottom_panel,
             map,
             root,
+            screen,
             side_panel,
         }
     }
@@ -65,26 +92,44 @@ impl GameRenderer {
         }
     }
 
-    fn blit(&mut self) {
+    fn blit(&mut self, show_screen: bool) {
         let w = self.root.width();
         let h = self.root.height();
-        let mw = self.map.width();
         let mh = self.map.height();
+        if show_screen {
+            blit(
+                &self.screen,
+                (0, 0),
+                (w, mh),
+                &mut self.root,
+                (0, 0),
+                1.0,
+                1.0,
+            );
+        } else {
+            self.blit_map_and_side_panel();
+        }
         blit(
-            &self.map,
+            &self.bottom_panel,
             (0, 0),
-            (mw, mh),
+            (w, h - mh),
             &mut self.root,
-            (0, 0),
+            (0, mh),
             1.0,
             1.0,
         );
+    }
+
+    fn blit_map_and_side_panel(&mut self) {
+        let w = self.root.width();
+        let mw = self.map.width();
+        let mh = self.map.height();
         blit(
-            &self.bottom_panel,
+            &self.map,
             (0, 0),
-            (w, h - mh),
+            (mw, mh),
             &mut self.root,
-            (0, mh),
+            (0, 0),
             1.0,
             1.0,
         );
@@ -98,6 +143,630 @@ impl GameRenderer {
             1.0,
         );
     }
+
+    fn clear_screen(&mut self, title: &str) {
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for y in 0..h {
+            for x in 0..w {
+                tile::UI_BACKGROUND.put(screen, x, y, 0);
+            }
+        }
+        screen.set_default_foreground(colors::DARKEST_SEPIA);
+        screen.print_ex(
+            w / 2,
+            1,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            title,
+        );
+    }
+
+    fn draw_equipment_table(&mut self, table: &EquipmentTable) {
+        self.clear_screen("Equipment");
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for (idx, column) in table.iter().enumerate() {
+            let title = match column {
+                EquipmentColumn::Slot(slot) => slot.to_string(),
+                EquipmentColumn::Policy => "Policy".to_owned(),
+            };
+            screen.print_ex(
+                EQUIPMENT_NAME_WIDTH + idx as i32 * EQUIPMENT_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                title,
+            );
+        }
+        for (row_idx, row) in table.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            screen.print_ex(
+                1,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &row.name,
+            );
+            for (idx, column) in table.iter().enumerate() {
+                let x =
+                    EQUIPMENT_NAME_WIDTH + idx as i32 * EQUIPMENT_COLUMN_WIDTH;
+                let cell = match column {
+                    EquipmentColumn::Slot(slot) => {
+                        let wanted = row.loadout.wanted(*slot);
+                        match (row.loadout.equipped(*slot), wanted) {
+                            (Some(item), _) => format!(
+                                "{} {}%{}",
+                                item.kind,
+                                item.durability.percent(),
+                                if wanted == Some(item.kind) {
+                                    "*"
+                                } else {
+                                    ""
+                                }
+                            ),
+                            (None, Some(kind)) => format!("({})", kind),
+                            (None, None) => "-".to_owned(),
+                        }
+                    }
+                    EquipmentColumn::Policy => row.loadout.policy.to_string(),
+                };
+                let is_selected = row_idx == table.selected_row()
+                    && table.is_selected(column);
+                let (fg_color, bg_color, bg_flag) = if is_selected {
+                    (colors::WHITE, colors::RED, BackgroundFlag::Set)
+                } else {
+                    (
+                        colors::DARKEST_SEPIA,
+                        colors::BLACK,
+                        BackgroundFlag::None,
+                    )
+                };
+                for dx in 0..EQUIPMENT_COLUMN_WIDTH - 1 {
+                    screen.set_char_background(x + dx, y, bg_color, bg_flag);
+                }
+                screen.set_default_foreground(fg_color);
+                screen.print_ex(
+                    x,
+                    y,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    &cell,
+                );
+                screen.set_default_foreground(colors::DARKEST_SEPIA);
+            }
+        }
+        screen.print_ex(
+            w / 2,
+            h - 3,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "* wanted, (item) looking for the item",
+        );
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<arrows> select, <enter> change, <i> close",
+        );
+    }
+
+    fn draw_faction_table(&mut self, table: &FactionTable) {
+        self.clear_screen("Factions");
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for (idx, title) in
+            ["Goodwill", "Relation", "Culture"].iter().enumerate()
+        {
+            screen.print_ex(
+                FACTION_NAME_WIDTH + idx as i32 * FACTION_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                *title,
+            );
+        }
+        for (row_idx, row) in table.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            if row_idx == table.selected_row() {
+                for x in 0..FACTION_NAME_WIDTH + 3 * FACTION_COLUMN_WIDTH {
+                    screen.set_char_background(
+                        x,
+                        y,
+                        colors::LIGHTER_SEPIA,
+                        BackgroundFlag::Set,
+                    );
+                }
+            }
+            screen.set_default_foreground(match row.relation {
+                Relation::Hostile => colors::DARK_RED,
+                Relation::Neutral => colors::DARKEST_SEPIA,
+                Relation::Ally => colors::DARK_GREEN,
+            });
+            screen.print_ex(
+                1,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &row.name,
+            );
+            screen.print_ex(
+                FACTION_NAME_WIDTH,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                format!("{:+}", row.goodwill),
+            );
+            screen.print_ex(
+                FACTION_NAME_WIDTH + FACTION_COLUMN_WIDTH,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                row.relation.to_string(),
+            );
+            screen.print_ex(
+                FACTION_NAME_WIDTH + 2 * FACTION_COLUMN_WIDTH,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &row.culture,
+            );
+            screen.set_default_foreground(colors::DARKEST_SEPIA);
+        }
+        if let Some(row) = table.selected() {
+            let history_y = 6 + table.rows().len() as i32;
+            screen.print_ex(
+                1,
+                history_y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                "History:",
+            );
+            if row.history.is_empty() {
+                screen.print_ex(
+                    3,
+                    history_y + 1,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    "-",
+                );
+            }
+            let lines = (h - 4 - history_y).max(0) as usize;
+            for (idx, entry) in
+                row.history.iter().rev().take(lines).enumerate()
+            {
+                screen.print_ex(
+                    3,
+                    history_y + 1 + idx as i32,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    entry,
+                );
+            }
+        }
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<up/down> select, <F1> close",
+        );
+    }
+
+    fn draw_relationship_table(&mut self, table: &RelationshipTable) {
+        self.clear_screen("Relationships");
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for (idx, title) in ["Friends", "Rivals"].iter().enumerate() {
+            screen.print_ex(
+                RELATIONSHIP_NAME_WIDTH
+                    + idx as i32 * RELATIONSHIP_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                *title,
+            );
+        }
+        let describe = |opinions: &[(String, i32)]| {
+            if opinions.is_empty() {
+                "-".to_owned()
+            } else {
+                opinions
+                    .iter()
+                    .map(|(name, opinion)| format!("{} {:+}", name, opinion))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            }
+        };
+        for (row_idx, row) in table.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            screen.print_ex(
+                1,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &row.name,
+            );
+            for (idx, opinions) in
+                [&row.friends, &row.rivals].iter().enumerate()
+            {
+                screen.print_ex(
+                    RELATIONSHIP_NAME_WIDTH
+                        + idx as i32 * RELATIONSHIP_COLUMN_WIDTH,
+                    y,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    describe(opinions),
+                );
+            }
+        }
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<l> close",
+        );
+    }
+
+    fn draw_research_table(&mut self, table: &ResearchTable) {
+        self.clear_screen("Research");
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for (idx, title) in ["Progress", "Status"].iter().enumerate() {
+            screen.print_ex(
+                RESEARCH_NAME_WIDTH + idx as i32 * RESEARCH_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                *title,
+            );
+        }
+        for (row_idx, row) in table.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            let fg_color = if row.is_completed {
+                colors::DARK_GREEN
+            } else if row.is_available || row.queued.is_some() {
+                colors::DARKEST_SEPIA
+            } else {
+                colors::GREY
+            };
+            if row_idx == table.selected_row() {
+                for x in 0..RESEARCH_NAME_WIDTH + 2 * RESEARCH_COLUMN_WIDTH {
+                    screen.set_char_background(
+                        x,
+                        y,
+                        colors::LIGHTER_SEPIA,
+                        BackgroundFlag::Set,
+                    );
+                }
+            }
+            screen.set_default_foreground(fg_color);
+            screen.print_ex(
+                1 + 2 * row.depth as i32,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &row.name,
+            );
+            screen.print_ex(
+                RESEARCH_NAME_WIDTH,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                format!("{:.0}/{}", row.progress, row.cost),
+            );
+            let status = match row.queued {
+                _ if row.is_completed => "Done".to_owned(),
+                Some(0) => "Researching".to_owned(),
+                Some(position) => format!("Queued {}", position),
+                None if row.is_available => "Available".to_owned(),
+                None => "Locked".to_owned(),
+            };
+            screen.print_ex(
+                RESEARCH_NAME_WIDTH + RESEARCH_COLUMN_WIDTH,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                status,
+            );
+            screen.set_default_foreground(colors::DARKEST_SEPIA);
+        }
+        if let Some(row) = table.rows().get(table.selected_row()) {
+            let details_y = 6 + table.rows().len() as i32;
+            let needs = if row.prerequisites.is_empty() {
+                "-".to_owned()
+            } else {
+                row.prerequisites.join(", ")
+            };
+            screen.print_ex(
+                1,
+                details_y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                format!("Needs: {}", needs),
+            );
+            screen.print_rect(
+                1,
+                details_y + 1,
+                w - 2,
+                3,
+                format!("Unlocks: {}", row.unlocks.join(", ")),
+            );
+        }
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<up/down> select, <enter> queue/unqueue, <tab> close",
+        );
+    }
+
+    fn draw_schedule_editor(&mut self, editor: &ScheduleEditor) {
+        self.clear_screen("Schedules");
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for hour in (0..HOURS_PER_DAY as i32).step_by(3) {
+            screen.print_ex(
+                SCHEDULE_NAME_WIDTH + hour * SCHEDULE_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                hour.to_string(),
+            );
+        }
+        for (row_idx, row) in editor.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            let mark = if editor.is_marked(row.entity) {
+                '*'
+            } else {
+                ' '
+            };
+            screen.print_ex(
+                0,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                format!("{}{}", mark, row.name),
+            );
+            for hour in 0..HOURS_PER_DAY as u8 {
+                let activity = row.schedule.get(hour);
+                let x = SCHEDULE_NAME_WIDTH
+                    + i32::from(hour) * SCHEDULE_COLUMN_WIDTH;
+                let is_selected = row_idx == editor.selected_row()
+                    && hour == editor.selected_hour();
+                let bg_color = if is_selected {
+                    colors::RED
+                } else {
+                    tile::activity_color(activity)
+                };
+                screen.set_char_background(
+                    x,
+                    y,
+                    bg_color,
+                    BackgroundFlag::Set,
+                );
+                screen.put_char(x, y, activity.symbol(), BackgroundFlag::None);
+            }
+        }
+        let brush_y = 6 + editor.rows().len() as i32;
+        let mut x = 1;
+        for activity in editor.iter() {
+            let label = format!(" {} ", activity);
+            let bg_flag = if editor.is_selected(activity) {
+                BackgroundFlag::Set
+            } else {
+                BackgroundFlag::None
+            };
+            for dx in 0..label.len() as i32 {
+                screen.set_char_background(
+                    x + dx,
+                    brush_y,
+                    tile::activity_color(*activity),
+                    bg_flag,
+                );
+            }
+            screen.print_ex(
+                x,
+                brush_y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &label,
+            );
+            x += label.len() as i32 + 1;
+        }
+        if editor.is_painting() {
+            screen.print_ex(
+                1,
+                brush_y + 1,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                "Painting",
+            );
+        }
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<enter> paint, <a> activity, <t> mark, <e> close",
+        );
+    }
+
+    fn draw_trade_table(&mut self, table: &TradeTable) {
+        let title = table.trader().map_or("Trade".to_owned(), |trader| {
+            format!("Trade with {}", trader)
+        });
+        self.clear_screen(&title);
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        let titles = ["Colony", "Sell", "Offer", "Buy", "Caravan"];
+        screen.print_ex(
+            1,
+            3,
+            BackgroundFlag::None,
+            TextAlignment::Left,
+            "Item",
+        );
+        for (idx, title) in titles.iter().enumerate() {
+            screen.print_ex(
+                TRADE_NAME_WIDTH + idx as i32 * TRADE_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                *title,
+            );
+        }
+        for (row_idx, row) in table.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            let (fg_color, bg_flag) = if row_idx == table.selected_row() {
+                (colors::WHITE, BackgroundFlag::Set)
+            } else {
+                (colors::DARKEST_SEPIA, BackgroundFlag::None)
+            };
+            for x in 0..TRADE_NAME_WIDTH + 5 * TRADE_COLUMN_WIDTH {
+                screen.set_char_background(x, y, colors::RED, bg_flag);
+            }
+            screen.set_default_foreground(fg_color);
+            screen.print_ex(
+                1,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                row.kind.to_string(),
+            );
+            let offer = if row.offer == 0 {
+                "-".to_owned()
+            } else {
+                format!("{:+}", row.offer)
+            };
+            let cells = [
+                row.colony.to_string(),
+                row.sell_price.to_string(),
+                offer,
+                row.buy_price.to_string(),
+                row.caravan.to_string(),
+            ];
+            for (idx, cell) in cells.iter().enumerate() {
+                screen.print_ex(
+                    TRADE_NAME_WIDTH + idx as i32 * TRADE_COLUMN_WIDTH,
+                    y,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    cell,
+                );
+            }
+            screen.set_default_foreground(colors::DARKEST_SEPIA);
+        }
+        let balance = table.balance();
+        let deal = if balance > 0 {
+            format!("Colony pays {} silver", balance)
+        } else if balance < 0 {
+            format!("Caravan pays {} silver", -balance)
+        } else {
+            "Even deal".to_owned()
+        };
+        if !table.can_afford() {
+            screen.set_default_foreground(colors::RED);
+        }
+        screen.print_ex(
+            1,
+            h - 4,
+            BackgroundFlag::None,
+            TextAlignment::Left,
+            format!(
+                "Silver: colony {}, caravan {}. {}.",
+                table.colony_silver(),
+                table.caravan_silver(),
+                deal
+            ),
+        );
+        screen.set_default_foreground(colors::DARKEST_SEPIA);
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<up/down> select, <left> sell, <right> buy, <enter> trade, \
+             <t> gift, <y> close",
+        );
+    }
+
+    fn draw_work_table(&mut self, work_table: &WorkTable) {
+        self.clear_screen("Work priorities");
+        let screen = &mut self.screen;
+        let w = screen.width();
+        let h = screen.height();
+        for (idx, work) in work_table.iter().enumerate() {
+            screen.print_ex(
+                WORK_TABLE_NAME_WIDTH + idx as i32 * WORK_TABLE_COLUMN_WIDTH,
+                3,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                work.short_name(),
+            );
+        }
+        for (row_idx, row) in work_table.rows().iter().enumerate() {
+            let y = 5 + row_idx as i32;
+            screen.print_ex(
+                1,
+                y,
+                BackgroundFlag::None,
+                TextAlignment::Left,
+                &row.name,
+            );
+            for (idx, work) in work_table.iter().enumerate() {
+                let x = WORK_TABLE_NAME_WIDTH
+                    + idx as i32 * WORK_TABLE_COLUMN_WIDTH;
+                let cell = if row.priorities.is_disabled(*work) {
+                    "x".to_owned()
+                } else {
+                    row.priorities
+                        .get(*work)
+                        .map_or("-".to_owned(), |p| p.to_string())
+                };
+                let is_selected = row_idx == work_table.selected_row()
+                    && work_table.is_selected(work);
+                let (fg_color, bg_color, bg_flag) = if is_selected {
+                    (colors::WHITE, colors::RED, BackgroundFlag::Set)
+                } else {
+                    (
+                        colors::DARKEST_SEPIA,
+                        colors::BLACK,
+                        BackgroundFlag::None,
+                    )
+                };
+                for dx in 0..3 {
+                    screen.set_char_background(x + dx, y, bg_color, bg_flag);
+                }
+                screen.set_default_foreground(fg_color);
+                screen.print_ex(
+                    x + 1,
+                    y,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    &cell,
+                );
+                screen.set_default_foreground(colors::DARKEST_SEPIA);
+            }
+        }
+        screen.print_ex(
+            w / 2,
+            h - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<arrows> select, <enter> change, <p> close",
+        );
+    }
 }
 
 impl fmt::Debug for GameRenderer {
@@ -141,6 +810,24 @@ impl Render for GameRenderer {
                             t,
                         );
                     }
+                    if let Some(quality) =
+                        scene_data.get_room_overlay(&Location { x, y })
+                    {
+                        map.set_char_background(
+                            x - boundaries.min_x,
+                            y - boundaries.min_y,
+                            tile::room_overlay_color(quality),
+                            BackgroundFlag::Multiply,
+                        );
+                    }
+                    if scene_data.is_selected(&Location { x, y }) {
+                        map.set_char_background(
+                            x - boundaries.min_x,
+                            y - boundaries.min_y,
+                            tile::SELECTION_COLOR,
+                            BackgroundFlag::Multiply,
+                        );
+                    }
                 },
                 boundaries,
             );
@@ -167,8 +854,12 @@ impl Render for GameRenderer {
             }
 
             // TODO: Put the color in constants
-            bottom_panel.set_default_foreground(colors::DARKEST_SEPIA);
             scene_data.for_each_game_log(5, |(idx, msg)| {
+                bottom_panel.set_default_foreground(match msg.kind() {
+                    LogKind::Info => colors::DARKEST_SEPIA,
+                    LogKind::Combat => colors::DARKER_RED,
+                    LogKind::Alert => colors::DARKER_ORANGE,
+                });
                 bottom_panel.print_rect(0, idx as i32, w, 1, msg.contents());
             });
         });
@@ -203,6 +894,46 @@ impl Render for GameRenderer {
             );
             side_panel.print_rect(w / 2, 1, w, 1, &s);
 
+            for (i, line) in
+                scene_data.get_inspector_lines().iter().enumerate()
+            {
+                side_panel.print_rect_ex(
+                    1,
+                    3 + i as i32,
+                    w - 1,
+                    1,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    line,
+                );
+            }
+
+            let quest_lines = scene_data.get_quest_lines();
+            if !quest_lines.is_empty() {
+                let quests_y =
+                    4 + scene_data.get_inspector_lines().len() as i32;
+                side_panel.print_rect_ex(
+                    1,
+                    quests_y,
+                    w - 1,
+                    1,
+                    BackgroundFlag::None,
+                    TextAlignment::Left,
+                    "Quests",
+                );
+                for (i, line) in quest_lines.iter().enumerate() {
+                    side_panel.print_rect_ex(
+                        1,
+                        quests_y + 1 + i as i32,
+                        w - 1,
+                        1,
+                        BackgroundFlag::None,
+                        TextAlignment::Left,
+                        line,
+                    );
+                }
+            }
+
             let objects: Vec<VisibleObject> =
                 scene_data.get_objects_for_location(&cursor_location);
             for (i, obj) in objects.iter().enumerate() {
@@ -211,7 +942,32 @@ impl Render for GameRenderer {
             }
         });
 
-        self.blit();
+        let mut show_screen = true;
+        stage.with_scene_data(|scene_data| match scene_data.get_screen() {
+            Screen::Equipment => {
+                self.draw_equipment_table(scene_data.get_equipment_table())
+            }
+            Screen::Factions => {
+                self.draw_faction_table(scene_data.get_faction_table())
+            }
+            Screen::Map => show_screen = false,
+            Screen::Relationships => self
+                .draw_relationship_table(scene_data.get_relationship_table()),
+            Screen::Research => {
+                self.draw_research_table(scene_data.get_research_table())
+            }
+            Screen::Schedules => {
+                self.draw_schedule_editor(scene_data.get_schedule_editor())
+            }
+            Screen::Trade => {
+                self.draw_trade_table(scene_data.get_trade_table())
+            }
+            Screen::WorkPriorities => {
+                self.draw_work_table(scene_data.get_work_table())
+            }
+        });
+
+        self.blit(show_screen);
     }
 }
 
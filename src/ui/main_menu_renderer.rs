@@ -15,6 +15,7 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
+use crate::data::{Goal, Personality};
 use crate::menu::Menu;
 use crate::stage::main_menu::{Choice, MainMenu};
 use crate::ui::render::Render;
@@ -35,30 +36,35 @@ impl MainMenuRenderer {
         MainMenuRenderer { root, console }
     }
 
+    /// Wide enough for the menu items with any of the settings.
     fn calculate_size() -> (u32, u32) {
-        let width = Choice::ALL
-            .iter()
-            .map(|c| format!("{}", c).len())
-            .max()
-            .unwrap();
+        let mut menu = MainMenu::new();
+        let mut width = 0;
+        for storyteller in Personality::ALL {
+            for goal in Goal::ALL {
+                menu.storyteller = *storyteller;
+                menu.goal = *goal;
+                width = Choice::ALL
+                    .iter()
+                    .map(|c| menu.label(*c).len())
+                    .fold(width, usize::max);
+            }
+        }
         let height = Choice::ALL.len();
         (width as u32, height as u32)
     }
 
     fn make_menu_console(width: u32, height: u32) -> Offscreen {
-        let mut console = Offscreen::new(width as i32, height as i32);
-        for (idx, choice) in Choice::ALL.iter().enumerate() {
-            console.print(0, idx as i32, format!("{}", choice));
-        }
-        console
+        Offscreen::new(width as i32, height as i32)
     }
 
     /// Blit menu items onto the root console for this renderer.
-    fn blit(&mut self) {
+    fn blit(&mut self, storyteller: Personality) {
         let w = self.console.width();
         let h = self.console.height();
         let sw = self.root.width();
         let sh = self.root.height();
+        self.root.clear();
         blit(
             &self.console,
             (0, 0),
@@ -78,6 +84,15 @@ impl MainMenuRenderer {
             TextAlignment::Center,
             "Press <alt> + <enter> to toggle full-screen.",
         );
+        self.root.print_rect_ex(
+            sw / 2,
+            (sh + h) / 2 + 1,
+            0,
+            1,
+            BackgroundFlag::Set,
+            TextAlignment::Center,
+            storyteller.description(),
+        );
     }
 }
 
@@ -97,8 +112,10 @@ impl Render for MainMenuRenderer {
     fn update(&mut self, menu: &MainMenu) {
         let bg_flag: BackgroundFlag = BackgroundFlag::Set;
         let width: i32 = self.console.width();
+        self.console.clear();
         for (idx, choice) in menu.iter().enumerate() {
             let y: i32 = idx as i32;
+            self.console.print(0, y, menu.label(*choice));
             let (fg_color, bg_color) = if menu.is_selected(choice) {
                 (colors::WHITE, colors::RED)
             } else {
@@ -109,6 +126,6 @@ impl Render for MainMenuRenderer {
                 self.console.set_char_background(x, y, bg_color, bg_flag);
             }
         }
-        self.blit();
+        self.blit(menu.storyteller);
     }
 }
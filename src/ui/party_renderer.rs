@@ -0,0 +1,148 @@
+// © Copyright 2019-2020, Atamert Ölçgen
+//
+// This file is part of z-buffer-game.
+//
+// z-buffer-game is free software: you can redistribute it and/or modify it
+// under the terms of the GNU Lesser General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or (at your
+// option) any later version.
+//
+// z-buffer-game is distributed in the hope that it will be useful, but
+// WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+// or FITNESS FOR A PARTICULAR PURPOSE.  See the GNU Lesser General Public
+// License for more details.
+//
+// You should have received a copy of the GNU Lesser General Public License
+// along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
+
+use crate::data::{Background, SkillKind};
+use crate::stage::party::Party;
+use crate::ui::render::Render;
+use std::fmt;
+use tcod::colors;
+use tcod::console::{BackgroundFlag, Console, Offscreen, TextAlignment};
+
+/// Lines between the top of the screen and the title.
+const TOP_MARGIN: i32 = 2;
+/// Width of the list of colonists on the left.
+const NAMES_WIDTH: i32 = 16;
+
+pub struct PartyRenderer {
+    root: Offscreen,
+}
+
+impl PartyRenderer {
+    pub fn new(window_width: u32, window_height: u32) -> PartyRenderer {
+        let root = Offscreen::new(window_width as i32, window_height as i32);
+        PartyRenderer { root }
+    }
+
+    fn print(&mut self, x: i32, y: i32, text: &str) {
+        self.root.print_ex(
+            x,
+            y,
+            BackgroundFlag::None,
+            TextAlignment::Left,
+            text,
+        );
+    }
+
+    /// Everything about a background, one line each.
+    fn describe(background: &Background) -> Vec<String> {
+        let mut lines = vec![
+            format!("Age {}", background.age),
+            format!("Childhood: {}", background.childhood.title),
+            format!("Adulthood: {}", background.adulthood.title),
+            String::new(),
+            "Traits".to_owned(),
+        ];
+        for t in background.traits.iter() {
+            lines.push(format!(" {:<12}{}", t.name, t.description));
+        }
+        let disabled = background.disabled();
+        if !disabled.is_empty() {
+            let works: Vec<String> =
+                disabled.iter().map(|w| w.to_string()).collect();
+            lines.push(String::new());
+            lines.push(format!("Will not: {}", works.join(", ")));
+        }
+        lines.push(String::new());
+        lines.push("Skills".to_owned());
+        let skills = background.skills();
+        for kind in SkillKind::ALL {
+            let level = skills
+                .iter()
+                .find(|(k, _)| k == kind)
+                .map_or(0, |(_, level)| *level);
+            lines.push(format!(" {:<12}{:>2}", kind.to_string(), level));
+        }
+        lines
+    }
+}
+
+impl fmt::Debug for PartyRenderer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PartyRenderer")
+    }
+}
+
+impl Render for PartyRenderer {
+    type StageType = Party;
+
+    fn borrow_root(&self) -> &Offscreen {
+        &self.root
+    }
+
+    fn update(&mut self, party: &Party) {
+        let width = self.root.width();
+        let height = self.root.height();
+        self.root.clear();
+        self.root.set_default_foreground(colors::LIGHT_YELLOW);
+        self.root.print_ex(
+            width / 2,
+            TOP_MARGIN,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            format!(
+                "Starting party of {}, a {} colony",
+                party.colony,
+                party.culture().name
+            ),
+        );
+        self.root.set_default_foreground(colors::WHITE);
+        let top = TOP_MARGIN + 2;
+        for (idx, (name, _)) in party.colonists.iter().enumerate() {
+            let y = top + idx as i32;
+            self.print(2, y, name);
+            if idx == party.selected {
+                for x in 1..NAMES_WIDTH - 1 {
+                    self.root.set_char_background(
+                        x,
+                        y,
+                        colors::RED,
+                        BackgroundFlag::Set,
+                    );
+                }
+            }
+        }
+        if let Some((_, background)) = party.colonists.get(party.selected) {
+            for (idx, line) in Self::describe(background).iter().enumerate() {
+                self.print(NAMES_WIDTH + 2, top + idx as i32, line);
+            }
+        }
+        self.root.print_ex(
+            width / 2,
+            height - 2,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            "<r> reroll, <enter> start, <esc> back to the main menu.",
+        );
+        self.root.print_ex(
+            width / 2,
+            height - 3,
+            BackgroundFlag::None,
+            TextAlignment::Center,
+            format!("World seed {}", party.seed),
+        );
+    }
+}
@@ -16,8 +16,10 @@
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
 use crate::stage::Stage;
+use crate::ui::game_over_renderer::GameOverRenderer;
 use crate::ui::game_renderer::GameRenderer;
 use crate::ui::main_menu_renderer::MainMenuRenderer;
+use crate::ui::party_renderer::PartyRenderer;
 use crate::ui::render::Render;
 use tcod::console::Offscreen;
 
@@ -27,23 +29,33 @@ use tcod::console::Offscreen;
 #[derive(Debug)]
 pub enum Renderer {
     Game(GameRenderer),
+    GameOver(GameOverRenderer),
     MainMenu(MainMenuRenderer),
+    Party(PartyRenderer),
 }
 
 impl Renderer {
     pub fn new(stage: &Stage, width: u32, height: u32) -> Self {
         match stage {
             Stage::Game(_) => Renderer::Game(GameRenderer::new(width, height)),
+            Stage::GameOver(_) => {
+                Renderer::GameOver(GameOverRenderer::new(width, height))
+            }
             Stage::MainMenu(_) => {
                 Renderer::MainMenu(MainMenuRenderer::new(width, height))
             }
+            Stage::Party(_) => {
+                Renderer::Party(PartyRenderer::new(width, height))
+            }
         }
     }
 
     pub fn borrow_root(&self) -> &Offscreen {
         match self {
             Renderer::Game(r) => r.borrow_root(),
+            Renderer::GameOver(r) => r.borrow_root(),
             Renderer::MainMenu(r) => r.borrow_root(),
+            Renderer::Party(r) => r.borrow_root(),
         }
     }
 
@@ -52,9 +64,15 @@ impl Renderer {
             (Stage::Game(g), Renderer::Game(renderer)) => {
                 renderer.update(g);
             }
+            (Stage::GameOver(g), Renderer::GameOver(renderer)) => {
+                renderer.update(g);
+            }
             (Stage::MainMenu(m), Renderer::MainMenu(renderer)) => {
                 renderer.update(m);
             }
+            (Stage::Party(p), Renderer::Party(renderer)) => {
+                renderer.update(p);
+            }
             (s, p) => panic!("Mismatched renderer {:?} for stage {:?}", p, s),
         };
     }
@@ -63,6 +81,8 @@ impl Renderer {
         match (stage, &self) {
             (Stage::MainMenu(_), Renderer::MainMenu(_)) => true,
             (Stage::Game(_), Renderer::Game(_)) => true,
+            (Stage::GameOver(_), Renderer::GameOver(_)) => true,
+            (Stage::Party(_), Renderer::Party(_)) => true,
             _ => false,
         }
     }
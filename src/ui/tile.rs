@@ -15,7 +15,10 @@
 // You should have received a copy of the GNU Lesser General Public License
 // along with z-buffer-game.  If not, see <https://www.gnu.org/licenses/>.
 
-use crate::data::VisibleObject;
+use crate::data::{
+    Activity, AnimalKind, ItemKind, RoomQuality, Structure, VisibleObject,
+    WorkbenchKind,
+};
 use tcod::colors::{self, Color};
 use tcod::console::{BackgroundFlag, Console};
 
@@ -106,12 +109,62 @@ impl Tile for StaticTile {
 
 pub fn from_visible_object(v: VisibleObject) -> impl Tile {
     match v {
+        VisibleObject::Animal(kind) => from_animal_kind(kind),
+        VisibleObject::Bed => StaticTile {
+            glyph: '\u{e8}',
+            foreground: colors::LIGHT_AZURE,
+            background: colors::DARK_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Blueprint(structure) => StaticTile {
+            glyph: structure_glyph(structure),
+            foreground: colors::LIGHT_BLUE,
+            background: colors::BLACK,
+            background_flag: BackgroundFlag::None,
+        },
+        VisibleObject::Colonist => StaticTile {
+            glyph: '\u{40}',
+            foreground: colors::WHITE,
+            background: colors::BLACK,
+            background_flag: BackgroundFlag::None,
+        },
+        VisibleObject::Crop(_) => StaticTile {
+            glyph: '\u{e7}',
+            foreground: colors::DARK_GREEN,
+            background: colors::DARK_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Defense(structure) => StaticTile {
+            glyph: structure_glyph(structure),
+            foreground: colors::DARKER_SEPIA,
+            background: colors::BLACK,
+            background_flag: BackgroundFlag::None,
+        },
+        VisibleObject::Field => StaticTile {
+            glyph: '\u{f0}',
+            foreground: colors::DARKER_SEPIA,
+            background: colors::DARK_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
         VisibleObject::Grass => StaticTile {
             glyph: '\u{af}',
             foreground: colors::DESATURATED_GREEN,
             background: colors::DARKEST_GREEN,
             background_flag: BackgroundFlag::Set,
         },
+        VisibleObject::Grave => StaticTile {
+            glyph: '\u{c5}',
+            foreground: colors::LIGHT_GREY,
+            background: colors::DARKER_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Item(kind) => from_item_kind(kind),
+        VisibleObject::Monument => StaticTile {
+            glyph: structure_glyph(Structure::Monument),
+            foreground: colors::LIGHTEST_AMBER,
+            background: colors::DARKER_GREY,
+            background_flag: BackgroundFlag::Set,
+        },
         VisibleObject::Rock => StaticTile {
             glyph: '\u{0f}',
             foreground: colors::DARKER_GREY,
@@ -124,6 +177,36 @@ pub fn from_visible_object(v: VisibleObject) -> impl Tile {
             background: colors::LIGHT_SEPIA,
             background_flag: BackgroundFlag::Set,
         },
+        VisibleObject::Pen => StaticTile {
+            glyph: '\u{fa}',
+            foreground: colors::DARKER_GREEN,
+            background: colors::DESATURATED_GREEN,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Projectile => StaticTile {
+            glyph: '\u{07}',
+            foreground: colors::LIGHTEST_YELLOW,
+            background: colors::BLACK,
+            background_flag: BackgroundFlag::None,
+        },
+        VisibleObject::Raider => StaticTile {
+            glyph: '\u{40}',
+            foreground: colors::LIGHT_RED,
+            background: colors::BLACK,
+            background_flag: BackgroundFlag::None,
+        },
+        VisibleObject::Stockpile => StaticTile {
+            glyph: '\u{fa}',
+            foreground: colors::DARKER_SEPIA,
+            background: colors::SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Trader => StaticTile {
+            glyph: '\u{40}',
+            foreground: colors::LIGHT_GREEN,
+            background: colors::BLACK,
+            background_flag: BackgroundFlag::None,
+        },
         VisibleObject::TreeTrunk => StaticTile {
             glyph: '\u{05}',
             foreground: colors::DARK_GREEN,
@@ -136,6 +219,113 @@ pub fn from_visible_object(v: VisibleObject) -> impl Tile {
             background: colors::DARKER_BLUE,
             background_flag: BackgroundFlag::Set,
         },
+        VisibleObject::Wall => StaticTile {
+            glyph: '\u{b2}',
+            foreground: colors::DARKER_SEPIA,
+            background: colors::DARK_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Workbench(WorkbenchKind::Crafting) => StaticTile {
+            glyph: '\u{d1}',
+            foreground: colors::DARKER_ORANGE,
+            background: colors::LIGHT_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Workbench(WorkbenchKind::Research) => StaticTile {
+            glyph: '\u{d1}',
+            foreground: colors::DARKER_BLUE,
+            background: colors::LIGHT_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+        VisibleObject::Workbench(WorkbenchKind::Stove) => StaticTile {
+            glyph: '\u{e9}',
+            foreground: colors::DARKER_RED,
+            background: colors::LIGHT_SEPIA,
+            background_flag: BackgroundFlag::Set,
+        },
+    }
+}
+
+/// Background of an hour in the schedule editor.
+pub fn activity_color(activity: Activity) -> Color {
+    match activity {
+        Activity::Anything => colors::LIGHTER_SEPIA,
+        Activity::Sleep => colors::LIGHT_BLUE,
+        Activity::Work => colors::LIGHT_ORANGE,
+        Activity::Recreation => colors::LIGHT_GREEN,
+    }
+}
+
+/// Background tint of the selection rectangle and the selected colonists.
+pub const SELECTION_COLOR: Color = colors::LIGHT_BLUE;
+
+/// Tint of the room overlay, better rooms are greener.
+pub fn room_overlay_color(quality: RoomQuality) -> Color {
+    match quality {
+        RoomQuality::Cramped => colors::LIGHT_RED,
+        RoomQuality::Plain => colors::LIGHT_YELLOW,
+        RoomQuality::Decent => colors::LIGHT_GREEN,
+        RoomQuality::Impressive => colors::LIGHT_CYAN,
+    }
+}
+
+fn structure_glyph(structure: Structure) -> char {
+    match structure {
+        Structure::Barricade => '\u{f0}',
+        Structure::Bed => '\u{e8}',
+        Structure::Monument => '\u{ea}',
+        Structure::Sandbags => '\u{ef}',
+        Structure::SpikeTrap => '\u{5e}',
+        Structure::Wall => '\u{23}',
+    }
+}
+
+/// Items are drawn on top of whatever is on the tile, keeping its background.
+fn from_animal_kind(kind: AnimalKind) -> StaticTile {
+    let (glyph, foreground) = match kind {
+        AnimalKind::Boar => ('\u{62}', colors::DARKER_ORANGE),
+        AnimalKind::Deer => ('\u{64}', colors::LIGHT_ORANGE),
+        AnimalKind::Fowl => ('\u{66}', colors::LIGHT_AMBER),
+        AnimalKind::Goat => ('\u{67}', colors::LIGHTEST_GREY),
+        AnimalKind::Hare => ('\u{68}', colors::LIGHT_SEPIA),
+        AnimalKind::Sheep => ('\u{73}', colors::WHITE),
+    };
+    StaticTile {
+        glyph,
+        foreground,
+        background: colors::BLACK,
+        background_flag: BackgroundFlag::None,
+    }
+}
+
+fn from_item_kind(kind: ItemKind) -> StaticTile {
+    let (glyph, foreground) = match kind {
+        ItemKind::Berries => ('\u{f9}', colors::DARK_PURPLE),
+        ItemKind::Bow => ('\u{29}', colors::LIGHT_ORANGE),
+        ItemKind::Corpse => ('\u{26}', colors::DARK_RED),
+        ItemKind::Egg => ('\u{f8}', colors::LIGHTEST_AMBER),
+        ItemKind::FineMeal => ('\u{ec}', colors::GOLD),
+        ItemKind::Hammer => ('\u{54}', colors::LIGHT_GREY),
+        ItemKind::Helmet => ('\u{5e}', colors::LIGHT_GREY),
+        ItemKind::Knife => ('\u{2f}', colors::LIGHT_GREY),
+        ItemKind::Leather => ('\u{7e}', colors::DARK_AMBER),
+        ItemKind::Log => ('\u{3d}', colors::DARK_ORANGE),
+        ItemKind::Medicine => ('\u{2b}', colors::LIGHT_RED),
+        ItemKind::Milk => ('\u{ad}', colors::WHITE),
+        ItemKind::Parka => ('\u{5b}', colors::LIGHT_BLUE),
+        ItemKind::Plank => ('\u{f0}', colors::LIGHT_ORANGE),
+        ItemKind::RawMeat => ('\u{25}', colors::DARK_RED),
+        ItemKind::SimpleMeal => ('\u{ec}', colors::LIGHT_AMBER),
+        ItemKind::Silver => ('\u{24}', colors::SILVER),
+        ItemKind::Stone => ('\u{2a}', colors::GREY),
+        ItemKind::StoneBlock => ('\u{fe}', colors::LIGHT_GREY),
+        ItemKind::Wool => ('\u{f7}', colors::LIGHTEST_GREY),
+    };
+    StaticTile {
+        glyph,
+        foreground,
+        background: colors::BLACK,
+        background_flag: BackgroundFlag::None,
     }
 }
 